@@ -0,0 +1,157 @@
+use std::sync::Arc;
+
+use crate::client::rpc::dataset::*;
+
+/// The blocking version of [`GetEntriesStreamedResult`].
+#[derive(Debug)]
+pub struct BlockingGetEntriesStreamedResult {
+    pub keys: Vec<String>,
+    pub indexes: Vec<u32>,
+    /// For each index in `indexes`, one blob for each key in `keys`. Each blob is a serialized
+    /// tensor, which can be parsed using `OwnedDecthingsTensor::from_bytes`.
+    pub data: super::BlockingResponseBlobs,
+}
+
+/// The blocking version of [`DatasetRpc`].
+pub struct BlockingDatasetRpc {
+    inner: DatasetRpc,
+    runtime: Arc<tokio::runtime::Runtime>,
+}
+
+impl BlockingDatasetRpc {
+    pub(super) fn new(inner: DatasetRpc, runtime: Arc<tokio::runtime::Runtime>) -> Self {
+        Self { inner, runtime }
+    }
+
+    /// Returns a copy of this API which uses the given timeout for each request, instead of
+    /// `DecthingsClientOptions::timeout`. Pass None to wait without a timeout.
+    pub fn with_timeout(&self, timeout: Option<std::time::Duration>) -> Self {
+        Self::new(self.inner.with_timeout(timeout), self.runtime.clone())
+    }
+
+    /// Returns a copy of this API which retries requests according to the given policy, instead
+    /// of `DecthingsClientOptions::retry_policy`. Pass None to never retry.
+    pub fn with_retry_policy(&self, retry_policy: Option<crate::client::RetryPolicy>) -> Self {
+        Self::new(
+            self.inner.with_retry_policy(retry_policy),
+            self.runtime.clone(),
+        )
+    }
+
+    /// Returns a copy of this API which sends requests using the given protocol, instead of
+    /// `DecthingsClientOptions::default_protocol`. Methods which subscribe to events always use
+    /// WebSocket.
+    pub fn with_protocol(&self, protocol: crate::client::RpcProtocol) -> Self {
+        Self::new(self.inner.with_protocol(protocol), self.runtime.clone())
+    }
+
+    pub fn create_dataset(
+        &self,
+        params: CreateDatasetParams<'_>,
+    ) -> Result<CreateDatasetResult, crate::client::DecthingsRpcError<CreateDatasetError>> {
+        self.runtime.block_on(self.inner.create_dataset(params))
+    }
+
+    pub fn update_dataset(
+        &self,
+        params: UpdateDatasetParams<'_>,
+    ) -> Result<UpdateDatasetResult, crate::client::DecthingsRpcError<UpdateDatasetError>> {
+        self.runtime.block_on(self.inner.update_dataset(params))
+    }
+
+    pub fn delete_dataset(
+        &self,
+        params: DeleteDatasetParams<'_>,
+    ) -> Result<DeleteDatasetResult, crate::client::DecthingsRpcError<DeleteDatasetError>> {
+        self.runtime.block_on(self.inner.delete_dataset(params))
+    }
+
+    pub fn get_datasets(
+        &self,
+        params: GetDatasetsParams<'_, impl AsRef<str>>,
+    ) -> Result<GetDatasetsResult, crate::client::DecthingsRpcError<GetDatasetsError>> {
+        self.runtime.block_on(self.inner.get_datasets(params))
+    }
+
+    pub fn add_entries(
+        &self,
+        params: AddEntriesParams<'_>,
+    ) -> Result<AddEntriesResult, crate::client::DecthingsRpcError<AddEntriesError>> {
+        self.runtime.block_on(self.inner.add_entries(params))
+    }
+
+    pub fn add_entries_to_needs_review(
+        &self,
+        params: AddEntriesToNeedsReviewParams<'_>,
+    ) -> Result<
+        AddEntriesToNeedsReviewResult,
+        crate::client::DecthingsRpcError<AddEntriesToNeedsReviewError>,
+    > {
+        self.runtime
+            .block_on(self.inner.add_entries_to_needs_review(params))
+    }
+
+    pub fn finalize_needs_review_entries(
+        &self,
+        params: FinalizeNeedsReviewEntriesParams<'_>,
+    ) -> Result<
+        FinalizeNeedsReviewEntriesResult,
+        crate::client::DecthingsRpcError<FinalizeNeedsReviewEntriesError>,
+    > {
+        self.runtime
+            .block_on(self.inner.finalize_needs_review_entries(params))
+    }
+
+    pub fn get_entries(
+        &self,
+        params: GetEntriesParams<'_>,
+    ) -> Result<GetEntriesResult, crate::client::DecthingsRpcError<GetEntriesError>> {
+        self.runtime.block_on(self.inner.get_entries(params))
+    }
+
+    /// Like `get_entries`, but the entries are read while they are downloaded instead of being
+    /// kept in memory.
+    pub fn get_entries_streamed(
+        &self,
+        params: GetEntriesParams<'_>,
+    ) -> Result<BlockingGetEntriesStreamedResult, crate::client::DecthingsRpcError<GetEntriesError>>
+    {
+        let res = self
+            .runtime
+            .block_on(self.inner.get_entries_streamed(params))?;
+        Ok(BlockingGetEntriesStreamedResult {
+            keys: res.keys,
+            indexes: res.indexes,
+            data: super::BlockingResponseBlobs::new(res.data, self.runtime.clone()),
+        })
+    }
+
+    pub fn get_needs_review_entries(
+        &self,
+        params: GetNeedsReviewEntriesParams<'_>,
+    ) -> Result<
+        GetNeedsReviewEntriesResult,
+        crate::client::DecthingsRpcError<GetNeedsReviewEntriesError>,
+    > {
+        self.runtime
+            .block_on(self.inner.get_needs_review_entries(params))
+    }
+
+    pub fn remove_entries(
+        &self,
+        params: RemoveEntriesParams<'_>,
+    ) -> Result<RemoveEntriesResult, crate::client::DecthingsRpcError<RemoveEntriesError>> {
+        self.runtime.block_on(self.inner.remove_entries(params))
+    }
+
+    pub fn remove_needs_review_entries(
+        &self,
+        params: RemoveNeedsReviewEntriesParams<'_>,
+    ) -> Result<
+        RemoveNeedsReviewEntriesResult,
+        crate::client::DecthingsRpcError<RemoveNeedsReviewEntriesError>,
+    > {
+        self.runtime
+            .block_on(self.inner.remove_needs_review_entries(params))
+    }
+}
@@ -0,0 +1,221 @@
+use std::sync::Arc;
+
+use crate::client::rpc::debug::*;
+
+/// The blocking version of [`DownloadWeightDataStreamedResult`].
+#[derive(Debug)]
+pub struct BlockingDownloadWeightDataStreamedResult {
+    /// The weight keys. `data` contains one blob for each key, in the same order.
+    pub keys: Vec<String>,
+    pub data: super::BlockingResponseBlobs,
+}
+
+/// The blocking version of [`DebugRpc`].
+pub struct BlockingDebugRpc {
+    inner: DebugRpc,
+    runtime: Arc<tokio::runtime::Runtime>,
+}
+
+impl BlockingDebugRpc {
+    pub(super) fn new(inner: DebugRpc, runtime: Arc<tokio::runtime::Runtime>) -> Self {
+        Self { inner, runtime }
+    }
+
+    /// Returns a copy of this API which uses the given timeout for each request, instead of
+    /// `DecthingsClientOptions::timeout`. Pass None to wait without a timeout.
+    pub fn with_timeout(&self, timeout: Option<std::time::Duration>) -> Self {
+        Self::new(self.inner.with_timeout(timeout), self.runtime.clone())
+    }
+
+    /// Returns a copy of this API which retries requests according to the given policy, instead
+    /// of `DecthingsClientOptions::retry_policy`. Pass None to never retry.
+    pub fn with_retry_policy(&self, retry_policy: Option<crate::client::RetryPolicy>) -> Self {
+        Self::new(
+            self.inner.with_retry_policy(retry_policy),
+            self.runtime.clone(),
+        )
+    }
+
+    /// Returns a copy of this API which sends requests using the given protocol, instead of
+    /// `DecthingsClientOptions::default_protocol`. Methods which subscribe to events always use
+    /// WebSocket.
+    pub fn with_protocol(&self, protocol: crate::client::RpcProtocol) -> Self {
+        Self::new(self.inner.with_protocol(protocol), self.runtime.clone())
+    }
+
+    pub fn launch_debug_session(
+        &self,
+        params: LaunchDebugSessionParams<'_>,
+    ) -> Result<LaunchDebugSessionResult, crate::client::DecthingsRpcError<LaunchDebugSessionError>>
+    {
+        self.runtime
+            .block_on(self.inner.launch_debug_session(params))
+    }
+
+    pub fn get_debug_sessions(
+        &self,
+        params: GetDebugSessionsParams<'_, impl AsRef<str>>,
+    ) -> Result<GetDebugSessionsResult, crate::client::DecthingsRpcError<GetDebugSessionsError>>
+    {
+        self.runtime.block_on(self.inner.get_debug_sessions(params))
+    }
+
+    pub fn terminate_debug_session(
+        &self,
+        params: TerminateDebugSessionParams<'_>,
+    ) -> Result<
+        TerminateDebugSessionResult,
+        crate::client::DecthingsRpcError<TerminateDebugSessionError>,
+    > {
+        self.runtime
+            .block_on(self.inner.terminate_debug_session(params))
+    }
+
+    pub fn call_initialize_weights<D>(
+        &self,
+        params: CallInitializeWeightsParams<'_>,
+    ) -> Result<
+        CallInitializeWeightsResult,
+        crate::client::DecthingsRpcError<CallInitializeWeightsError>,
+    > {
+        self.runtime
+            .block_on(self.inner.call_initialize_weights::<D>(params))
+    }
+
+    pub fn call_instantiate_model(
+        &self,
+        params: CallInstantiateModelParams<'_, impl Clone + Into<bytes::Bytes>>,
+    ) -> Result<
+        CallInstantiateModelResult,
+        crate::client::DecthingsRpcError<CallInstantiateModelError>,
+    > {
+        self.runtime
+            .block_on(self.inner.call_instantiate_model(params))
+    }
+
+    pub fn call_train<D>(
+        &self,
+        params: CallTrainParams<'_>,
+    ) -> Result<CallTrainResult, crate::client::DecthingsRpcError<CallTrainError>> {
+        self.runtime.block_on(self.inner.call_train::<D>(params))
+    }
+
+    pub fn get_training_status(
+        &self,
+        params: DebugGetTrainingStatusParams<'_>,
+    ) -> Result<
+        DebugGetTrainingStatusResult,
+        crate::client::DecthingsRpcError<DebugGetTrainingStatusError>,
+    > {
+        self.runtime
+            .block_on(self.inner.get_training_status(params))
+    }
+
+    pub fn get_training_metrics(
+        &self,
+        params: DebugGetTrainingMetricsParams<'_>,
+    ) -> Result<
+        DebugGetTrainingMetricsResult,
+        crate::client::DecthingsRpcError<DebugGetTrainingMetricsError>,
+    > {
+        self.runtime
+            .block_on(self.inner.get_training_metrics(params))
+    }
+
+    pub fn cancel_training_session(
+        &self,
+        params: DebugCancelTrainingSessionParams<'_>,
+    ) -> Result<
+        DebugCancelTrainingSessionResult,
+        crate::client::DecthingsRpcError<DebugCancelTrainingSessionError>,
+    > {
+        self.runtime
+            .block_on(self.inner.cancel_training_session(params))
+    }
+
+    pub fn call_evaluate(
+        &self,
+        params: CallEvaluateParams<'_>,
+    ) -> Result<CallEvaluateResult, crate::client::DecthingsRpcError<CallEvaluateError>> {
+        self.runtime.block_on(self.inner.call_evaluate(params))
+    }
+
+    pub fn call_get_weights(
+        &self,
+        params: CallGetWeightsParams<'_>,
+    ) -> Result<CallGetWeightsResult, crate::client::DecthingsRpcError<CallGetWeightsError>> {
+        self.runtime.block_on(self.inner.call_get_weights(params))
+    }
+
+    pub fn download_weight_data(
+        &self,
+        params: DownloadWeightDataParams<'_, impl AsRef<str>>,
+    ) -> Result<DownloadWeightDataResult, crate::client::DecthingsRpcError<DownloadWeightDataError>>
+    {
+        self.runtime
+            .block_on(self.inner.download_weight_data(params))
+    }
+
+    /// Like `download_weight_data`, but the weight data is read while it is downloaded instead of
+    /// being kept in memory.
+    pub fn download_weight_data_streamed(
+        &self,
+        params: DownloadWeightDataParams<'_, impl AsRef<str>>,
+    ) -> Result<
+        BlockingDownloadWeightDataStreamedResult,
+        crate::client::DecthingsRpcError<DownloadWeightDataError>,
+    > {
+        let res = self
+            .runtime
+            .block_on(self.inner.download_weight_data_streamed(params))?;
+        Ok(BlockingDownloadWeightDataStreamedResult {
+            keys: res.keys,
+            data: super::BlockingResponseBlobs::new(res.data, self.runtime.clone()),
+        })
+    }
+
+    pub fn send_to_remote_inspector(
+        &self,
+        params: SendToRemoteInspectorParams<'_, impl Into<bytes::Bytes>>,
+    ) -> Result<
+        SendToRemoteInspectorResult,
+        crate::client::DecthingsRpcError<SendToRemoteInspectorError>,
+    > {
+        self.runtime
+            .block_on(self.inner.send_to_remote_inspector(params))
+    }
+
+    /// Returns an iterator over the events of the debug session `debug_session_id`. Each call to
+    /// `next` blocks until an event is received. See [`DebugRpc::events`].
+    #[cfg(feature = "events")]
+    pub fn events(
+        &self,
+        debug_session_id: impl Into<String>,
+    ) -> futures::executor::BlockingStream<crate::client::event::EventStream<DebugEvent>> {
+        futures::executor::block_on_stream(self.inner.events(debug_session_id))
+    }
+
+    #[cfg(feature = "events")]
+    pub fn subscribe_to_events(
+        &self,
+        params: DebugSubscribeToEventsParams<'_>,
+    ) -> Result<
+        DebugSubscribeToEventsResult,
+        crate::client::DecthingsRpcError<DebugSubscribeToEventsError>,
+    > {
+        self.runtime
+            .block_on(self.inner.subscribe_to_events(params))
+    }
+
+    #[cfg(feature = "events")]
+    pub fn unsubscribe_from_events(
+        &self,
+        params: DebugUnsubscribeFromEventsParams<'_>,
+    ) -> Result<
+        DebugUnsubscribeFromEventsResult,
+        crate::client::DecthingsRpcError<DebugUnsubscribeFromEventsError>,
+    > {
+        self.runtime
+            .block_on(self.inner.unsubscribe_from_events(params))
+    }
+}
@@ -0,0 +1,149 @@
+use std::sync::Arc;
+
+use crate::client::rpc::fs::*;
+
+/// The blocking version of [`FsRpc`].
+pub struct BlockingFsRpc {
+    inner: FsRpc,
+    runtime: Arc<tokio::runtime::Runtime>,
+}
+
+impl BlockingFsRpc {
+    pub(super) fn new(inner: FsRpc, runtime: Arc<tokio::runtime::Runtime>) -> Self {
+        Self { inner, runtime }
+    }
+
+    /// Returns a copy of this API which uses the given timeout for each request, instead of
+    /// `DecthingsClientOptions::timeout`. Pass None to wait without a timeout.
+    pub fn with_timeout(&self, timeout: Option<std::time::Duration>) -> Self {
+        Self::new(self.inner.with_timeout(timeout), self.runtime.clone())
+    }
+
+    /// Returns a copy of this API which retries requests according to the given policy, instead
+    /// of `DecthingsClientOptions::retry_policy`. Pass None to never retry.
+    pub fn with_retry_policy(&self, retry_policy: Option<crate::client::RetryPolicy>) -> Self {
+        Self::new(
+            self.inner.with_retry_policy(retry_policy),
+            self.runtime.clone(),
+        )
+    }
+
+    /// Returns a copy of this API which sends requests using the given protocol, instead of
+    /// `DecthingsClientOptions::default_protocol`. Methods which subscribe to events always use
+    /// WebSocket.
+    pub fn with_protocol(&self, protocol: crate::client::RpcProtocol) -> Self {
+        Self::new(self.inner.with_protocol(protocol), self.runtime.clone())
+    }
+
+    pub fn lookup(
+        &self,
+        params: LookupParams<'_, impl AsRef<[u8]>>,
+    ) -> Result<LookupResult, crate::client::DecthingsRpcError<LookupError>> {
+        self.runtime.block_on(self.inner.lookup(params))
+    }
+
+    pub fn getattr(
+        &self,
+        params: GetattrParams<'_>,
+    ) -> Result<GetattrResult, crate::client::DecthingsRpcError<GetattrError>> {
+        self.runtime.block_on(self.inner.getattr(params))
+    }
+
+    pub fn setattr(
+        &self,
+        params: SetattrParams<'_>,
+    ) -> Result<SetattrResult, crate::client::DecthingsRpcError<SetattrError>> {
+        self.runtime.block_on(self.inner.setattr(params))
+    }
+
+    pub fn mknod(
+        &self,
+        params: MknodParams<'_, impl AsRef<[u8]>>,
+    ) -> Result<MknodResult, crate::client::DecthingsRpcError<MknodError>> {
+        self.runtime.block_on(self.inner.mknod(params))
+    }
+
+    pub fn read(
+        &self,
+        params: ReadParams<'_>,
+    ) -> Result<ReadResult, crate::client::DecthingsRpcError<ReadError>> {
+        self.runtime.block_on(self.inner.read(params))
+    }
+
+    pub fn write(
+        &self,
+        params: WriteParams<'_, impl Into<bytes::Bytes>>,
+    ) -> Result<WriteResult, crate::client::DecthingsRpcError<WriteError>> {
+        self.runtime.block_on(self.inner.write(params))
+    }
+
+    pub fn symlink(
+        &self,
+        params: SymlinkParams<'_, impl AsRef<[u8]>, impl AsRef<[u8]>>,
+    ) -> Result<SymlinkResult, crate::client::DecthingsRpcError<SymlinkError>> {
+        self.runtime.block_on(self.inner.symlink(params))
+    }
+
+    pub fn readlink(
+        &self,
+        params: ReadlinkParams<'_>,
+    ) -> Result<ReadlinkResult, crate::client::DecthingsRpcError<ReadlinkError>> {
+        self.runtime.block_on(self.inner.readlink(params))
+    }
+
+    pub fn mkdir(
+        &self,
+        params: MkdirParams<'_, impl AsRef<[u8]>>,
+    ) -> Result<MkdirResult, crate::client::DecthingsRpcError<MkdirError>> {
+        self.runtime.block_on(self.inner.mkdir(params))
+    }
+
+    pub fn unlink(
+        &self,
+        params: UnlinkParams<'_, impl AsRef<[u8]>>,
+    ) -> Result<UnlinkResult, crate::client::DecthingsRpcError<UnlinkError>> {
+        self.runtime.block_on(self.inner.unlink(params))
+    }
+
+    pub fn rmdir(
+        &self,
+        params: RmdirParams<'_, impl AsRef<[u8]>>,
+    ) -> Result<RmdirResult, crate::client::DecthingsRpcError<RmdirError>> {
+        self.runtime.block_on(self.inner.rmdir(params))
+    }
+
+    pub fn rename(
+        &self,
+        params: RenameParams<'_, impl AsRef<[u8]>, impl AsRef<[u8]>>,
+    ) -> Result<RenameResult, crate::client::DecthingsRpcError<RenameError>> {
+        self.runtime.block_on(self.inner.rename(params))
+    }
+
+    pub fn link(
+        &self,
+        params: LinkParams<'_, impl AsRef<[u8]>>,
+    ) -> Result<LinkResult, crate::client::DecthingsRpcError<LinkError>> {
+        self.runtime.block_on(self.inner.link(params))
+    }
+
+    pub fn readdir(
+        &self,
+        params: ReaddirParams<'_>,
+    ) -> Result<ReaddirResult, crate::client::DecthingsRpcError<ReaddirError>> {
+        self.runtime.block_on(self.inner.readdir(params))
+    }
+
+    pub fn rmdir_all(
+        &self,
+        params: RmdirAllParams<'_, impl AsRef<[u8]>>,
+    ) -> Result<RmdirAllResult, crate::client::DecthingsRpcError<RmdirAllError>> {
+        self.runtime.block_on(self.inner.rmdir_all(params))
+    }
+
+    pub fn copy(
+        &self,
+        params: CopyParams<'_, impl AsRef<[u8]>>,
+    ) -> Result<CopyResult, crate::client::DecthingsRpcError<CopyError>> {
+        self.runtime.block_on(self.inner.copy(params))
+    }
+}
@@ -0,0 +1,68 @@
+use std::sync::Arc;
+
+use crate::client::rpc::image::*;
+
+/// The blocking version of [`ImageRpc`].
+pub struct BlockingImageRpc {
+    inner: ImageRpc,
+    runtime: Arc<tokio::runtime::Runtime>,
+}
+
+impl BlockingImageRpc {
+    pub(super) fn new(inner: ImageRpc, runtime: Arc<tokio::runtime::Runtime>) -> Self {
+        Self { inner, runtime }
+    }
+
+    /// Returns a copy of this API which uses the given timeout for each request, instead of
+    /// `DecthingsClientOptions::timeout`. Pass None to wait without a timeout.
+    pub fn with_timeout(&self, timeout: Option<std::time::Duration>) -> Self {
+        Self::new(self.inner.with_timeout(timeout), self.runtime.clone())
+    }
+
+    /// Returns a copy of this API which retries requests according to the given policy, instead
+    /// of `DecthingsClientOptions::retry_policy`. Pass None to never retry.
+    pub fn with_retry_policy(&self, retry_policy: Option<crate::client::RetryPolicy>) -> Self {
+        Self::new(
+            self.inner.with_retry_policy(retry_policy),
+            self.runtime.clone(),
+        )
+    }
+
+    /// Returns a copy of this API which sends requests using the given protocol, instead of
+    /// `DecthingsClientOptions::default_protocol`. Methods which subscribe to events always use
+    /// WebSocket.
+    pub fn with_protocol(&self, protocol: crate::client::RpcProtocol) -> Self {
+        Self::new(self.inner.with_protocol(protocol), self.runtime.clone())
+    }
+
+    pub fn create_repository(
+        &self,
+        params: CreateRepositoryParams<'_>,
+    ) -> Result<CreateRepositoryResult, crate::client::DecthingsRpcError<CreateRepositoryError>>
+    {
+        self.runtime.block_on(self.inner.create_repository(params))
+    }
+
+    pub fn update_repository(
+        &self,
+        params: UpdateRepositoryParams<'_>,
+    ) -> Result<UpdateRepositoryResult, crate::client::DecthingsRpcError<UpdateRepositoryError>>
+    {
+        self.runtime.block_on(self.inner.update_repository(params))
+    }
+
+    pub fn delete_repository(
+        &self,
+        params: DeleteRepositoryParams<'_>,
+    ) -> Result<DeleteRepositoryResult, crate::client::DecthingsRpcError<DeleteRepositoryError>>
+    {
+        self.runtime.block_on(self.inner.delete_repository(params))
+    }
+
+    pub fn get_repositories(
+        &self,
+        params: GetRepositoriesParams<'_, impl AsRef<str>>,
+    ) -> Result<GetRepositoriesResult, crate::client::DecthingsRpcError<GetRepositoriesError>> {
+        self.runtime.block_on(self.inner.get_repositories(params))
+    }
+}
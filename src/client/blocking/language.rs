@@ -0,0 +1,71 @@
+use std::sync::Arc;
+
+use crate::client::rpc::language::*;
+
+/// The blocking version of [`LanguageRpc`].
+pub struct BlockingLanguageRpc {
+    inner: LanguageRpc,
+    runtime: Arc<tokio::runtime::Runtime>,
+}
+
+impl BlockingLanguageRpc {
+    pub(super) fn new(inner: LanguageRpc, runtime: Arc<tokio::runtime::Runtime>) -> Self {
+        Self { inner, runtime }
+    }
+
+    /// Returns a copy of this API which uses the given timeout for each request, instead of
+    /// `DecthingsClientOptions::timeout`. Pass None to wait without a timeout.
+    pub fn with_timeout(&self, timeout: Option<std::time::Duration>) -> Self {
+        Self::new(self.inner.with_timeout(timeout), self.runtime.clone())
+    }
+
+    /// Returns a copy of this API which retries requests according to the given policy, instead
+    /// of `DecthingsClientOptions::retry_policy`. Pass None to never retry.
+    pub fn with_retry_policy(&self, retry_policy: Option<crate::client::RetryPolicy>) -> Self {
+        Self::new(
+            self.inner.with_retry_policy(retry_policy),
+            self.runtime.clone(),
+        )
+    }
+
+    pub fn start_language_server(
+        &self,
+        params: StartLanguageServerParams<'_>,
+    ) -> Result<StartLanguageServerResult, crate::client::DecthingsRpcError<StartLanguageServerError>>
+    {
+        self.runtime
+            .block_on(self.inner.start_language_server(params))
+    }
+
+    pub fn write_to_language_server(
+        &self,
+        params: WriteToLanguageServerParams<'_, impl Into<bytes::Bytes>>,
+    ) -> Result<
+        WriteToLanguageServerResult,
+        crate::client::DecthingsRpcError<WriteToLanguageServerError>,
+    > {
+        self.runtime
+            .block_on(self.inner.write_to_language_server(params))
+    }
+
+    /// Returns an iterator over the events of the language server `language_server_id`. Each call
+    /// to `next` blocks until an event is received. See [`LanguageRpc::events`].
+    #[cfg(feature = "events")]
+    pub fn events(
+        &self,
+        language_server_id: impl Into<String>,
+    ) -> futures::executor::BlockingStream<crate::client::event::EventStream<LanguageEvent>> {
+        futures::executor::block_on_stream(self.inner.events(language_server_id))
+    }
+
+    pub fn unsubscribe_from_events(
+        &self,
+        params: LanguageUnsubscribeFromEventsParams<'_>,
+    ) -> Result<
+        LanguageUnsubscribeFromEventsResult,
+        crate::client::DecthingsRpcError<LanguageUnsubscribeFromEventsError>,
+    > {
+        self.runtime
+            .block_on(self.inner.unsubscribe_from_events(params))
+    }
+}
@@ -0,0 +1,302 @@
+//! A blocking client, for use in synchronous code.
+//!
+//! [`BlockingDecthingsClient`] wraps a [`DecthingsClient`] together with its own Tokio runtime,
+//! and each method blocks the current thread until the request has finished. The methods must not
+//! be called from within an async runtime.
+
+#[cfg(feature = "dataset")]
+mod dataset;
+#[cfg(feature = "debug")]
+mod debug;
+#[cfg(feature = "fs")]
+mod fs;
+#[cfg(feature = "image")]
+mod image;
+#[cfg(feature = "language")]
+mod language;
+#[cfg(feature = "model")]
+mod model;
+#[cfg(feature = "persistent-launcher")]
+mod persistent_launcher;
+#[cfg(feature = "spawned")]
+mod spawned;
+#[cfg(feature = "terminal")]
+mod terminal;
+
+use std::sync::Arc;
+
+#[cfg(feature = "events")]
+use super::event::{DecthingsEvent, EventListenerDisposer};
+use super::{DecthingsClient, DecthingsClientError, DecthingsClientOptions, ResponseBlobs};
+
+#[cfg(feature = "dataset")]
+pub use dataset::{BlockingDatasetRpc, BlockingGetEntriesStreamedResult};
+#[cfg(feature = "debug")]
+pub use debug::{BlockingDebugRpc, BlockingDownloadWeightDataStreamedResult};
+#[cfg(feature = "fs")]
+pub use fs::BlockingFsRpc;
+#[cfg(feature = "image")]
+pub use image::BlockingImageRpc;
+#[cfg(feature = "language")]
+pub use language::BlockingLanguageRpc;
+#[cfg(feature = "model")]
+pub use model::{BlockingGetWeightsStreamedResult, BlockingModelRpc};
+#[cfg(feature = "persistent-launcher")]
+pub use persistent_launcher::BlockingPersistentLauncherRpc;
+#[cfg(feature = "spawned")]
+pub use spawned::BlockingSpawnedRpc;
+#[cfg(feature = "terminal")]
+pub use terminal::BlockingTerminalRpc;
+
+pub struct BlockingDecthingsClient {
+    inner: DecthingsClient,
+    runtime: Arc<tokio::runtime::Runtime>,
+    #[cfg(feature = "dataset")]
+    pub dataset: BlockingDatasetRpc,
+    #[cfg(feature = "debug")]
+    pub debug: BlockingDebugRpc,
+    #[cfg(feature = "fs")]
+    pub fs: BlockingFsRpc,
+    #[cfg(feature = "image")]
+    pub image: BlockingImageRpc,
+    #[cfg(feature = "language")]
+    pub language: BlockingLanguageRpc,
+    #[cfg(feature = "model")]
+    pub model: BlockingModelRpc,
+    #[cfg(feature = "persistent-launcher")]
+    pub persistent_launcher: BlockingPersistentLauncherRpc,
+    #[cfg(feature = "spawned")]
+    pub spawned: BlockingSpawnedRpc,
+    #[cfg(feature = "terminal")]
+    pub terminal: BlockingTerminalRpc,
+}
+
+impl Default for BlockingDecthingsClient {
+    fn default() -> Self {
+        Self::new(Default::default())
+    }
+}
+
+impl BlockingDecthingsClient {
+    /// Create a client with a new runtime. The runtime uses one background thread, which keeps
+    /// WebSocket connections alive and delivers events between calls.
+    ///
+    /// Panics if the runtime could not be created.
+    pub fn new(options: DecthingsClientOptions) -> Self {
+        let runtime = tokio::runtime::Builder::new_multi_thread()
+            .worker_threads(1)
+            .thread_name("decthings-blocking")
+            .enable_all()
+            .build()
+            .expect("failed to create Tokio runtime");
+        Self::with_runtime(options, Arc::new(runtime))
+    }
+
+    /// Create a client which uses an existing runtime.
+    pub fn with_runtime(
+        options: DecthingsClientOptions,
+        runtime: Arc<tokio::runtime::Runtime>,
+    ) -> Self {
+        let inner = {
+            let _guard = runtime.enter();
+            DecthingsClient::new(options)
+        };
+        Self {
+            #[cfg(feature = "dataset")]
+            dataset: BlockingDatasetRpc::new(
+                super::rpc::dataset::DatasetRpc::new(inner.rpc.clone()),
+                runtime.clone(),
+            ),
+            #[cfg(feature = "debug")]
+            debug: BlockingDebugRpc::new(
+                super::rpc::debug::DebugRpc::new(inner.rpc.clone()),
+                runtime.clone(),
+            ),
+            #[cfg(feature = "fs")]
+            fs: BlockingFsRpc::new(
+                super::rpc::fs::FsRpc::new(inner.rpc.clone()),
+                runtime.clone(),
+            ),
+            #[cfg(feature = "image")]
+            image: BlockingImageRpc::new(
+                super::rpc::image::ImageRpc::new(inner.rpc.clone()),
+                runtime.clone(),
+            ),
+            #[cfg(feature = "language")]
+            language: BlockingLanguageRpc::new(
+                super::rpc::language::LanguageRpc::new(inner.rpc.clone()),
+                runtime.clone(),
+            ),
+            #[cfg(feature = "model")]
+            model: BlockingModelRpc::new(
+                super::rpc::model::ModelRpc::new(inner.rpc.clone()),
+                runtime.clone(),
+            ),
+            #[cfg(feature = "persistent-launcher")]
+            persistent_launcher: BlockingPersistentLauncherRpc::new(
+                super::rpc::persistent_launcher::PersistentLauncherRpc::new(inner.rpc.clone()),
+                runtime.clone(),
+            ),
+            #[cfg(feature = "spawned")]
+            spawned: BlockingSpawnedRpc::new(
+                super::rpc::spawned::SpawnedRpc::new(inner.rpc.clone()),
+                runtime.clone(),
+            ),
+            #[cfg(feature = "terminal")]
+            terminal: BlockingTerminalRpc::new(
+                super::rpc::terminal::TerminalRpc::new(inner.rpc.clone()),
+                runtime.clone(),
+            ),
+            inner,
+            runtime,
+        }
+    }
+
+    /// Call an RPC method on the server.
+    ///
+    /// You most likely want to use the helper classes (client.model, client.dataset, etc.) instead.
+    /// Pass the blobs in `data` as `bytes::Bytes` to avoid copying them.
+    pub fn raw_method_call<P: serde::Serialize, D: Into<bytes::Bytes>>(
+        &self,
+        api: &str,
+        method: &str,
+        params: P,
+        data: impl IntoIterator<Item = D>,
+    ) -> Result<(bytes::Bytes, Vec<bytes::Bytes>), DecthingsClientError> {
+        self.runtime
+            .block_on(self.inner.raw_method_call(api, method, params, data))
+    }
+
+    /// Returns an iterator over all events received after this call. Subscribe to events using
+    /// for example `client.spawned.subscribe_to_events`.
+    #[cfg(feature = "events")]
+    pub fn events(&self) -> DecthingsEvents {
+        let (tx, rx) = std::sync::mpsc::channel();
+        let disposer = self.runtime.block_on(self.inner.on_event(move |event| {
+            tx.send(event.clone()).ok();
+        }));
+        DecthingsEvents {
+            rx,
+            disposer: Some(disposer),
+            runtime: self.runtime.clone(),
+        }
+    }
+
+    pub fn set_api_key(&self, api_key: String) {
+        self.runtime.block_on(self.inner.set_api_key(api_key));
+    }
+}
+
+/// An iterator over events, created using [`BlockingDecthingsClient::events`]. Each call to `next`
+/// blocks until an event is received. The iterator ends when the client is dropped.
+#[cfg(feature = "events")]
+pub struct DecthingsEvents {
+    rx: std::sync::mpsc::Receiver<DecthingsEvent>,
+    disposer: Option<EventListenerDisposer>,
+    runtime: Arc<tokio::runtime::Runtime>,
+}
+
+#[cfg(feature = "events")]
+impl DecthingsEvents {
+    /// Wait for the next event for at most `timeout`. Returns None if no event was received in
+    /// time, or if the client has been dropped.
+    pub fn next_timeout(&mut self, timeout: std::time::Duration) -> Option<DecthingsEvent> {
+        self.rx.recv_timeout(timeout).ok()
+    }
+}
+
+#[cfg(feature = "events")]
+impl Iterator for DecthingsEvents {
+    type Item = DecthingsEvent;
+
+    fn next(&mut self) -> Option<DecthingsEvent> {
+        self.rx.recv().ok()
+    }
+}
+
+#[cfg(feature = "events")]
+impl Drop for DecthingsEvents {
+    fn drop(&mut self) {
+        if let Some(disposer) = self.disposer.take() {
+            self.runtime.spawn(disposer.dispose());
+        }
+    }
+}
+
+/// The blocking version of [`ResponseBlobs`].
+#[derive(Debug)]
+pub struct BlockingResponseBlobs {
+    inner: ResponseBlobs,
+    runtime: Arc<tokio::runtime::Runtime>,
+}
+
+impl BlockingResponseBlobs {
+    #[cfg(any(feature = "dataset", feature = "debug", feature = "model"))]
+    fn new(inner: ResponseBlobs, runtime: Arc<tokio::runtime::Runtime>) -> Self {
+        Self { inner, runtime }
+    }
+
+    /// Move to the next blob and return its length in bytes, or None if there are no more blobs.
+    /// Any part of the current blob which has not been read is skipped.
+    pub fn next_blob(&mut self) -> Result<Option<u64>, DecthingsClientError> {
+        self.runtime.block_on(self.inner.next_blob())
+    }
+
+    /// Read the next chunk of the current blob, or None if the whole blob has been read.
+    pub fn chunk(&mut self) -> Result<Option<bytes::Bytes>, DecthingsClientError> {
+        self.runtime.block_on(self.inner.chunk())
+    }
+
+    /// Read the rest of the current blob into memory.
+    pub fn read_blob(&mut self) -> Result<bytes::Bytes, DecthingsClientError> {
+        self.runtime.block_on(self.inner.read_blob())
+    }
+}
+
+#[cfg(all(feature = "mock", feature = "spawned"))]
+#[test]
+fn test_blocking_client() {
+    use crate::{
+        client::rpc::{ExecutionLocationProvider, spawned},
+        mock::{MockResponse, MockServer},
+    };
+
+    // The mock server runs on its own runtime, since the blocking client must not be used from
+    // within one.
+    let server_runtime = tokio::runtime::Runtime::new().unwrap();
+    let server = server_runtime.block_on(async {
+        let server = MockServer::start().await.unwrap();
+        server
+            .handle("Spawned", "spawnCommand", |_| {
+                MockResponse::result(serde_json::json!({ "spawnedCommandId": "cmd" }))
+            })
+            .await;
+        server
+    });
+
+    let client = BlockingDecthingsClient::new(server.client_options());
+    let mut events = client.events();
+    let res = client
+        .spawned
+        .spawn_command(spawned::SpawnCommandParams {
+            execution_location: ExecutionLocationProvider::PersistentLauncher {
+                persistent_launcher_id: "launcher",
+            },
+            command: "echo",
+            args: &["hello"],
+            options: None,
+            subscribe_to_events: None,
+        })
+        .unwrap();
+    assert_eq!(res.spawned_command_id, "cmd");
+
+    server_runtime.block_on(server.push_event(
+        "Spawned",
+        serde_json::json!({ "event": "stdout", "params": { "spawnedCommandId": "cmd" } }),
+        [b"hello".to_vec()],
+    ));
+    assert!(matches!(
+        events.next().unwrap(),
+        DecthingsEvent::Spawned(spawned::SpawnedEvent::Stdout { data, .. }) if &data[..] == b"hello"
+    ));
+}
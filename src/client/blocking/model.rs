@@ -0,0 +1,249 @@
+use std::sync::Arc;
+
+use crate::client::rpc::model::*;
+
+/// The blocking version of [`GetWeightsStreamedResult`].
+#[derive(Debug)]
+pub struct BlockingGetWeightsStreamedResult {
+    /// The weight keys. `data` contains one blob for each key, in the same order.
+    pub keys: Vec<String>,
+    pub data: super::BlockingResponseBlobs,
+}
+
+/// The blocking version of [`ModelRpc`].
+pub struct BlockingModelRpc {
+    inner: ModelRpc,
+    runtime: Arc<tokio::runtime::Runtime>,
+}
+
+impl BlockingModelRpc {
+    pub(super) fn new(inner: ModelRpc, runtime: Arc<tokio::runtime::Runtime>) -> Self {
+        Self { inner, runtime }
+    }
+
+    /// Returns a copy of this API which uses the given timeout for each request, instead of
+    /// `DecthingsClientOptions::timeout`. Pass None to wait without a timeout.
+    pub fn with_timeout(&self, timeout: Option<std::time::Duration>) -> Self {
+        Self::new(self.inner.with_timeout(timeout), self.runtime.clone())
+    }
+
+    /// Returns a copy of this API which retries requests according to the given policy, instead
+    /// of `DecthingsClientOptions::retry_policy`. Pass None to never retry.
+    pub fn with_retry_policy(&self, retry_policy: Option<crate::client::RetryPolicy>) -> Self {
+        Self::new(
+            self.inner.with_retry_policy(retry_policy),
+            self.runtime.clone(),
+        )
+    }
+
+    /// Returns a copy of this API which sends requests using the given protocol, instead of
+    /// `DecthingsClientOptions::default_protocol`. Methods which subscribe to events always use
+    /// WebSocket.
+    pub fn with_protocol(&self, protocol: crate::client::RpcProtocol) -> Self {
+        Self::new(self.inner.with_protocol(protocol), self.runtime.clone())
+    }
+
+    pub fn create_model(
+        &self,
+        params: CreateModelParams<'_>,
+    ) -> Result<CreateModelResult, crate::client::DecthingsRpcError<CreateModelError>> {
+        self.runtime.block_on(self.inner.create_model(params))
+    }
+
+    pub fn delete_model(
+        &self,
+        params: DeleteModelParams<'_>,
+    ) -> Result<DeleteModelResult, crate::client::DecthingsRpcError<DeleteModelError>> {
+        self.runtime.block_on(self.inner.delete_model(params))
+    }
+
+    pub fn update_model(
+        &self,
+        params: UpdateModelParams<'_>,
+    ) -> Result<UpdateModelResult, crate::client::DecthingsRpcError<UpdateModelError>> {
+        self.runtime.block_on(self.inner.update_model(params))
+    }
+
+    pub fn get_models(
+        &self,
+        params: GetModelsParams<'_, impl AsRef<str>>,
+    ) -> Result<GetModelsResult, crate::client::DecthingsRpcError<GetModelsError>> {
+        self.runtime.block_on(self.inner.get_models(params))
+    }
+
+    pub fn set_filesystem_size(
+        &self,
+        params: SetFilesystemSizeParams<'_>,
+    ) -> Result<SetFilesystemSizeResult, crate::client::DecthingsRpcError<SetFilesystemSizeError>>
+    {
+        self.runtime
+            .block_on(self.inner.set_filesystem_size(params))
+    }
+
+    pub fn create_model_version(
+        &self,
+        params: CreateModelVersionParams<'_>,
+    ) -> Result<CreateModelVersionResult, crate::client::DecthingsRpcError<CreateModelVersionError>>
+    {
+        self.runtime
+            .block_on(self.inner.create_model_version(params))
+    }
+
+    pub fn create_model_version_upload_weights(
+        &self,
+        params: CreateModelVersionUploadWeightsParams<'_, impl Clone + Into<bytes::Bytes>>,
+    ) -> Result<
+        CreateModelVersionUploadWeightsResult,
+        crate::client::DecthingsRpcError<CreateModelVersionUploadWeightsError>,
+    > {
+        self.runtime
+            .block_on(self.inner.create_model_version_upload_weights(params))
+    }
+
+    pub fn update_model_version(
+        &self,
+        params: UpdateModelVersionParams<'_>,
+    ) -> Result<UpdateModelVersionResult, crate::client::DecthingsRpcError<UpdateModelVersionError>>
+    {
+        self.runtime
+            .block_on(self.inner.update_model_version(params))
+    }
+
+    pub fn get_weights(
+        &self,
+        params: GetWeightsParams<'_, impl AsRef<str>>,
+    ) -> Result<GetWeightsResult, crate::client::DecthingsRpcError<GetWeightsError>> {
+        self.runtime.block_on(self.inner.get_weights(params))
+    }
+
+    /// Like `get_weights`, but the weight data is read while it is downloaded instead of being
+    /// kept in memory.
+    pub fn get_weights_streamed(
+        &self,
+        params: GetWeightsParams<'_, impl AsRef<str>>,
+    ) -> Result<BlockingGetWeightsStreamedResult, crate::client::DecthingsRpcError<GetWeightsError>>
+    {
+        let res = self
+            .runtime
+            .block_on(self.inner.get_weights_streamed(params))?;
+        Ok(BlockingGetWeightsStreamedResult {
+            keys: res.keys,
+            data: super::BlockingResponseBlobs::new(res.data, self.runtime.clone()),
+        })
+    }
+
+    pub fn delete_model_version(
+        &self,
+        params: DeleteModelVersionParams<'_>,
+    ) -> Result<DeleteModelVersionResult, crate::client::DecthingsRpcError<DeleteModelVersionError>>
+    {
+        self.runtime
+            .block_on(self.inner.delete_model_version(params))
+    }
+
+    pub fn train(
+        &self,
+        params: TrainParams<'_>,
+    ) -> Result<TrainResult, crate::client::DecthingsRpcError<TrainError>> {
+        self.runtime.block_on(self.inner.train(params))
+    }
+
+    pub fn get_training_status(
+        &self,
+        params: GetTrainingStatusParams<'_>,
+    ) -> Result<GetTrainingStatusResult, crate::client::DecthingsRpcError<GetTrainingStatusError>>
+    {
+        self.runtime
+            .block_on(self.inner.get_training_status(params))
+    }
+
+    pub fn get_training_metrics(
+        &self,
+        params: GetTrainingMetricsParams<'_>,
+    ) -> Result<GetTrainingMetricsResult, crate::client::DecthingsRpcError<GetTrainingMetricsError>>
+    {
+        self.runtime
+            .block_on(self.inner.get_training_metrics(params))
+    }
+
+    pub fn cancel_training_session(
+        &self,
+        params: CancelTrainingSessionParams<'_>,
+    ) -> Result<
+        CancelTrainingSessionResult,
+        crate::client::DecthingsRpcError<CancelTrainingSessionError>,
+    > {
+        self.runtime
+            .block_on(self.inner.cancel_training_session(params))
+    }
+
+    pub fn clear_previous_training_session(
+        &self,
+        params: ClearPreviousTrainingSessionParams<'_>,
+    ) -> Result<
+        ClearPreviousTrainingSessionResult,
+        crate::client::DecthingsRpcError<ClearPreviousTrainingSessionError>,
+    > {
+        self.runtime
+            .block_on(self.inner.clear_previous_training_session(params))
+    }
+
+    pub fn evaluate<'a>(
+        &self,
+        params: EvaluateParams<'a>,
+    ) -> Result<EvaluateResult, crate::client::DecthingsRpcError<EvaluateError>> {
+        self.runtime.block_on(self.inner.evaluate(params))
+    }
+
+    pub fn get_evaluations(
+        &self,
+        params: GetEvaluationsParams<'_>,
+    ) -> Result<GetEvaluationsResult, crate::client::DecthingsRpcError<GetEvaluationsError>> {
+        self.runtime.block_on(self.inner.get_evaluations(params))
+    }
+
+    pub fn get_finished_evaluation_result(
+        &self,
+        params: GetFinishedEvaluationResultParams<'_>,
+    ) -> Result<
+        GetFinishedEvaluationResultResult,
+        crate::client::DecthingsRpcError<GetFinishedEvaluationResultError>,
+    > {
+        self.runtime
+            .block_on(self.inner.get_finished_evaluation_result(params))
+    }
+
+    pub fn cancel_evaluation(
+        &self,
+        params: CancelEvaluationParams<'_>,
+    ) -> Result<CancelEvaluationResult, crate::client::DecthingsRpcError<CancelEvaluationError>>
+    {
+        self.runtime.block_on(self.inner.cancel_evaluation(params))
+    }
+
+    pub fn set_used_persistent_launchers_for_evaluate(
+        &self,
+        params: SetUsedPersistentLaunchersForEvaluateParams<'_>,
+    ) -> Result<
+        SetUsedPersistentLaunchersForEvaluateResult,
+        crate::client::DecthingsRpcError<SetUsedPersistentLaunchersForEvaluateError>,
+    > {
+        self.runtime.block_on(
+            self.inner
+                .set_used_persistent_launchers_for_evaluate(params),
+        )
+    }
+
+    pub fn get_used_persistent_launchers_for_evaluate(
+        &self,
+        params: GetUsedPersistentLaunchersForEvaluateParams<'_>,
+    ) -> Result<
+        GetUsedPersistentLaunchersForEvaluateResult,
+        crate::client::DecthingsRpcError<GetUsedPersistentLaunchersForEvaluateError>,
+    > {
+        self.runtime.block_on(
+            self.inner
+                .get_used_persistent_launchers_for_evaluate(params),
+        )
+    }
+}
@@ -0,0 +1,77 @@
+use std::sync::Arc;
+
+use crate::client::rpc::persistent_launcher::*;
+
+/// The blocking version of [`PersistentLauncherRpc`].
+pub struct BlockingPersistentLauncherRpc {
+    inner: PersistentLauncherRpc,
+    runtime: Arc<tokio::runtime::Runtime>,
+}
+
+impl BlockingPersistentLauncherRpc {
+    pub(super) fn new(inner: PersistentLauncherRpc, runtime: Arc<tokio::runtime::Runtime>) -> Self {
+        Self { inner, runtime }
+    }
+
+    /// Returns a copy of this API which uses the given timeout for each request, instead of
+    /// `DecthingsClientOptions::timeout`. Pass None to wait without a timeout.
+    pub fn with_timeout(&self, timeout: Option<std::time::Duration>) -> Self {
+        Self::new(self.inner.with_timeout(timeout), self.runtime.clone())
+    }
+
+    /// Returns a copy of this API which retries requests according to the given policy, instead
+    /// of `DecthingsClientOptions::retry_policy`. Pass None to never retry.
+    pub fn with_retry_policy(&self, retry_policy: Option<crate::client::RetryPolicy>) -> Self {
+        Self::new(
+            self.inner.with_retry_policy(retry_policy),
+            self.runtime.clone(),
+        )
+    }
+
+    /// Returns a copy of this API which sends requests using the given protocol, instead of
+    /// `DecthingsClientOptions::default_protocol`. Methods which subscribe to events always use
+    /// WebSocket.
+    pub fn with_protocol(&self, protocol: crate::client::RpcProtocol) -> Self {
+        Self::new(self.inner.with_protocol(protocol), self.runtime.clone())
+    }
+
+    pub fn create_persistent_launcher(
+        &self,
+        params: CreatePersistentLauncherParams<'_>,
+    ) -> Result<
+        CreatePersistentLauncherResult,
+        crate::client::DecthingsRpcError<CreatePersistentLauncherError>,
+    > {
+        self.runtime
+            .block_on(self.inner.create_persistent_launcher(params))
+    }
+
+    pub fn get_persistent_launchers(
+        &self,
+        params: GetPersistentLaunchersParams<'_, impl AsRef<str>>,
+    ) -> Result<
+        GetPersistentLaunchersResult,
+        crate::client::DecthingsRpcError<GetPersistentLaunchersError>,
+    > {
+        self.runtime
+            .block_on(self.inner.get_persistent_launchers(params))
+    }
+
+    pub fn get_sysinfo(
+        &self,
+        params: GetSysinfoParams<'_>,
+    ) -> Result<GetSysinfoResult, crate::client::DecthingsRpcError<GetSysinfoError>> {
+        self.runtime.block_on(self.inner.get_sysinfo(params))
+    }
+
+    pub fn delete_persistent_launcher(
+        &self,
+        params: DeletePersistentLauncherParams<'_>,
+    ) -> Result<
+        DeletePersistentLauncherResult,
+        crate::client::DecthingsRpcError<DeletePersistentLauncherError>,
+    > {
+        self.runtime
+            .block_on(self.inner.delete_persistent_launcher(params))
+    }
+}
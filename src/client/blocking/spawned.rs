@@ -0,0 +1,120 @@
+use std::sync::Arc;
+
+use crate::client::rpc::spawned::*;
+
+/// The blocking version of [`SpawnedRpc`].
+pub struct BlockingSpawnedRpc {
+    inner: SpawnedRpc,
+    runtime: Arc<tokio::runtime::Runtime>,
+}
+
+impl BlockingSpawnedRpc {
+    pub(super) fn new(inner: SpawnedRpc, runtime: Arc<tokio::runtime::Runtime>) -> Self {
+        Self { inner, runtime }
+    }
+
+    /// Returns a copy of this API which uses the given timeout for each request, instead of
+    /// `DecthingsClientOptions::timeout`. Pass None to wait without a timeout.
+    pub fn with_timeout(&self, timeout: Option<std::time::Duration>) -> Self {
+        Self::new(self.inner.with_timeout(timeout), self.runtime.clone())
+    }
+
+    /// Returns a copy of this API which retries requests according to the given policy, instead
+    /// of `DecthingsClientOptions::retry_policy`. Pass None to never retry.
+    pub fn with_retry_policy(&self, retry_policy: Option<crate::client::RetryPolicy>) -> Self {
+        Self::new(
+            self.inner.with_retry_policy(retry_policy),
+            self.runtime.clone(),
+        )
+    }
+
+    /// Returns a copy of this API which sends requests using the given protocol, instead of
+    /// `DecthingsClientOptions::default_protocol`. Methods which subscribe to events always use
+    /// WebSocket.
+    pub fn with_protocol(&self, protocol: crate::client::RpcProtocol) -> Self {
+        Self::new(self.inner.with_protocol(protocol), self.runtime.clone())
+    }
+
+    pub fn spawn_command(
+        &self,
+        params: SpawnCommandParams<'_, impl AsRef<str>>,
+    ) -> Result<SpawnCommandResult, crate::client::DecthingsRpcError<SpawnCommandError>> {
+        self.runtime.block_on(self.inner.spawn_command(params))
+    }
+
+    pub fn spawn_command_for_model(
+        &self,
+        params: SpawnCommandForModelParams<'_, impl AsRef<str>>,
+    ) -> Result<
+        SpawnCommandForModelResult,
+        crate::client::DecthingsRpcError<SpawnCommandForModelError>,
+    > {
+        self.runtime
+            .block_on(self.inner.spawn_command_for_model(params))
+    }
+
+    pub fn terminate_spawned_command(
+        &self,
+        params: TerminateSpawnedCommandParams<'_>,
+    ) -> Result<
+        TerminateSpawnedCommandResult,
+        crate::client::DecthingsRpcError<TerminateSpawnedCommandError>,
+    > {
+        self.runtime
+            .block_on(self.inner.terminate_spawned_command(params))
+    }
+
+    pub fn get_spawned_commands(
+        &self,
+        params: GetSpawnedCommandsParams<'_, impl AsRef<str>>,
+    ) -> Result<GetSpawnedCommandsResult, crate::client::DecthingsRpcError<GetSpawnedCommandsError>>
+    {
+        self.runtime
+            .block_on(self.inner.get_spawned_commands(params))
+    }
+
+    pub fn write_to_spawned_command(
+        &self,
+        params: WriteToSpawnedCommandParams<'_, impl Into<bytes::Bytes>>,
+    ) -> Result<
+        WriteToSpawnedCommandResult,
+        crate::client::DecthingsRpcError<WriteToSpawnedCommandError>,
+    > {
+        self.runtime
+            .block_on(self.inner.write_to_spawned_command(params))
+    }
+
+    /// Returns an iterator over the events of the spawned command `spawned_command_id`. Each call
+    /// to `next` blocks until an event is received. See [`SpawnedRpc::events`].
+    #[cfg(feature = "events")]
+    pub fn events(
+        &self,
+        spawned_command_id: impl Into<String>,
+    ) -> futures::executor::BlockingStream<crate::client::event::EventStream<SpawnedEvent>> {
+        futures::executor::block_on_stream(self.inner.events(spawned_command_id))
+    }
+
+    #[cfg(feature = "events")]
+    pub fn subscribe_to_events(
+        &self,
+        params: SpawnedSubscribeToEventsParams<'_>,
+    ) -> Result<
+        SpawnedSubscribeToEventsResult,
+        crate::client::DecthingsRpcError<SpawnedSubscribeToEventsError>,
+    > {
+        self.runtime
+            .block_on(self.inner.subscribe_to_events(params))
+    }
+
+    #[cfg(feature = "events")]
+    pub fn unsubscribe_from_events(
+        &self,
+        params: SpawnedUnsubscribeFromEventsParams<'_>,
+    ) -> Result<
+        SpawnedUnsubscribeFromEventsResult,
+        crate::client::DecthingsRpcError<SpawnedUnsubscribeFromEventsError>,
+    > {
+        self.runtime
+            .block_on(self.inner.unsubscribe_from_events(params))
+    }
+}
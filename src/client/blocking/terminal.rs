@@ -0,0 +1,124 @@
+use std::sync::Arc;
+
+use crate::client::rpc::terminal::*;
+
+/// The blocking version of [`TerminalRpc`].
+pub struct BlockingTerminalRpc {
+    inner: TerminalRpc,
+    runtime: Arc<tokio::runtime::Runtime>,
+}
+
+impl BlockingTerminalRpc {
+    pub(super) fn new(inner: TerminalRpc, runtime: Arc<tokio::runtime::Runtime>) -> Self {
+        Self { inner, runtime }
+    }
+
+    /// Returns a copy of this API which uses the given timeout for each request, instead of
+    /// `DecthingsClientOptions::timeout`. Pass None to wait without a timeout.
+    pub fn with_timeout(&self, timeout: Option<std::time::Duration>) -> Self {
+        Self::new(self.inner.with_timeout(timeout), self.runtime.clone())
+    }
+
+    /// Returns a copy of this API which retries requests according to the given policy, instead
+    /// of `DecthingsClientOptions::retry_policy`. Pass None to never retry.
+    pub fn with_retry_policy(&self, retry_policy: Option<crate::client::RetryPolicy>) -> Self {
+        Self::new(
+            self.inner.with_retry_policy(retry_policy),
+            self.runtime.clone(),
+        )
+    }
+
+    /// Returns a copy of this API which sends requests using the given protocol, instead of
+    /// `DecthingsClientOptions::default_protocol`. Methods which subscribe to events always use
+    /// WebSocket.
+    pub fn with_protocol(&self, protocol: crate::client::RpcProtocol) -> Self {
+        Self::new(self.inner.with_protocol(protocol), self.runtime.clone())
+    }
+
+    pub fn launch_terminal_session(
+        &self,
+        params: LaunchTerminalSessionParams<'_>,
+    ) -> Result<
+        LaunchTerminalSessionResult,
+        crate::client::DecthingsRpcError<LaunchTerminalSessionError>,
+    > {
+        self.runtime
+            .block_on(self.inner.launch_terminal_session(params))
+    }
+
+    pub fn terminate_terminal_session(
+        &self,
+        params: TerminateTerminalSessionParams<'_>,
+    ) -> Result<
+        TerminateTerminalSessionResult,
+        crate::client::DecthingsRpcError<TerminateTerminalSessionError>,
+    > {
+        self.runtime
+            .block_on(self.inner.terminate_terminal_session(params))
+    }
+
+    pub fn get_terminal_sessions(
+        &self,
+        params: GetTerminalSessionsParams<'_, impl AsRef<str>>,
+    ) -> Result<GetTerminalSessionsResult, crate::client::DecthingsRpcError<GetTerminalSessionsError>>
+    {
+        self.runtime
+            .block_on(self.inner.get_terminal_sessions(params))
+    }
+
+    pub fn write_to_terminal_session(
+        &self,
+        params: WriteToTerminalSessionParams<'_, impl Into<bytes::Bytes>>,
+    ) -> Result<
+        WriteToTerminalSessionResult,
+        crate::client::DecthingsRpcError<WriteToTerminalSessionError>,
+    > {
+        self.runtime
+            .block_on(self.inner.write_to_terminal_session(params))
+    }
+
+    pub fn resize_terminal_session(
+        &self,
+        params: ResizeTerminalSessionParams<'_>,
+    ) -> Result<
+        ResizeTerminalSessionResult,
+        crate::client::DecthingsRpcError<ResizeTerminalSessionError>,
+    > {
+        self.runtime
+            .block_on(self.inner.resize_terminal_session(params))
+    }
+
+    /// Returns an iterator over the events of the terminal session `terminal_session_id`. Each call
+    /// to `next` blocks until an event is received. See [`TerminalRpc::events`].
+    #[cfg(feature = "events")]
+    pub fn events(
+        &self,
+        terminal_session_id: impl Into<String>,
+    ) -> futures::executor::BlockingStream<crate::client::event::EventStream<TerminalEvent>> {
+        futures::executor::block_on_stream(self.inner.events(terminal_session_id))
+    }
+
+    #[cfg(feature = "events")]
+    pub fn subscribe_to_events(
+        &self,
+        params: TerminalSubscribeToEventsParams<'_>,
+    ) -> Result<
+        TerminalSubscribeToEventsResult,
+        crate::client::DecthingsRpcError<TerminalSubscribeToEventsError>,
+    > {
+        self.runtime
+            .block_on(self.inner.subscribe_to_events(params))
+    }
+
+    #[cfg(feature = "events")]
+    pub fn unsubscribe_from_events(
+        &self,
+        params: TerminalUnsubscribeFromEventsParams<'_>,
+    ) -> Result<
+        TerminalUnsubscribeFromEventsResult,
+        crate::client::DecthingsRpcError<TerminalUnsubscribeFromEventsError>,
+    > {
+        self.runtime
+            .block_on(self.inner.unsubscribe_from_events(params))
+    }
+}
@@ -0,0 +1,453 @@
+use std::sync::Arc;
+
+use super::{
+    Certificate, Compression, CredentialProvider, DecodeLimits, DecthingsClient,
+    DecthingsClientOptions, Identity, Middleware, Proxy, RequestLimits, RetryPolicy, RpcProtocol,
+    Transport,
+};
+
+/// Error returned by [`DecthingsClientBuilder`] and [`Proxy`] when the configuration is invalid or
+/// could not be loaded.
+#[derive(Debug)]
+pub enum ConfigError {
+    /// An environment variable was set to an invalid value.
+    InvalidEnv { name: String, reason: String },
+    /// A header from the environment or a profile has an invalid name or value.
+    InvalidHeader { name: String },
+    /// A proxy URL could not be parsed.
+    InvalidProxy { url: String, reason: String },
+    #[cfg(feature = "config")]
+    /// The location of the config file could not be determined, because the user has no config
+    /// directory.
+    NoConfigDir,
+    #[cfg(feature = "config")]
+    /// The config file could not be read.
+    ReadFile {
+        path: std::path::PathBuf,
+        error: std::io::Error,
+    },
+    #[cfg(feature = "config")]
+    /// The config file is not valid TOML, or has an unexpected structure.
+    ParseFile {
+        path: std::path::PathBuf,
+        error: toml::de::Error,
+    },
+    #[cfg(feature = "config")]
+    /// The config file does not contain the requested profile.
+    ProfileNotFound {
+        path: std::path::PathBuf,
+        profile: String,
+    },
+}
+
+impl std::fmt::Display for ConfigError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::InvalidEnv { name, reason } => {
+                write!(f, "environment variable {name} is invalid: {reason}")
+            }
+            Self::InvalidHeader { name } => write!(f, "header '{name}' is invalid"),
+            Self::InvalidProxy { url, reason } => write!(f, "proxy '{url}' is invalid: {reason}"),
+            #[cfg(feature = "config")]
+            Self::NoConfigDir => write!(f, "could not determine the config directory"),
+            #[cfg(feature = "config")]
+            Self::ReadFile { path, error } => {
+                write!(f, "failed to read {}: {error}", path.display())
+            }
+            #[cfg(feature = "config")]
+            Self::ParseFile { path, error } => {
+                write!(f, "failed to parse {}: {error}", path.display())
+            }
+            #[cfg(feature = "config")]
+            Self::ProfileNotFound { path, profile } => {
+                write!(f, "profile '{profile}' not found in {}", path.display())
+            }
+        }
+    }
+}
+
+impl std::error::Error for ConfigError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            #[cfg(feature = "config")]
+            Self::ReadFile { error, .. } => Some(error),
+            #[cfg(feature = "config")]
+            Self::ParseFile { error, .. } => Some(error),
+            _ => None,
+        }
+    }
+}
+
+/// The format of the config file. See `DecthingsClientBuilder::profile_from_file`.
+#[cfg(feature = "config")]
+#[derive(serde::Deserialize)]
+#[serde(deny_unknown_fields)]
+struct ConfigFile {
+    #[serde(default)]
+    profiles: std::collections::HashMap<String, Profile>,
+}
+
+#[cfg(feature = "config")]
+#[derive(serde::Deserialize)]
+#[serde(deny_unknown_fields)]
+struct Profile {
+    api_key: Option<String>,
+    http_url: Option<String>,
+    // Accepted without the events feature, so that the same file works for all clients.
+    #[cfg_attr(not(feature = "events"), allow(dead_code))]
+    ws_url: Option<String>,
+    #[serde(default)]
+    headers: std::collections::BTreeMap<String, String>,
+}
+
+/// Builds a [`DecthingsClient`], optionally reading the configuration from the environment or
+/// from a profile in the config file. Create using `DecthingsClient::builder()`.
+///
+/// Each method overrides the values set by the methods called before it, so for example
+/// `.profile("staging")?.from_env()?` lets environment variables override the profile.
+#[derive(Debug, Clone, Default)]
+pub struct DecthingsClientBuilder {
+    options: DecthingsClientOptions,
+}
+
+impl From<DecthingsClientOptions> for DecthingsClientBuilder {
+    fn from(options: DecthingsClientOptions) -> Self {
+        Self { options }
+    }
+}
+
+impl DecthingsClientBuilder {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn api_key(mut self, api_key: impl Into<String>) -> Self {
+        self.options.api_key = Some(api_key.into());
+        self
+    }
+
+    /// Fetch the API key from a provider instead. See [`CredentialProvider`].
+    pub fn credential_provider(mut self, provider: impl CredentialProvider + 'static) -> Self {
+        self.options.credential_provider = Some(Arc::new(provider));
+        self
+    }
+
+    pub fn http_server_address(mut self, address: impl Into<String>) -> Self {
+        self.options.http_server_address = address.into();
+        self
+    }
+
+    #[cfg(feature = "events")]
+    pub fn ws_server_address(mut self, address: impl Into<String>) -> Self {
+        self.options.ws_server_address = address.into();
+        self
+    }
+
+    #[cfg(feature = "events")]
+    pub fn ws_auto_reconnect(mut self, ws_auto_reconnect: bool) -> Self {
+        self.options.ws_auto_reconnect = ws_auto_reconnect;
+        self
+    }
+
+    /// Set to None to disable the heartbeat. See `DecthingsClientOptions::ws_keepalive`.
+    #[cfg(feature = "events")]
+    pub fn ws_keepalive(mut self, keepalive: Option<super::Keepalive>) -> Self {
+        self.options.ws_keepalive = keepalive;
+        self
+    }
+
+    /// Trust an additional root certificate. Can be called multiple times.
+    pub fn root_certificate(mut self, certificate: Certificate) -> Self {
+        self.options.root_certificates.push(certificate);
+        self
+    }
+
+    /// Present a client certificate to the server (mutual TLS).
+    pub fn client_identity(mut self, identity: Identity) -> Self {
+        self.options.client_identity = Some(identity);
+        self
+    }
+
+    /// Use a proxy for HTTP and WebSocket connections. See [`Proxy`].
+    pub fn proxy(mut self, proxy: Proxy) -> Self {
+        self.options.proxy = Some(proxy);
+        self
+    }
+
+    /// Add a header which is sent with each request.
+    pub fn header(mut self, name: http::HeaderName, value: http::HeaderValue) -> Self {
+        self.options.extra_headers.insert(name, value);
+        self
+    }
+
+    pub fn transport(mut self, transport: Arc<dyn Transport>) -> Self {
+        self.options.transport = Some(transport);
+        self
+    }
+
+    pub fn timeout(mut self, timeout: std::time::Duration) -> Self {
+        self.options.timeout = Some(timeout);
+        self
+    }
+
+    pub fn retry_policy(mut self, retry_policy: RetryPolicy) -> Self {
+        self.options.retry_policy = Some(retry_policy);
+        self
+    }
+
+    pub fn request_limits(mut self, limits: RequestLimits) -> Self {
+        self.options.request_limits = limits;
+        self
+    }
+
+    /// Set the limits for the requests to one API, such as "Model". See
+    /// `DecthingsClientOptions::api_request_limits`.
+    pub fn api_request_limits(mut self, api: impl Into<String>, limits: RequestLimits) -> Self {
+        self.options.api_request_limits.insert(api.into(), limits);
+        self
+    }
+
+    pub fn decode_limits(mut self, limits: DecodeLimits) -> Self {
+        self.options.decode_limits = limits;
+        self
+    }
+
+    /// Compress large request bodies, and ask Decthings to compress responses. See
+    /// `DecthingsClientOptions::compression`.
+    pub fn compression(mut self, compression: Compression) -> Self {
+        self.options.compression = Some(compression);
+        self
+    }
+
+    pub fn default_protocol(mut self, protocol: RpcProtocol) -> Self {
+        self.options.default_protocol = protocol;
+        self
+    }
+
+    /// Add a middleware. See `DecthingsClientOptions::middleware`.
+    pub fn middleware(mut self, middleware: impl Middleware + 'static) -> Self {
+        self.options.middleware.push(Arc::new(middleware));
+        self
+    }
+
+    /// Read the configuration from environment variables. Variables which are not set are
+    /// ignored:
+    ///
+    /// * `DECTHINGS_PROFILE`: Load this profile from the config file before reading the other
+    ///   variables. Requires the `config` feature.
+    /// * `DECTHINGS_API_KEY`: The API key.
+    /// * `DECTHINGS_HTTP_URL`: Server address to use for the HTTP API.
+    /// * `DECTHINGS_WS_URL`: Server address to use for the WebSocket API.
+    /// * `DECTHINGS_HEADERS`: Extra headers, as comma separated `name=value` pairs.
+    pub fn from_env(self) -> Result<Self, ConfigError> {
+        self.apply_env(|name| match std::env::var(name) {
+            Ok(val) => Ok(Some(val)),
+            Err(std::env::VarError::NotPresent) => Ok(None),
+            Err(std::env::VarError::NotUnicode(_)) => Err(ConfigError::InvalidEnv {
+                name: name.to_owned(),
+                reason: "not valid unicode".to_owned(),
+            }),
+        })
+    }
+
+    fn apply_env(
+        mut self,
+        var: impl Fn(&str) -> Result<Option<String>, ConfigError>,
+    ) -> Result<Self, ConfigError> {
+        #[cfg(feature = "config")]
+        if let Some(profile) = var("DECTHINGS_PROFILE")? {
+            self = self.profile(&profile)?;
+        }
+        if let Some(api_key) = var("DECTHINGS_API_KEY")? {
+            self.options.api_key = Some(api_key);
+        }
+        if let Some(address) = var("DECTHINGS_HTTP_URL")? {
+            self.options.http_server_address = address;
+        }
+        #[cfg(feature = "events")]
+        if let Some(address) = var("DECTHINGS_WS_URL")? {
+            self.options.ws_server_address = address;
+        }
+        if let Some(headers) = var("DECTHINGS_HEADERS")? {
+            for header in headers.split(',').filter(|x| !x.trim().is_empty()) {
+                let Some((name, value)) = header.split_once('=') else {
+                    return Err(ConfigError::InvalidEnv {
+                        name: "DECTHINGS_HEADERS".to_owned(),
+                        reason: format!("expected name=value, got '{header}'"),
+                    });
+                };
+                self = self.parsed_header(name.trim(), value.trim())?;
+            }
+        }
+        Ok(self)
+    }
+
+    fn parsed_header(self, name: &str, value: &str) -> Result<Self, ConfigError> {
+        let invalid = || ConfigError::InvalidHeader {
+            name: name.to_owned(),
+        };
+        let header_name = http::HeaderName::from_bytes(name.as_bytes()).map_err(|_| invalid())?;
+        let header_value = http::HeaderValue::from_str(value).map_err(|_| invalid())?;
+        Ok(self.header(header_name, header_value))
+    }
+
+    /// The default location of the config file, which is `decthings/config.toml` in the user's
+    /// config directory, or the path in the `DECTHINGS_CONFIG_FILE` environment variable if set.
+    #[cfg(feature = "config")]
+    pub fn default_config_file() -> Result<std::path::PathBuf, ConfigError> {
+        if let Some(path) = std::env::var_os("DECTHINGS_CONFIG_FILE") {
+            return Ok(path.into());
+        }
+        let dir = dirs::config_dir().ok_or(ConfigError::NoConfigDir)?;
+        Ok(dir.join("decthings").join("config.toml"))
+    }
+
+    /// Load a profile from the default config file. See
+    /// [`default_config_file`](Self::default_config_file).
+    #[cfg(feature = "config")]
+    pub fn profile(self, name: &str) -> Result<Self, ConfigError> {
+        let path = Self::default_config_file()?;
+        self.profile_from_file(path, name)
+    }
+
+    /// Load a profile from a TOML config file. Each profile is a table under `profiles`, which
+    /// can set `api_key`, `http_url`, `ws_url` and `headers`:
+    ///
+    /// ```toml
+    /// [profiles.staging]
+    /// api_key = "..."
+    /// http_url = "https://staging.example.com/v0"
+    /// ws_url = "wss://staging.example.com/v0/ws"
+    ///
+    /// [profiles.staging.headers]
+    /// X-Team = "tools"
+    /// ```
+    #[cfg(feature = "config")]
+    pub fn profile_from_file(
+        self,
+        path: impl AsRef<std::path::Path>,
+        name: &str,
+    ) -> Result<Self, ConfigError> {
+        let path = path.as_ref();
+        let contents = std::fs::read_to_string(path).map_err(|error| ConfigError::ReadFile {
+            path: path.to_owned(),
+            error,
+        })?;
+        let mut file: ConfigFile =
+            toml::from_str(&contents).map_err(|error| ConfigError::ParseFile {
+                path: path.to_owned(),
+                error,
+            })?;
+        let profile = file
+            .profiles
+            .remove(name)
+            .ok_or_else(|| ConfigError::ProfileNotFound {
+                path: path.to_owned(),
+                profile: name.to_owned(),
+            })?;
+        self.apply_profile(profile)
+    }
+
+    #[cfg(feature = "config")]
+    fn apply_profile(mut self, profile: Profile) -> Result<Self, ConfigError> {
+        if let Some(api_key) = profile.api_key {
+            self.options.api_key = Some(api_key);
+        }
+        if let Some(address) = profile.http_url {
+            self.options.http_server_address = address;
+        }
+        #[cfg(feature = "events")]
+        if let Some(address) = profile.ws_url {
+            self.options.ws_server_address = address;
+        }
+        for (name, value) in &profile.headers {
+            self = self.parsed_header(name, value)?;
+        }
+        Ok(self)
+    }
+
+    /// Returns the options which would be used to create the client.
+    pub fn options(self) -> DecthingsClientOptions {
+        self.options
+    }
+
+    pub fn build(self) -> DecthingsClient {
+        DecthingsClient::new(self.options)
+    }
+
+    #[cfg(feature = "blocking")]
+    pub fn build_blocking(self) -> super::blocking::BlockingDecthingsClient {
+        super::blocking::BlockingDecthingsClient::new(self.options)
+    }
+}
+
+#[test]
+fn test_builder_env() {
+    let env = |name: &str| {
+        Ok(match name {
+            "DECTHINGS_API_KEY" => Some("key".to_owned()),
+            "DECTHINGS_HTTP_URL" => Some("http://localhost:3000".to_owned()),
+            "DECTHINGS_HEADERS" => Some("X-A=1, x-b = 2".to_owned()),
+            _ => None,
+        })
+    };
+    let options = DecthingsClientBuilder::new()
+        .api_key("overridden")
+        .apply_env(env)
+        .unwrap()
+        .options();
+    assert_eq!(options.api_key.as_deref(), Some("key"));
+    assert_eq!(options.http_server_address, "http://localhost:3000");
+    assert_eq!(options.extra_headers["x-a"], "1");
+    assert_eq!(options.extra_headers["x-b"], "2");
+
+    let err = DecthingsClientBuilder::new()
+        .apply_env(|name| Ok((name == "DECTHINGS_HEADERS").then(|| "X-A".to_owned())))
+        .unwrap_err();
+    assert!(matches!(err, ConfigError::InvalidEnv { .. }));
+}
+
+#[cfg(feature = "config")]
+#[test]
+fn test_builder_profile() {
+    let path = std::env::temp_dir().join(format!("decthings-config-{}.toml", std::process::id()));
+    std::fs::write(
+        &path,
+        r#"
+[profiles.staging]
+api_key = "staging-key"
+http_url = "https://staging.example.com/v0"
+
+[profiles.staging.headers]
+X-Team = "tools"
+
+[profiles.production]
+api_key = "production-key"
+"#,
+    )
+    .unwrap();
+
+    let options = DecthingsClientBuilder::new()
+        .profile_from_file(&path, "staging")
+        .unwrap()
+        .options();
+    assert_eq!(options.api_key.as_deref(), Some("staging-key"));
+    assert_eq!(
+        options.http_server_address,
+        "https://staging.example.com/v0"
+    );
+    assert_eq!(options.extra_headers["x-team"], "tools");
+
+    let options = DecthingsClientBuilder::new()
+        .profile_from_file(&path, "production")
+        .unwrap()
+        .options();
+    assert_eq!(options.api_key.as_deref(), Some("production-key"));
+    assert_eq!(options.http_server_address, "https://api.decthings.com/v0");
+
+    assert!(matches!(
+        DecthingsClientBuilder::new().profile_from_file(&path, "missing"),
+        Err(ConfigError::ProfileNotFound { .. })
+    ));
+    std::fs::remove_file(&path).ok();
+}
@@ -0,0 +1,425 @@
+// Without a compression algorithm, the options are accepted but never apply.
+#![cfg_attr(not(any(feature = "gzip", feature = "zstd")), allow(dead_code, unused))]
+
+use std::io::Write;
+
+/// A compression algorithm for HTTP bodies. Each algorithm requires the feature of the same name.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CompressionAlgorithm {
+    #[cfg(feature = "gzip")]
+    Gzip,
+    #[cfg(feature = "zstd")]
+    Zstd,
+}
+
+impl CompressionAlgorithm {
+    /// The value of the Content-Encoding header.
+    pub fn as_str(&self) -> &'static str {
+        match *self {
+            #[cfg(feature = "gzip")]
+            Self::Gzip => "gzip",
+            #[cfg(feature = "zstd")]
+            Self::Zstd => "zstd",
+        }
+    }
+
+    /// The first enabled algorithm in an Accept-Encoding header.
+    #[cfg(feature = "mock")]
+    pub(crate) fn from_accept_encoding(accept_encoding: &str) -> Option<Self> {
+        accept_encoding
+            .split(',')
+            .find_map(|encoding| match encoding.split(';').next()?.trim() {
+                #[cfg(feature = "gzip")]
+                "gzip" => Some(Self::Gzip),
+                #[cfg(feature = "zstd")]
+                "zstd" => Some(Self::Zstd),
+                _ => None,
+            })
+    }
+}
+
+/// Compression of HTTP request bodies. Set using `DecthingsClientOptions::compression`.
+///
+/// When set, the client also asks Decthings to compress responses using any of the enabled
+/// algorithms. WebSocket messages are never compressed, since the WebSocket library does not
+/// support the permessage-deflate extension, so it is not negotiated even if the server offers it.
+#[derive(Debug, Clone)]
+pub struct Compression {
+    pub algorithm: CompressionAlgorithm,
+    /// Request bodies smaller than this, in bytes, are sent uncompressed. Default: 16 KiB.
+    pub min_size: usize,
+    /// Compression level, or None for the default level of the algorithm. For gzip the level is
+    /// between 0 and 9, and for zstd between 1 and 22.
+    pub level: Option<u32>,
+}
+
+impl Compression {
+    pub fn new(algorithm: CompressionAlgorithm) -> Self {
+        Self {
+            algorithm,
+            min_size: 16 * 1024,
+            level: None,
+        }
+    }
+}
+
+/// How much a request body was compressed. Passed to middleware in
+/// `RpcResponse::request_compression`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct CompressionStats {
+    pub algorithm: CompressionAlgorithm,
+    pub uncompressed_size: usize,
+    pub compressed_size: usize,
+}
+
+impl CompressionStats {
+    /// The compressed size divided by the uncompressed size.
+    pub fn ratio(&self) -> f64 {
+        self.compressed_size as f64 / self.uncompressed_size as f64
+    }
+}
+
+/// Compress a serialized request body if it is at least `Compression::min_size` bytes and
+/// becomes smaller when compressed. Returns the body to send, and the stats if it was compressed.
+pub(crate) fn compress(
+    compression: &Compression,
+    body: Vec<bytes::Bytes>,
+) -> (Vec<bytes::Bytes>, Option<CompressionStats>) {
+    let uncompressed_size = body.iter().map(|segment| segment.len()).sum::<usize>();
+    if uncompressed_size < compression.min_size {
+        return (body, None);
+    }
+    let compressed = match encode(compression, &body) {
+        Ok(compressed) if compressed.len() < uncompressed_size => compressed,
+        _ => return (body, None),
+    };
+    let stats = CompressionStats {
+        algorithm: compression.algorithm,
+        uncompressed_size,
+        compressed_size: compressed.len(),
+    };
+    (vec![compressed.into()], Some(stats))
+}
+
+fn encode(compression: &Compression, body: &[bytes::Bytes]) -> std::io::Result<Vec<u8>> {
+    match compression.algorithm {
+        #[cfg(feature = "gzip")]
+        CompressionAlgorithm::Gzip => {
+            let level = compression
+                .level
+                .map_or_else(flate2::Compression::default, |level| {
+                    flate2::Compression::new(level.min(9))
+                });
+            let mut encoder = flate2::write::GzEncoder::new(vec![], level);
+            for segment in body {
+                encoder.write_all(segment)?;
+            }
+            encoder.finish()
+        }
+        #[cfg(feature = "zstd")]
+        CompressionAlgorithm::Zstd => {
+            let level = compression
+                .level
+                .map_or(zstd::DEFAULT_COMPRESSION_LEVEL, |level| {
+                    level.min(22) as i32
+                });
+            let mut encoder = zstd::stream::write::Encoder::new(vec![], level)?;
+            for segment in body {
+                encoder.write_all(segment)?;
+            }
+            encoder.finish()
+        }
+    }
+}
+
+/// The value of the Accept-Encoding header, listing the enabled algorithms.
+pub(crate) fn accept_encoding() -> Option<&'static str> {
+    match (cfg!(feature = "gzip"), cfg!(feature = "zstd")) {
+        (true, true) => Some("zstd, gzip"),
+        (true, false) => Some("gzip"),
+        (false, true) => Some("zstd"),
+        (false, false) => None,
+    }
+}
+
+/// The output of a `Decoder`. Writing fails once the output is larger than the limit, so that a
+/// small compressed body cannot expand into more memory than the decode limits allow.
+pub(crate) struct LimitedBuffer {
+    data: Vec<u8>,
+    written: u64,
+    limit: Option<u64>,
+}
+
+impl Write for LimitedBuffer {
+    fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+        self.written += buf.len() as u64;
+        if let Some(limit) = self.limit.filter(|limit| self.written > *limit) {
+            return Err(std::io::Error::new(
+                std::io::ErrorKind::InvalidData,
+                format!("decompressed body is larger than the limit of {limit} bytes"),
+            ));
+        }
+        self.data.extend_from_slice(buf);
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> std::io::Result<()> {
+        Ok(())
+    }
+}
+
+/// Decompresses a response body as it is received.
+pub(crate) enum Decoder {
+    #[cfg(feature = "gzip")]
+    Gzip(flate2::write::GzDecoder<LimitedBuffer>),
+    #[cfg(feature = "zstd")]
+    Zstd(zstd::stream::write::Decoder<'static, LimitedBuffer>),
+}
+
+impl Decoder {
+    /// Returns None if the body is not compressed, or an error if the encoding is not supported.
+    /// Decompressing fails once the output is larger than `max_size` bytes.
+    pub(crate) fn new(
+        content_encoding: Option<&str>,
+        max_size: Option<u64>,
+    ) -> std::io::Result<Option<Self>> {
+        let output = LimitedBuffer {
+            data: vec![],
+            written: 0,
+            limit: max_size,
+        };
+        match content_encoding.map(str::trim) {
+            None | Some("identity") => Ok(None),
+            #[cfg(feature = "gzip")]
+            Some("gzip") => Ok(Some(Self::Gzip(flate2::write::GzDecoder::new(output)))),
+            #[cfg(feature = "zstd")]
+            Some("zstd") => Ok(Some(Self::Zstd(zstd::stream::write::Decoder::new(output)?))),
+            Some(encoding) => Err(std::io::Error::new(
+                std::io::ErrorKind::InvalidData,
+                format!("unsupported content encoding '{encoding}'"),
+            )),
+        }
+    }
+
+    /// Decompress the next chunk of the body, and return the data which is ready.
+    pub(crate) fn write(&mut self, chunk: &[u8]) -> std::io::Result<bytes::Bytes> {
+        match *self {
+            #[cfg(feature = "gzip")]
+            Self::Gzip(ref mut decoder) => {
+                decoder.write_all(chunk)?;
+                Ok(std::mem::take(&mut decoder.get_mut().data).into())
+            }
+            #[cfg(feature = "zstd")]
+            Self::Zstd(ref mut decoder) => {
+                decoder.write_all(chunk)?;
+                decoder.flush()?;
+                Ok(std::mem::take(&mut decoder.get_mut().data).into())
+            }
+        }
+    }
+
+    /// Finish decompressing, and return the rest of the data.
+    pub(crate) fn finish(self) -> std::io::Result<bytes::Bytes> {
+        match self {
+            #[cfg(feature = "gzip")]
+            Self::Gzip(decoder) => decoder.finish().map(|output| output.data.into()),
+            #[cfg(feature = "zstd")]
+            Self::Zstd(mut decoder) => {
+                decoder.flush()?;
+                Ok(std::mem::take(&mut decoder.get_mut().data).into())
+            }
+        }
+    }
+}
+
+/// Decompress a stream of response chunks.
+pub(crate) fn decode_stream(stream: super::ByteStream, decoder: Decoder) -> super::ByteStream {
+    use futures::TryStreamExt;
+
+    Box::pin(futures::stream::try_unfold(
+        (stream, Some(decoder)),
+        |(mut stream, mut decoder)| async move {
+            let Some(active) = decoder.as_mut() else {
+                return Ok(None);
+            };
+            let decoded = match stream.try_next().await? {
+                Some(chunk) => active.write(&chunk),
+                None => decoder.take().unwrap().finish(),
+            }
+            .map_err(super::DecthingsClientError::Decompress)?;
+            Ok(Some((decoded, (stream, decoder))))
+        },
+    ))
+}
+
+#[cfg(all(feature = "gzip", feature = "zstd"))]
+#[test]
+fn test_compression_roundtrip() {
+    let body: Vec<bytes::Bytes> = vec![
+        vec![1, 0, 2].into(),
+        br#"{"a":1}"#.to_vec().into(),
+        vec![7; 100_000].into(),
+    ];
+    let expected = body.concat();
+    for algorithm in [CompressionAlgorithm::Gzip, CompressionAlgorithm::Zstd] {
+        let (compressed, stats) = compress(&Compression::new(algorithm), body.clone());
+        let stats = stats.unwrap();
+        assert_eq!(stats.uncompressed_size, expected.len());
+        assert!(stats.ratio() < 0.1);
+
+        // Decompress in small chunks, so that the decoder has to keep state between them.
+        let mut decoder = Decoder::new(Some(algorithm.as_str()), None)
+            .unwrap()
+            .unwrap();
+        let mut decompressed = vec![];
+        for chunk in compressed.concat().chunks(100) {
+            decompressed.extend_from_slice(&decoder.write(chunk).unwrap());
+        }
+        decompressed.extend_from_slice(&decoder.finish().unwrap());
+        assert_eq!(decompressed, expected);
+
+        // Decompressing stops once the output is larger than the limit.
+        let mut decoder = Decoder::new(Some(algorithm.as_str()), Some(1000))
+            .unwrap()
+            .unwrap();
+        let res = decoder
+            .write(&compressed.concat())
+            .and_then(|_| decoder.finish());
+        assert_eq!(res.unwrap_err().kind(), std::io::ErrorKind::InvalidData);
+    }
+
+    // Small bodies are not compressed.
+    let (uncompressed, stats) = compress(
+        &Compression::new(CompressionAlgorithm::Gzip),
+        body[..2].to_vec(),
+    );
+    assert!(stats.is_none());
+    assert_eq!(uncompressed.len(), 2);
+}
+
+#[cfg(all(feature = "mock", feature = "gzip", feature = "fs"))]
+#[tokio::test]
+async fn test_compressed_requests() {
+    use crate::{
+        client::{
+            DecthingsClient, DecthingsClientOptions, Middleware, RpcResponse,
+            rpc::fs::{ReadParams, WriteParams},
+        },
+        mock::{MockResponse, MockServer},
+    };
+    use std::sync::{Arc, Mutex};
+
+    #[derive(Default)]
+    struct Recorder(Mutex<Vec<Option<CompressionStats>>>);
+
+    impl Middleware for Recorder {
+        fn on_response(&self, response: &mut RpcResponse<'_>) {
+            self.0.lock().unwrap().push(response.request_compression);
+        }
+    }
+
+    let server = MockServer::start().await.unwrap();
+    server
+        .handle("FS", "write", |req| {
+            MockResponse::result(serde_json::json!({ "bytesWritten": req.data[0].len() }))
+        })
+        .await;
+    server
+        .handle("FS", "read", |_| {
+            MockResponse::result(serde_json::json!({})).with_data([vec![3; 100_000]])
+        })
+        .await;
+
+    let recorder = Arc::new(Recorder::default());
+    let client = DecthingsClient::new(DecthingsClientOptions {
+        compression: Some(Compression::new(CompressionAlgorithm::Gzip)),
+        middleware: vec![recorder.clone()],
+        ..server.client_options()
+    });
+    let write = |data| {
+        client.fs.write(WriteParams {
+            model_id: "m",
+            inode: 1,
+            data,
+            offset: 0,
+            truncate: None,
+        })
+    };
+    assert_eq!(
+        write(vec![7; 100_000]).await.unwrap().bytes_written,
+        100_000
+    );
+    assert_eq!(write(vec![7; 4]).await.unwrap().bytes_written, 4);
+    let read = client
+        .fs
+        .read(ReadParams {
+            model_id: "m",
+            version_id: None,
+            inode: 1,
+            offset: 0,
+            count: 100_000,
+        })
+        .await
+        .unwrap();
+    assert_eq!(read.data, vec![3; 100_000]);
+
+    let seen = recorder.0.lock().unwrap();
+    let stats = seen[0].unwrap();
+    assert_eq!(stats.algorithm, CompressionAlgorithm::Gzip);
+    assert!(stats.compressed_size < stats.uncompressed_size / 10);
+    // Small requests are sent as they are.
+    assert_eq!(seen[1..], [None, None]);
+}
+
+#[cfg(all(feature = "mock", feature = "gzip", feature = "fs"))]
+#[tokio::test]
+async fn test_decompression_limit() {
+    use crate::{
+        client::{
+            DecodeLimits, DecthingsClient, DecthingsClientError, DecthingsClientOptions,
+            DecthingsRpcError, RetryPolicy, rpc::fs::ReadParams,
+        },
+        mock::{MockResponse, MockServer},
+    };
+    use std::sync::{
+        Arc,
+        atomic::{AtomicUsize, Ordering},
+    };
+
+    let server = MockServer::start().await.unwrap();
+    let calls = Arc::new(AtomicUsize::new(0));
+    let calls2 = calls.clone();
+    // 20 MB of zeros compresses to about 20 KB.
+    server
+        .handle("FS", "read", move |_| {
+            calls2.fetch_add(1, Ordering::SeqCst);
+            MockResponse::result(serde_json::json!({})).with_data([vec![0; 20_000_000]])
+        })
+        .await;
+
+    let client = DecthingsClient::new(DecthingsClientOptions {
+        compression: Some(Compression::new(CompressionAlgorithm::Gzip)),
+        decode_limits: DecodeLimits {
+            max_blobs: Some(1),
+            max_blob_size: Some(1_000_000),
+        },
+        retry_policy: Some(RetryPolicy::default()),
+        ..server.client_options()
+    });
+    let res = client
+        .fs
+        .read(ReadParams {
+            model_id: "m",
+            version_id: None,
+            inode: 1,
+            offset: 0,
+            count: 20_000_000,
+        })
+        .await;
+    let Err(DecthingsRpcError::Request(DecthingsClientError::Decompress(e))) = res else {
+        panic!("expected a decompression error, got {res:?}");
+    };
+    assert_eq!(e.kind(), std::io::ErrorKind::InvalidData);
+    // The error is not retried, since the same response would be received again.
+    assert_eq!(calls.load(Ordering::SeqCst), 1);
+}
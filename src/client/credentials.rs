@@ -0,0 +1,180 @@
+use std::{
+    future::Future,
+    pin::Pin,
+    sync::Arc,
+    time::{Duration, Instant},
+};
+
+use super::DecthingsClientError;
+
+/// Future returned by [`CredentialProvider::fetch`].
+pub type CredentialFuture<'a> = Pin<
+    Box<
+        dyn Future<Output = Result<Credential, Box<dyn std::error::Error + Send + Sync>>>
+            + Send
+            + 'a,
+    >,
+>;
+
+/// An API key returned by a [`CredentialProvider`].
+#[derive(Debug, Clone)]
+pub struct Credential {
+    pub api_key: String,
+    /// How long the key can be used, counted from when it was fetched. The client fetches a new
+    /// key shortly before it expires. If None, the key is used until Decthings rejects it.
+    pub expires_in: Option<Duration>,
+}
+
+/// Provides the API key for each request, for example by fetching short-lived keys from a secret
+/// manager. Set using `DecthingsClientOptions::credential_provider`.
+///
+/// The key is cached by the client. `fetch` is called for the first request, when the cached key
+/// is about to expire, and when Decthings rejects the cached key with the error code
+/// "bad_credentials". In the last case the request is sent once more using the new key.
+pub trait CredentialProvider: Send + Sync {
+    fn fetch(&self) -> CredentialFuture<'_>;
+}
+
+/// A key is refreshed when less than this remains before it expires, or half of its lifetime if
+/// that is shorter.
+const REFRESH_MARGIN: Duration = Duration::from_secs(30);
+
+struct CachedCredential {
+    api_key: Arc<str>,
+    fetched_at: Instant,
+    refresh_at: Option<Instant>,
+}
+
+pub(crate) struct CredentialCache {
+    provider: Arc<dyn CredentialProvider>,
+    cached: tokio::sync::Mutex<Option<CachedCredential>>,
+}
+
+impl CredentialCache {
+    pub(crate) fn new(provider: Arc<dyn CredentialProvider>) -> Self {
+        Self {
+            provider,
+            cached: tokio::sync::Mutex::new(None),
+        }
+    }
+
+    /// Returns the cached key, or fetches a new one if there is none or it is about to expire.
+    /// Concurrent requests wait for the same fetch.
+    pub(crate) async fn get(&self) -> Result<Arc<str>, DecthingsClientError> {
+        let mut cached = self.cached.lock().await;
+        if let Some(credential) = cached.as_ref()
+            && credential
+                .refresh_at
+                .is_none_or(|refresh_at| Instant::now() < refresh_at)
+        {
+            return Ok(Arc::clone(&credential.api_key));
+        }
+        let fetched_at = Instant::now();
+        let credential = self
+            .provider
+            .fetch()
+            .await
+            .map_err(DecthingsClientError::Credential)?;
+        #[cfg(feature = "tracing")]
+        tracing::debug!(expires_in = ?credential.expires_in, "Fetched new API key");
+        let api_key: Arc<str> = Arc::from(credential.api_key);
+        *cached = Some(CachedCredential {
+            api_key: Arc::clone(&api_key),
+            fetched_at,
+            refresh_at: credential
+                .expires_in
+                .map(|expires_in| fetched_at + expires_in - REFRESH_MARGIN.min(expires_in / 2)),
+        });
+        Ok(api_key)
+    }
+
+    /// Forget the cached key if it was fetched before Decthings rejected a key at `rejected_at`,
+    /// so that the next request fetches a new one. A key fetched after that is kept, so that
+    /// concurrent requests which were rejected at the same time only cause one fetch.
+    pub(crate) async fn invalidate(&self, rejected_at: Instant) {
+        let mut cached = self.cached.lock().await;
+        if cached
+            .as_ref()
+            .is_some_and(|credential| credential.fetched_at <= rejected_at)
+        {
+            *cached = None;
+        }
+    }
+}
+
+/// Whether the result is the error "bad_credentials", which means that the API key was rejected.
+pub(crate) fn is_bad_credentials_response(response: &[u8]) -> bool {
+    super::protocol::peek_error_code(response).as_deref() == Some("bad_credentials")
+}
+
+#[cfg(feature = "mock")]
+#[tokio::test]
+async fn test_credential_provider() {
+    use crate::{
+        client::{DecthingsClient, DecthingsClientOptions, RpcProtocol},
+        mock::{MockResponse, MockServer, ping},
+    };
+    use std::sync::atomic::{AtomicU32, Ordering};
+
+    struct Rotating {
+        fetched: Arc<AtomicU32>,
+    }
+
+    impl CredentialProvider for Rotating {
+        fn fetch(&self) -> CredentialFuture<'_> {
+            let n = self.fetched.fetch_add(1, Ordering::SeqCst);
+            Box::pin(async move {
+                if n == 2 {
+                    return Err("secret manager unavailable".into());
+                }
+                Ok(Credential {
+                    api_key: format!("key{n}"),
+                    expires_in: None,
+                })
+            })
+        }
+    }
+
+    let server = MockServer::start().await.unwrap();
+    server
+        .handle("Test", "ping", |req| {
+            if req.api_key.as_deref() == Some("key1") {
+                MockResponse::result(serde_json::json!({}))
+            } else {
+                MockResponse::error_code("bad_credentials")
+            }
+        })
+        .await;
+
+    for protocol in [RpcProtocol::Http, RpcProtocol::Ws] {
+        let fetched = Arc::new(AtomicU32::new(0));
+        let client = DecthingsClient::new(DecthingsClientOptions {
+            credential_provider: Some(Arc::new(Rotating {
+                fetched: fetched.clone(),
+            })),
+            default_protocol: protocol,
+            ..server.client_options()
+        });
+
+        // key0 is rejected, so key1 is fetched and the request is sent again.
+        ping(&client).await.unwrap();
+        assert_eq!(fetched.load(Ordering::SeqCst), 2);
+        // key1 is cached.
+        ping(&client).await.unwrap();
+        assert_eq!(fetched.load(Ordering::SeqCst), 2);
+
+        // If fetching a new key fails, the request fails with the error from the provider.
+        fetched.store(2, Ordering::SeqCst);
+        client
+            .rpc
+            .credentials
+            .as_ref()
+            .unwrap()
+            .invalidate(Instant::now())
+            .await;
+        assert!(matches!(
+            ping(&client).await,
+            Err(DecthingsClientError::Credential(_))
+        ));
+    }
+}
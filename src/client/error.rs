@@ -13,6 +13,12 @@ pub enum DecthingsClientError {
     /// If Decthings returned an error, DecthingsRpcError::Rpc should have been returned instead.
     Http(esp_idf_svc::sys::EspError),
 
+    /// The request failed in a custom transport set using `DecthingsClientOptions::transport`.
+    Transport(Box<dyn std::error::Error + Send + Sync>),
+
+    /// The API key could not be fetched from `DecthingsClientOptions::credential_provider`.
+    Credential(Box<dyn std::error::Error + Send + Sync>),
+
     #[cfg(feature = "events")]
     /// Failed to connect websocket to Decthings.
     WebSocketConnect(Arc<tokio_tungstenite::tungstenite::Error>),
@@ -25,11 +31,28 @@ pub enum DecthingsClientError {
     /// Failed to read websocket data from Decthings.
     WebSocketRead(Arc<tokio_tungstenite::tungstenite::Error>),
 
+    #[cfg(feature = "events")]
+    /// The WebSocket connection was lost before the response was received, for example because
+    /// Decthings did not answer a heartbeat ping in time. See
+    /// `DecthingsClientOptions::ws_keepalive`.
+    ConnectionLost,
+
     /// JSON parse failed for the data received from Decthings.
     ParseResponseFailed(serde_json::Error),
 
     /// The data received by Decthings was invalid.
     InvalidMessage,
+
+    /// A message received from Decthings could not be decoded, or exceeded
+    /// `DecthingsClientOptions::decode_limits`.
+    InvalidFrame(super::DeserializeError),
+
+    /// A compressed response from Decthings could not be decompressed, or expanded beyond
+    /// `DecthingsClientOptions::decode_limits`. See `DecthingsClientOptions::compression`.
+    Decompress(std::io::Error),
+
+    /// The request did not finish within the configured timeout.
+    Timeout,
 }
 
 #[cfg(target_os = "espidf")]
@@ -46,9 +69,51 @@ impl From<esp_idf_svc::io::EspIOError> for DecthingsClientError {
     }
 }
 
+impl DecthingsClientError {
+    /// A stable code for the kind of error, such as "timeout". Unlike the codes of the errors
+    /// returned by Decthings, these are never sent by the server.
+    pub fn code(&self) -> &'static str {
+        match self {
+            Self::Http(_) => "http",
+            Self::Transport(_) => "transport",
+            Self::Credential(_) => "credential",
+            #[cfg(feature = "events")]
+            Self::WebSocketConnect(_) => "websocket_connect",
+            #[cfg(feature = "events")]
+            Self::WebSocketWrite(_) => "websocket_write",
+            #[cfg(feature = "events")]
+            Self::WebSocketRead(_) => "websocket_read",
+            #[cfg(feature = "events")]
+            Self::ConnectionLost => "connection_lost",
+            Self::ParseResponseFailed(_) => "parse_response_failed",
+            Self::InvalidMessage => "invalid_message",
+            Self::InvalidFrame(_) => "invalid_frame",
+            Self::Decompress(_) => "decompress",
+            Self::Timeout => "timeout",
+        }
+    }
+}
+
 impl std::fmt::Display for DecthingsClientError {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        write!(f, "{:?}", self)
+        match self {
+            Self::Http(e) => write!(f, "HTTP request to Decthings failed: {e}"),
+            Self::Transport(e) => write!(f, "request to Decthings failed: {e}"),
+            Self::Credential(e) => write!(f, "failed to fetch API key: {e}"),
+            #[cfg(feature = "events")]
+            Self::WebSocketConnect(e) => write!(f, "failed to connect WebSocket to Decthings: {e}"),
+            #[cfg(feature = "events")]
+            Self::WebSocketWrite(e) => write!(f, "failed to write to WebSocket: {e}"),
+            #[cfg(feature = "events")]
+            Self::WebSocketRead(e) => write!(f, "failed to read from WebSocket: {e}"),
+            #[cfg(feature = "events")]
+            Self::ConnectionLost => write!(f, "the WebSocket connection to Decthings was lost"),
+            Self::ParseResponseFailed(e) => write!(f, "failed to parse response: {e}"),
+            Self::InvalidMessage => write!(f, "received an invalid message from Decthings"),
+            Self::InvalidFrame(e) => write!(f, "received an invalid message from Decthings: {e}"),
+            Self::Decompress(e) => write!(f, "failed to decompress response: {e}"),
+            Self::Timeout => write!(f, "request timed out"),
+        }
     }
 }
 
@@ -56,14 +121,21 @@ impl std::error::Error for DecthingsClientError {
     fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
         match self {
             Self::Http(e) => Some(e),
+            Self::Transport(e) => Some(e.as_ref()),
+            Self::Credential(e) => Some(e.as_ref()),
             #[cfg(feature = "events")]
             Self::WebSocketConnect(e) => Some(e),
             #[cfg(feature = "events")]
             Self::WebSocketRead(e) => Some(e),
             #[cfg(feature = "events")]
             Self::WebSocketWrite(e) => Some(e),
+            #[cfg(feature = "events")]
+            Self::ConnectionLost => None,
             Self::ParseResponseFailed(e) => Some(e),
             Self::InvalidMessage => None,
+            Self::InvalidFrame(e) => Some(e),
+            Self::Decompress(e) => Some(e),
+            Self::Timeout => None,
         }
     }
 }
@@ -81,13 +153,36 @@ impl From<super::websocket::WebSocketClientError> for DecthingsClientError {
             super::websocket::WebSocketClientError::Read(e) => {
                 DecthingsClientError::WebSocketRead(e)
             }
-            super::websocket::WebSocketClientError::InvalidMessage => {
-                DecthingsClientError::InvalidMessage
+            super::websocket::WebSocketClientError::InvalidFrame(e) => {
+                DecthingsClientError::InvalidFrame(e)
+            }
+            super::websocket::WebSocketClientError::ConnectionLost => {
+                DecthingsClientError::ConnectionLost
             }
         }
     }
 }
 
+/// Implemented by all error enums returned by Decthings, such as `model::CreateModelError`, so
+/// that errors from different methods can be handled in the same way.
+pub trait DecthingsErrorCode: std::fmt::Debug + std::fmt::Display {
+    /// The error code returned by Decthings, such as "model_not_found".
+    fn code(&self) -> &str;
+
+    /// Returns the name of the parameter and the reason if the error is an invalid parameter.
+    fn invalid_parameter(&self) -> Option<(&str, &str)>;
+
+    /// Returns true if the request may succeed if it is sent again later.
+    fn is_retryable(&self) -> bool {
+        matches!(self.code(), "too_many_requests" | "server_overloaded")
+    }
+
+    /// Returns true if the API key is missing, invalid or lacks access to the resource.
+    fn is_auth_error(&self) -> bool {
+        matches!(self.code(), "bad_credentials" | "access_denied")
+    }
+}
+
 #[derive(Debug)]
 pub enum DecthingsRpcError<E> {
     /// The request failed, for example network or JSON error.
@@ -96,9 +191,54 @@ pub enum DecthingsRpcError<E> {
     Rpc(E),
 }
 
-impl<E: std::fmt::Debug> std::fmt::Display for DecthingsRpcError<E> {
+impl<E: std::fmt::Display> std::fmt::Display for DecthingsRpcError<E> {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        write!(f, "{:?}", self)
+        match self {
+            Self::Request(e) => e.fmt(f),
+            Self::Rpc(e) => e.fmt(f),
+        }
+    }
+}
+
+impl<E: DecthingsErrorCode> DecthingsRpcError<E> {
+    /// The error code returned by Decthings, or None if the request failed.
+    pub fn code(&self) -> Option<&str> {
+        match self {
+            Self::Request(_) => None,
+            Self::Rpc(e) => Some(e.code()),
+        }
+    }
+
+    /// Returns true if the request may succeed if it is sent again later. Errors such as network
+    /// failures and timeouts are retryable, but not invalid responses or responses which exceed
+    /// `DecthingsClientOptions::decode_limits`.
+    pub fn is_retryable(&self) -> bool {
+        match self {
+            Self::Request(e) => !matches!(
+                e,
+                DecthingsClientError::ParseResponseFailed(_)
+                    | DecthingsClientError::InvalidMessage
+                    | DecthingsClientError::InvalidFrame(_)
+                    | DecthingsClientError::Decompress(_)
+            ),
+            Self::Rpc(e) => e.is_retryable(),
+        }
+    }
+
+    /// Returns true if Decthings rejected the API key.
+    pub fn is_auth_error(&self) -> bool {
+        match self {
+            Self::Request(_) => false,
+            Self::Rpc(e) => e.is_auth_error(),
+        }
+    }
+
+    /// Returns the name of the parameter and the reason if Decthings rejected a parameter.
+    pub fn invalid_parameter(&self) -> Option<(&str, &str)> {
+        match self {
+            Self::Request(_) => None,
+            Self::Rpc(e) => e.invalid_parameter(),
+        }
     }
 }
 
@@ -114,7 +254,7 @@ impl<E> From<DecthingsClientError> for DecthingsRpcError<E> {
     }
 }
 
-impl<E: std::fmt::Debug> std::error::Error for DecthingsRpcError<E> {
+impl<E: std::fmt::Debug + std::fmt::Display> std::error::Error for DecthingsRpcError<E> {
     fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
         match self {
             Self::Request(e) => Some(e),
@@ -122,3 +262,30 @@ impl<E: std::fmt::Debug> std::error::Error for DecthingsRpcError<E> {
         }
     }
 }
+
+#[cfg(feature = "model")]
+#[test]
+fn test_error_code() {
+    use super::rpc::model::CreateModelError;
+
+    let err: DecthingsRpcError<CreateModelError> = DecthingsRpcError::Rpc(
+        serde_json::from_str(
+            r#"{"code":"invalid_parameter","parameterName":"name","reason":"Too long"}"#,
+        )
+        .unwrap(),
+    );
+    assert_eq!(err.code(), Some("invalid_parameter"));
+    assert_eq!(err.invalid_parameter(), Some(("name", "Too long")));
+    assert!(!err.is_retryable());
+    assert!(!err.is_auth_error());
+    assert_eq!(err.to_string(), "parameter 'name' invalid: Too long");
+
+    let err: CreateModelError = serde_json::from_str(r#"{"code":"bad_credentials"}"#).unwrap();
+    assert!(err.is_auth_error());
+    let err: CreateModelError = serde_json::from_str(r#"{"code":"too_many_requests"}"#).unwrap();
+    assert!(err.is_retryable());
+    assert_eq!(err.to_string(), "too many requests");
+
+    let err: DecthingsRpcError<CreateModelError> = DecthingsClientError::Timeout.into();
+    assert_eq!(err.to_string(), "request timed out");
+}
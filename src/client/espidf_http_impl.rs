@@ -1,6 +1,8 @@
 use std::sync::Arc;
 
-use super::DecthingsClientError;
+use super::{
+    DecodeLimits, DecthingsClientError, Transport, TransportFuture, proxy::Proxy, tls::TlsConfig,
+};
 use embedded_svc::{http::client::Client, io::Write, utils::io::try_read_full};
 use esp_idf_svc::{
     http::client::{Configuration, EspHttpConnection},
@@ -8,20 +10,34 @@ use esp_idf_svc::{
 };
 use esp_idf_sys::EspError;
 
-#[derive(Clone, Default)]
-pub(crate) struct HttpImpl {}
+pub(crate) struct HttpImpl {
+    http_server_address: String,
+}
 
 impl HttpImpl {
-    pub async fn get(
+    /// The TLS, proxy and compression options are not supported. The ESP-IDF certificate bundle is
+    /// always used.
+    pub fn new(
+        http_server_address: String,
+        _tls: &TlsConfig,
+        _proxy: Option<&Proxy>,
+        _accept_compressed: bool,
+        _decode_limits: &DecodeLimits,
+    ) -> Self {
+        Self {
+            http_server_address,
+        }
+    }
+
+    async fn get_impl(
         &self,
-        http_server_address: &str,
         api: &str,
         method: &str,
-        body: Vec<u8>,
+        body: Vec<bytes::Bytes>,
         api_key: Option<Arc<str>>,
         extra_headers: Arc<http::HeaderMap<http::HeaderValue>>,
     ) -> Result<bytes::Bytes, DecthingsClientError> {
-        let path = format!("{}/{}/{}", http_server_address, api, method);
+        let path = format!("{}/{}/{}", self.http_server_address, api, method);
         tokio::task::spawn_blocking(move || {
             let mut client = Client::wrap(EspHttpConnection::new(&Configuration {
                 crt_bundle_attach: Some(esp_idf_sys::esp_crt_bundle_attach),
@@ -33,7 +49,11 @@ impl HttpImpl {
                 Vec::with_capacity(extra_headers.len() + 2 + if api_key.is_some() { 1 } else { 0 });
 
             headers.push(("content-type", "application/octet-stream"));
-            let content_length_str = body.len().to_string();
+            let content_length_str = body
+                .iter()
+                .map(|segment| segment.len())
+                .sum::<usize>()
+                .to_string();
             headers.push(("content-length", &content_length_str));
 
             let bearer_auth = api_key.map(|api_key| format!("Bearer {api_key}"));
@@ -52,7 +72,9 @@ impl HttpImpl {
 
             let mut req = client.post(&path, &headers)?;
 
-            req.write_all(&body)?;
+            for segment in &body {
+                req.write_all(segment)?;
+            }
             req.flush()?;
 
             let mut response = req.submit()?;
@@ -82,3 +104,16 @@ impl HttpImpl {
         .unwrap()
     }
 }
+
+impl Transport for HttpImpl {
+    fn get<'a>(
+        &'a self,
+        api: &'a str,
+        method: &'a str,
+        body: Vec<bytes::Bytes>,
+        api_key: Option<Arc<str>>,
+        extra_headers: Arc<http::HeaderMap<http::HeaderValue>>,
+    ) -> TransportFuture<'a> {
+        Box::pin(self.get_impl(api, method, body, api_key, extra_headers))
+    }
+}
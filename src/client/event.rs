@@ -1,27 +1,72 @@
+// Without any of the APIs which have events, all events are unrecognized.
+#![cfg_attr(
+    not(any(
+        feature = "debug",
+        feature = "language",
+        feature = "spawned",
+        feature = "terminal"
+    )),
+    allow(dead_code, unused_mut, unused_variables)
+)]
+
 use std::{
-    collections::HashMap,
-    sync::{Arc, Weak},
+    collections::{HashMap, VecDeque},
+    pin::Pin,
+    sync::{Arc, Mutex, Weak},
+    task::{Context, Poll, Waker},
 };
 
-use super::{
-    rpc::{
-        debug::DebugEvent, language::LanguageEvent, spawned::SpawnedEvent, terminal::TerminalEvent,
-    },
-    StateModification,
-};
-use tokio::sync::Mutex;
+use super::StateModification;
+#[cfg(feature = "debug")]
+use super::rpc::debug::DebugEvent;
+#[cfg(feature = "language")]
+use super::rpc::language::LanguageEvent;
+#[cfg(feature = "spawned")]
+use super::rpc::spawned::SpawnedEvent;
+#[cfg(feature = "terminal")]
+use super::rpc::terminal::TerminalEvent;
 
 #[derive(Debug, Clone)]
 pub enum DecthingsEvent {
+    #[cfg(feature = "debug")]
     Debug(DebugEvent),
+    #[cfg(feature = "language")]
     Language(LanguageEvent),
+    #[cfg(feature = "spawned")]
     Spawned(SpawnedEvent),
+    #[cfg(feature = "terminal")]
     Terminal(TerminalEvent),
     /// This event will be emitted when the Websocket connection to Decthings closes unexpectedly.
     /// This means that all subscriptions are cancelled. To solve this, call subscribe for the
     /// corresponding API again (no need to call on_event again). Note that you may miss some
     /// events while the subscription is inactive.
+    ///
+    /// If `DecthingsClientOptions::ws_auto_reconnect` is set, the client subscribes again
+    /// automatically and emits Resubscribed when done.
     SubscriptionsRemoved,
+    /// This event will be emitted after the client has reconnected the WebSocket and subscribed
+    /// again to the events which were active when the connection closed. Only emitted if
+    /// `DecthingsClientOptions::ws_auto_reconnect` is set.
+    Resubscribed {
+        /// Subscriptions which are active again.
+        restored: Vec<EventSubscription>,
+        /// Subscriptions which could not be restored, along with the reason. For example, a
+        /// spawned command may have exited while the connection was closed.
+        failed: Vec<(EventSubscription, String)>,
+    },
+    /// An event from an API which is not known by this version of the client, or whose feature is
+    /// not enabled.
+    Unrecognized { api: String, raw: serde_json::Value },
+}
+
+/// A subscription to the events of a spawned command, terminal session, debug session or language
+/// server.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct EventSubscription {
+    /// The API the subscription belongs to, such as "Spawned" or "Terminal".
+    pub api: String,
+    /// The id of the spawned command, terminal session, debug session or language server.
+    pub id: String,
 }
 
 impl DecthingsEvent {
@@ -31,6 +76,7 @@ impl DecthingsEvent {
         mut blobs: Vec<bytes::Bytes>,
     ) -> Result<(DecthingsEvent, StateModification), ()> {
         match api {
+            #[cfg(feature = "debug")]
             b"Debug" => {
                 let mut deserialized: DebugEvent = serde_json::from_slice(data).map_err(|_| ())?;
                 let state_modification = match &mut deserialized {
@@ -74,9 +120,11 @@ impl DecthingsEvent {
                         *data = blobs.remove(0);
                         StateModification::empty()
                     }
+                    DebugEvent::Unrecognized { raw: _ } => StateModification::empty(),
                 };
                 Ok((DecthingsEvent::Debug(deserialized), state_modification))
             }
+            #[cfg(feature = "language")]
             b"Language" => {
                 let mut deserialized: LanguageEvent =
                     serde_json::from_slice(data).map_err(|_| ())?;
@@ -98,9 +146,11 @@ impl DecthingsEvent {
                         *data = blobs.remove(0);
                         StateModification::empty()
                     }
+                    LanguageEvent::Unrecognized { raw: _ } => StateModification::empty(),
                 };
                 Ok((DecthingsEvent::Language(deserialized), state_modification))
             }
+            #[cfg(feature = "spawned")]
             b"Spawned" => {
                 let mut deserialized: SpawnedEvent =
                     serde_json::from_slice(data).map_err(|_| ())?;
@@ -132,9 +182,11 @@ impl DecthingsEvent {
                         *data = blobs.remove(0);
                         StateModification::empty()
                     }
+                    SpawnedEvent::Unrecognized { raw: _ } => StateModification::empty(),
                 };
                 Ok((DecthingsEvent::Spawned(deserialized), state_modification))
             }
+            #[cfg(feature = "terminal")]
             b"Terminal" => {
                 let mut deserialized: TerminalEvent =
                     serde_json::from_slice(data).map_err(|_| ())?;
@@ -156,62 +208,976 @@ impl DecthingsEvent {
                         *data = blobs.remove(0);
                         StateModification::empty()
                     }
+                    TerminalEvent::Unrecognized { raw: _ } => StateModification::empty(),
                 };
                 Ok((DecthingsEvent::Terminal(deserialized), state_modification))
             }
-            _ => Err(()),
+            _ => {
+                let raw = serde_json::from_slice(data).map_err(|_| ())?;
+                Ok((
+                    DecthingsEvent::Unrecognized {
+                        api: String::from_utf8_lossy(api).into_owned(),
+                        raw,
+                    },
+                    StateModification::empty(),
+                ))
+            }
+        }
+    }
+}
+
+/// The events of a single spawned command, terminal session, debug session or language server.
+pub(crate) trait SessionEvent: Clone + Send + 'static {
+    const API: &'static str;
+
+    fn from_event(event: &DecthingsEvent) -> Option<&Self>;
+
+    /// The id of the session the event belongs to. None for unrecognized events.
+    fn session_id(&self) -> Option<&str>;
+
+    fn is_exit(&self) -> bool;
+}
+
+#[cfg(feature = "debug")]
+impl SessionEvent for DebugEvent {
+    const API: &'static str = "Debug";
+
+    fn from_event(event: &DecthingsEvent) -> Option<&Self> {
+        match event {
+            DecthingsEvent::Debug(event) => Some(event),
+            _ => None,
+        }
+    }
+
+    fn session_id(&self) -> Option<&str> {
+        match self {
+            Self::Exit {
+                debug_session_id, ..
+            }
+            | Self::Stdout {
+                debug_session_id, ..
+            }
+            | Self::Stderr {
+                debug_session_id, ..
+            }
+            | Self::Initialized { debug_session_id }
+            | Self::RemoteInspectorData {
+                debug_session_id, ..
+            } => Some(debug_session_id),
+            Self::Unrecognized { .. } => None,
+        }
+    }
+
+    fn is_exit(&self) -> bool {
+        matches!(self, Self::Exit { .. })
+    }
+}
+
+#[cfg(feature = "language")]
+impl SessionEvent for LanguageEvent {
+    const API: &'static str = "Language";
+
+    fn from_event(event: &DecthingsEvent) -> Option<&Self> {
+        match event {
+            DecthingsEvent::Language(event) => Some(event),
+            _ => None,
+        }
+    }
+
+    fn session_id(&self) -> Option<&str> {
+        match self {
+            Self::Exit {
+                language_server_id, ..
+            }
+            | Self::Data {
+                language_server_id, ..
+            } => Some(language_server_id),
+            Self::Unrecognized { .. } => None,
+        }
+    }
+
+    fn is_exit(&self) -> bool {
+        matches!(self, Self::Exit { .. })
+    }
+}
+
+#[cfg(feature = "spawned")]
+impl SessionEvent for SpawnedEvent {
+    const API: &'static str = "Spawned";
+
+    fn from_event(event: &DecthingsEvent) -> Option<&Self> {
+        match event {
+            DecthingsEvent::Spawned(event) => Some(event),
+            _ => None,
+        }
+    }
+
+    fn session_id(&self) -> Option<&str> {
+        match self {
+            Self::Exit {
+                spawned_command_id, ..
+            }
+            | Self::Stdout {
+                spawned_command_id, ..
+            }
+            | Self::Stderr {
+                spawned_command_id, ..
+            } => Some(spawned_command_id),
+            Self::Unrecognized { .. } => None,
+        }
+    }
+
+    fn is_exit(&self) -> bool {
+        matches!(self, Self::Exit { .. })
+    }
+}
+
+#[cfg(feature = "terminal")]
+impl SessionEvent for TerminalEvent {
+    const API: &'static str = "Terminal";
+
+    fn from_event(event: &DecthingsEvent) -> Option<&Self> {
+        match event {
+            DecthingsEvent::Terminal(event) => Some(event),
+            _ => None,
+        }
+    }
+
+    fn session_id(&self) -> Option<&str> {
+        match self {
+            Self::Exit {
+                terminal_session_id,
+                ..
+            }
+            | Self::Data {
+                terminal_session_id,
+                ..
+            } => Some(terminal_session_id),
+            Self::Unrecognized { .. } => None,
+        }
+    }
+
+    fn is_exit(&self) -> bool {
+        matches!(self, Self::Exit { .. })
+    }
+}
+
+/// Yielded by an [`EventStream`] in place of events which were dropped because the stream was not
+/// read fast enough. Contains the number of dropped events.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Lagged(pub u64);
+
+impl std::fmt::Display for Lagged {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "the event stream lagged behind and missed {} events",
+            self.0
+        )
+    }
+}
+
+impl std::error::Error for Lagged {}
+
+/// Number of events an [`EventStream`] holds before it starts dropping them.
+const EVENT_STREAM_CAPACITY: usize = 1024;
+
+struct EventStreamState<T> {
+    queue: VecDeque<Result<T, Lagged>>,
+    /// Number of events dropped since the last event which was queued.
+    lagged: u64,
+    ended: bool,
+    waker: Option<Waker>,
+}
+
+/// A stream of the events of a single spawned command, terminal session, debug session or
+/// language server, created using for example `client.spawned.events(spawned_command_id)`.
+///
+/// Only events received after the stream was created are included, and the events must be
+/// subscribed to separately, for example using `subscribe_to_events`. The stream ends after the
+/// Exit event, or when the subscription is lost because the WebSocket connection closed and was
+/// not restored.
+///
+/// Events are buffered so that the WebSocket connection is never blocked by a slow reader. If
+/// the buffer is full, new events are dropped and the stream yields [`Lagged`] in their place.
+/// The Exit event is never dropped.
+pub struct EventStream<T> {
+    state: Arc<Mutex<EventStreamState<T>>>,
+    disposer: Option<EventListenerDisposer>,
+}
+
+impl<T> std::fmt::Debug for EventStream<T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("EventStream").finish_non_exhaustive()
+    }
+}
+
+impl<T> EventStream<T> {
+    /// If `resubscribes` is false, the stream ends when the WebSocket connection closes.
+    pub(super) fn new(event_listeners: &Arc<EventListeners>, id: String, resubscribes: bool) -> Self
+    where
+        T: SessionEvent,
+    {
+        let state = Arc::new(Mutex::new(EventStreamState {
+            queue: VecDeque::new(),
+            lagged: 0,
+            ended: false,
+            waker: None,
+        }));
+        let state_clone = Arc::clone(&state);
+        let disposer = event_listeners.add(move |event| {
+            let (event, end) = match event {
+                DecthingsEvent::SubscriptionsRemoved => (None, !resubscribes),
+                DecthingsEvent::Resubscribed { failed, .. } => (
+                    None,
+                    failed.iter().any(|(subscription, _)| {
+                        subscription.api == T::API && subscription.id == id
+                    }),
+                ),
+                event => match T::from_event(event) {
+                    Some(event) if event.session_id() == Some(id.as_str()) => {
+                        (Some(event), event.is_exit())
+                    }
+                    _ => return,
+                },
+            };
+            let mut state = state_clone.lock().unwrap();
+            if state.ended {
+                return;
+            }
+            if let Some(event) = event {
+                if state.queue.len() >= EVENT_STREAM_CAPACITY && !end {
+                    state.lagged += 1;
+                    return;
+                }
+                if state.lagged > 0 {
+                    let lagged = std::mem::take(&mut state.lagged);
+                    state.queue.push_back(Err(Lagged(lagged)));
+                }
+                state.queue.push_back(Ok(event.clone()));
+            }
+            state.ended = end;
+            if let Some(waker) = state.waker.take() {
+                waker.wake();
+            }
+        });
+        Self {
+            state,
+            disposer: Some(disposer),
+        }
+    }
+}
+
+impl<T> futures::Stream for EventStream<T> {
+    type Item = Result<T, Lagged>;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        let mut state = self.state.lock().unwrap();
+        if let Some(item) = state.queue.pop_front() {
+            return Poll::Ready(Some(item));
+        }
+        if state.lagged > 0 {
+            let lagged = std::mem::take(&mut state.lagged);
+            return Poll::Ready(Some(Err(Lagged(lagged))));
+        }
+        if state.ended {
+            return Poll::Ready(None);
+        }
+        state.waker = Some(cx.waker().clone());
+        Poll::Pending
+    }
+}
+
+impl<T> Drop for EventStream<T> {
+    fn drop(&mut self) {
+        if let Some(disposer) = self.disposer.take() {
+            disposer.remove();
+        }
+    }
+}
+
+/// What to do when an event is emitted while the queue of an async listener is full. See
+/// [`EventListenerOptions`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum OverflowPolicy {
+    /// Drop the oldest queued event to make room for the new one.
+    #[default]
+    DropOldest,
+    /// Wait until there is room in the queue. This stops the WebSocket connection from reading
+    /// any further messages, including responses to requests, until the listener catches up.
+    Block,
+    /// Drop the new event and remove the listener. Events which are already queued are still
+    /// delivered.
+    Disconnect,
+}
+
+/// Options for an async event listener, added using `DecthingsClient::on_event_async`.
+#[derive(Debug, Clone, Copy)]
+pub struct EventListenerOptions {
+    /// Number of events which can wait to be handled by the listener. Default: 1024.
+    pub capacity: usize,
+    /// What to do when the queue is full. Default: drop the oldest event.
+    pub overflow: OverflowPolicy,
+}
+
+impl Default for EventListenerOptions {
+    fn default() -> Self {
+        Self {
+            capacity: 1024,
+            overflow: OverflowPolicy::DropOldest,
+        }
+    }
+}
+
+type DecthingsClientEventListener = Arc<dyn Fn(&DecthingsEvent) + Send + Sync>;
+
+struct ListenerQueueState {
+    events: VecDeque<DecthingsEvent>,
+    /// Set when the listener has been removed. No more events are queued after that.
+    closed: bool,
+}
+
+/// The queue of an async listener. Events are pushed by the WebSocket read loop and handled by a
+/// task which is spawned for each listener.
+struct ListenerQueue {
+    state: Mutex<ListenerQueueState>,
+    options: EventListenerOptions,
+    dropped: std::sync::atomic::AtomicU64,
+    /// Notified when an event is queued or the queue is closed.
+    pushed: tokio::sync::Notify,
+    /// Notified when an event is taken from the queue.
+    popped: tokio::sync::Notify,
+}
+
+impl ListenerQueue {
+    /// Queue an event. Returns false if the listener should be removed.
+    async fn push(&self, ev: &DecthingsEvent) -> bool {
+        loop {
+            // The guard is dropped at the end of the block, before waiting for room.
+            let popped = {
+                let mut state = self.state.lock().unwrap();
+                if state.closed {
+                    return true;
+                }
+                if state.events.len() < self.options.capacity.max(1) {
+                    state.events.push_back(ev.clone());
+                    drop(state);
+                    self.pushed.notify_one();
+                    return true;
+                }
+                match self.options.overflow {
+                    OverflowPolicy::DropOldest => {
+                        state.events.pop_front();
+                        state.events.push_back(ev.clone());
+                        drop(state);
+                        self.add_dropped();
+                        return true;
+                    }
+                    OverflowPolicy::Disconnect => {
+                        state.closed = true;
+                        drop(state);
+                        self.add_dropped();
+                        self.pushed.notify_one();
+                        return false;
+                    }
+                    OverflowPolicy::Block => self.popped.notified(),
+                }
+            };
+            popped.await;
+        }
+    }
+
+    fn add_dropped(&self) {
+        self.dropped
+            .fetch_add(1, std::sync::atomic::Ordering::Relaxed);
+        #[cfg(feature = "tracing")]
+        tracing::warn!("Dropped an event because an event listener is too slow");
+    }
+
+    fn close(&self, discard_queued: bool) {
+        let mut state = self.state.lock().unwrap();
+        state.closed = true;
+        if discard_queued {
+            state.events.clear();
+        }
+        drop(state);
+        self.pushed.notify_one();
+        self.popped.notify_one();
+    }
+
+    /// Wait for the next event. Returns None when the queue is closed and empty.
+    async fn pop(&self) -> Option<DecthingsEvent> {
+        loop {
+            let pushed = self.pushed.notified();
+            {
+                let mut state = self.state.lock().unwrap();
+                if let Some(ev) = state.events.pop_front() {
+                    drop(state);
+                    self.popped.notify_one();
+                    return Some(ev);
+                }
+                if state.closed {
+                    return None;
+                }
+            }
+            pushed.await;
         }
     }
 }
 
-type DecthingsClientEventListener = Box<dyn Fn(&DecthingsEvent) + Send + Sync>;
+enum EventListener {
+    Sync(DecthingsClientEventListener),
+    Queued(Arc<ListenerQueue>),
+}
 
 pub(super) struct EventListeners {
-    listeners: Mutex<HashMap<u64, DecthingsClientEventListener>>,
+    listeners: Mutex<HashMap<u64, EventListener>>,
+    /// Ids are never reused, so that a disposer cannot remove a listener which was added after its
+    /// own listener was removed.
+    next_id: std::sync::atomic::AtomicU64,
 }
 
 impl EventListeners {
     pub fn new() -> Self {
         Self {
             listeners: Mutex::new(HashMap::new()),
+            next_id: std::sync::atomic::AtomicU64::new(0),
         }
     }
 
-    pub async fn add(
-        self: &Arc<Self>,
-        ev: impl Fn(&DecthingsEvent) + Send + Sync + 'static,
-    ) -> EventListenerDisposer {
-        let mut lock = self.listeners.lock().await;
-        let mut id = 0;
-        while lock.contains_key(&id) {
-            id += 1;
-        }
-        lock.insert(id, Box::new(ev));
-        drop(lock);
+    fn insert(self: &Arc<Self>, listener: EventListener) -> EventListenerDisposer {
+        let queue = match &listener {
+            EventListener::Sync(_) => None,
+            EventListener::Queued(queue) => Some(Arc::clone(queue)),
+        };
+        let id = self
+            .next_id
+            .fetch_add(1, std::sync::atomic::Ordering::Relaxed);
+        self.listeners.lock().unwrap().insert(id, listener);
         EventListenerDisposer {
             event_listeners: Arc::downgrade(self),
             id,
+            queue,
         }
     }
 
+    /// Add a listener which is called directly by the WebSocket read loop.
+    pub fn add(
+        self: &Arc<Self>,
+        ev: impl Fn(&DecthingsEvent) + Send + Sync + 'static,
+    ) -> EventListenerDisposer {
+        self.insert(EventListener::Sync(Arc::new(ev)))
+    }
+
+    /// Add a listener which is called from its own task, with the events passed through a queue.
+    pub fn add_async<F, Fut>(
+        self: &Arc<Self>,
+        options: EventListenerOptions,
+        ev: F,
+    ) -> EventListenerDisposer
+    where
+        F: Fn(DecthingsEvent) -> Fut + Send + Sync + 'static,
+        Fut: std::future::Future<Output = ()> + Send + 'static,
+    {
+        let queue = Arc::new(ListenerQueue {
+            state: Mutex::new(ListenerQueueState {
+                events: VecDeque::new(),
+                closed: false,
+            }),
+            options,
+            dropped: std::sync::atomic::AtomicU64::new(0),
+            pushed: tokio::sync::Notify::new(),
+            popped: tokio::sync::Notify::new(),
+        });
+        let queue_clone = Arc::clone(&queue);
+        tokio::spawn(async move {
+            while let Some(event) = queue_clone.pop().await {
+                ev(event).await;
+            }
+        });
+        self.insert(EventListener::Queued(queue))
+    }
+
     pub async fn call(&self, ev: &DecthingsEvent) {
-        let locked = self.listeners.lock().await;
-        for listener in locked.values() {
+        // The lock is released before the listeners are called, since a listener may add or
+        // remove listeners, and before pushing to the queues, since pushing may wait for room.
+        let mut sync_listeners = vec![];
+        let mut queues = vec![];
+        {
+            let locked = self.listeners.lock().unwrap();
+            for (id, listener) in locked.iter() {
+                match listener {
+                    EventListener::Sync(listener) => sync_listeners.push(Arc::clone(listener)),
+                    EventListener::Queued(queue) => queues.push((*id, Arc::clone(queue))),
+                }
+            }
+        }
+        for listener in sync_listeners {
             listener(ev);
         }
+        for (id, queue) in queues {
+            if !queue.push(ev).await {
+                self.listeners.lock().unwrap().remove(&id);
+            }
+        }
+    }
+}
+
+impl Drop for EventListeners {
+    fn drop(&mut self) {
+        // Stop the tasks of async listeners once they have handled the queued events.
+        let listeners = self.listeners.get_mut().unwrap();
+        for listener in listeners.values() {
+            if let EventListener::Queued(queue) = listener {
+                queue.close(false);
+            }
+        }
     }
 }
 
 pub struct EventListenerDisposer {
     event_listeners: Weak<EventListeners>,
     id: u64,
+    queue: Option<Arc<ListenerQueue>>,
 }
 
 impl EventListenerDisposer {
+    /// Remove the listener. Events which are queued for an async listener are discarded.
     pub async fn dispose(self) {
+        self.remove();
+    }
+
+    /// Number of events which were dropped because the queue of the listener was full. Always 0
+    /// for listeners added using `on_event`.
+    pub fn dropped_events(&self) -> u64 {
+        self.queue.as_ref().map_or(0, |queue| {
+            queue.dropped.load(std::sync::atomic::Ordering::Relaxed)
+        })
+    }
+
+    /// Whether the listener was removed because its queue was full and its overflow policy is
+    /// [`OverflowPolicy::Disconnect`].
+    pub fn is_disconnected(&self) -> bool {
+        self.queue
+            .as_ref()
+            .is_some_and(|queue| queue.state.lock().unwrap().closed)
+    }
+
+    fn remove(&self) {
+        if let Some(queue) = &self.queue {
+            queue.close(true);
+        }
         if let Some(event_listeners) = self.event_listeners.upgrade() {
-            let mut lock = event_listeners.listeners.lock().await;
-            lock.remove(&self.id);
+            // The listener is dropped after the lock is released, in case dropping it removes
+            // another listener.
+            let removed = event_listeners.listeners.lock().unwrap().remove(&self.id);
+            drop(removed);
         }
     }
 }
+
+#[cfg(all(feature = "mock", feature = "spawned"))]
+#[tokio::test]
+async fn test_resubscribe_on_reconnect() {
+    use crate::{
+        client::rpc::{ExecutionLocationProvider, spawned},
+        mock::{MockResponse, MockServer},
+    };
+
+    let server = MockServer::start().await.unwrap();
+    let counter = std::sync::atomic::AtomicU32::new(0);
+    server
+        .handle("Spawned", "spawnCommand", move |_| {
+            let id = counter.fetch_add(1, std::sync::atomic::Ordering::Relaxed);
+            MockResponse::result(serde_json::json!({ "spawnedCommandId": format!("cmd{id}") }))
+        })
+        .await;
+    server
+        .handle("Spawned", "subscribeToEvents", |req| {
+            if req.params["spawnedCommandId"] == "cmd0" {
+                MockResponse::result(serde_json::json!({}))
+            } else {
+                MockResponse::error_code("spawned_command_not_found")
+            }
+        })
+        .await;
+
+    let client = crate::client::DecthingsClient::new(crate::client::DecthingsClientOptions {
+        ws_auto_reconnect: true,
+        ..server.client_options()
+    });
+    let (tx, mut rx) = tokio::sync::mpsc::unbounded_channel();
+    let _disposer = client
+        .on_event(move |event| {
+            tx.send(event.clone()).ok();
+        })
+        .await;
+
+    for _ in 0..2 {
+        client
+            .spawned
+            .spawn_command(spawned::SpawnCommandParams {
+                execution_location: ExecutionLocationProvider::PersistentLauncher {
+                    persistent_launcher_id: "launcher",
+                },
+                command: "sleep",
+                args: &["100"],
+                options: None,
+                subscribe_to_events: None,
+            })
+            .await
+            .unwrap();
+    }
+
+    server.disconnect_websockets().await;
+
+    assert!(matches!(
+        rx.recv().await.unwrap(),
+        DecthingsEvent::SubscriptionsRemoved
+    ));
+    // A socket which is opened for a failed subscription closes again, so SubscriptionsRemoved may
+    // be emitted more than once.
+    let (restored, failed) = loop {
+        match rx.recv().await.unwrap() {
+            DecthingsEvent::Resubscribed { restored, failed } => break (restored, failed),
+            DecthingsEvent::SubscriptionsRemoved => {}
+            e => panic!("expected Resubscribed event, got {e:?}"),
+        }
+    };
+    assert_eq!(
+        restored,
+        vec![EventSubscription {
+            api: "Spawned".to_owned(),
+            id: "cmd0".to_owned(),
+        }]
+    );
+    assert_eq!(failed.len(), 1);
+    assert_eq!(failed[0].0.id, "cmd1");
+    assert_eq!(failed[0].1, "spawned_command_not_found");
+    assert_eq!(server.connected_websockets().await, 1);
+}
+
+#[cfg(all(feature = "mock", feature = "spawned"))]
+#[tokio::test]
+async fn test_unrecognized_events() {
+    use crate::{
+        client::rpc::{ExecutionLocationProvider, spawned},
+        mock::{MockResponse, MockServer},
+    };
+
+    let server = MockServer::start().await.unwrap();
+    server
+        .handle("Spawned", "spawnCommand", |_| {
+            MockResponse::result(serde_json::json!({ "spawnedCommandId": "cmd" }))
+        })
+        .await;
+    server
+        .handle("Spawned", "writeToSpawnedCommand", |_| {
+            MockResponse::error_code("new_error_code")
+        })
+        .await;
+
+    let client = crate::client::DecthingsClient::new(server.client_options());
+    let (tx, mut rx) = tokio::sync::mpsc::unbounded_channel();
+    let _disposer = client
+        .on_event(move |event| {
+            tx.send(event.clone()).ok();
+        })
+        .await;
+    client
+        .spawned
+        .spawn_command(spawned::SpawnCommandParams {
+            execution_location: ExecutionLocationProvider::PersistentLauncher {
+                persistent_launcher_id: "launcher",
+            },
+            command: "sleep",
+            args: &["100"],
+            options: None,
+            subscribe_to_events: None,
+        })
+        .await
+        .unwrap();
+
+    let event = serde_json::json!({ "event": "newEvent", "params": { "spawnedCommandId": "cmd" } });
+    server
+        .push_event("Spawned", &event, Vec::<bytes::Bytes>::new())
+        .await;
+    server
+        .push_event("NewApi", &event, Vec::<bytes::Bytes>::new())
+        .await;
+    // Stdout without its data blob is invalid, and should be skipped without closing the socket.
+    server
+        .push_event(
+            "Spawned",
+            serde_json::json!({ "event": "stdout", "params": { "spawnedCommandId": "cmd" } }),
+            Vec::<bytes::Bytes>::new(),
+        )
+        .await;
+    server
+        .push_event(
+            "Spawned",
+            serde_json::json!({ "event": "stdout", "params": { "spawnedCommandId": "cmd" } }),
+            [b"out".to_vec()],
+        )
+        .await;
+    // A known event with an unknown value nested in it.
+    let reason = serde_json::json!({ "code": "new_reason", "detail": 1 });
+    server
+        .push_event(
+            "Spawned",
+            serde_json::json!({
+                "event": "exit",
+                "params": { "spawnedCommandId": "cmd", "reason": reason },
+            }),
+            Vec::<bytes::Bytes>::new(),
+        )
+        .await;
+
+    assert!(matches!(
+        rx.recv().await.unwrap(),
+        DecthingsEvent::Spawned(spawned::SpawnedEvent::Unrecognized { raw }) if raw == event
+    ));
+    assert!(matches!(
+        rx.recv().await.unwrap(),
+        DecthingsEvent::Unrecognized { api, raw } if api == "NewApi" && raw == event
+    ));
+    assert!(matches!(
+        rx.recv().await.unwrap(),
+        DecthingsEvent::Spawned(spawned::SpawnedEvent::Stdout { data, .. }) if &data[..] == b"out"
+    ));
+    assert!(matches!(
+        rx.recv().await.unwrap(),
+        DecthingsEvent::Spawned(spawned::SpawnedEvent::Exit {
+            reason: spawned::SpawnedCommandTerminatedReason::Unrecognized { raw },
+            ..
+        }) if raw == reason
+    ));
+
+    let err = client
+        .spawned
+        .write_to_spawned_command(spawned::WriteToSpawnedCommandParams {
+            spawned_command_id: "cmd",
+            data: bytes::Bytes::from_static(b"in"),
+        })
+        .await
+        .unwrap_err();
+    assert_eq!(err.code(), Some("new_error_code"));
+    assert_eq!(err.to_string(), "unrecognized error 'new_error_code'");
+}
+
+#[cfg(all(feature = "mock", feature = "spawned"))]
+#[tokio::test]
+async fn test_event_stream() {
+    use crate::mock::{MockResponse, MockServer};
+    use futures::StreamExt;
+
+    let server = MockServer::start().await.unwrap();
+    server
+        .handle("Spawned", "subscribeToEvents", |_| {
+            MockResponse::result(serde_json::json!({}))
+        })
+        .await;
+
+    let client = server.client();
+    let mut stream = client.spawned.events("cmd");
+    client
+        .spawned
+        .subscribe_to_events(
+            crate::client::rpc::spawned::SpawnedSubscribeToEventsParams {
+                spawned_command_id: "cmd",
+            },
+        )
+        .await
+        .unwrap();
+
+    // Used to wait until the client has received all events, so that the stream lags behind.
+    let (tx, mut rx) = tokio::sync::mpsc::unbounded_channel();
+    let _disposer = client
+        .on_event(move |event| {
+            if matches!(event, DecthingsEvent::Spawned(SpawnedEvent::Exit { .. })) {
+                tx.send(()).ok();
+            }
+        })
+        .await;
+
+    let stdout =
+        |id: &str| serde_json::json!({ "event": "stdout", "params": { "spawnedCommandId": id } });
+    server
+        .push_event("Spawned", stdout("other"), [&b"ignored"[..]])
+        .await;
+    for _ in 0..EVENT_STREAM_CAPACITY + 2 {
+        server
+            .push_event("Spawned", stdout("cmd"), [&b"out"[..]])
+            .await;
+    }
+    server
+        .push_event(
+            "Spawned",
+            serde_json::json!({
+                "event": "exit",
+                "params": { "spawnedCommandId": "cmd", "reason": { "code": "unknown" } }
+            }),
+            Vec::<bytes::Bytes>::new(),
+        )
+        .await;
+    rx.recv().await.unwrap();
+
+    for _ in 0..EVENT_STREAM_CAPACITY {
+        assert!(matches!(
+            stream.next().await,
+            Some(Ok(SpawnedEvent::Stdout { data, .. })) if &data[..] == b"out"
+        ));
+    }
+    assert_eq!(stream.next().await.unwrap().unwrap_err(), Lagged(2));
+    assert!(matches!(
+        stream.next().await,
+        Some(Ok(SpawnedEvent::Exit { .. }))
+    ));
+    assert!(stream.next().await.is_none());
+}
+
+#[cfg(all(feature = "mock", feature = "spawned"))]
+#[tokio::test]
+async fn test_async_event_listeners() {
+    use crate::{
+        client::{DecthingsClient, DecthingsClientOptions, RpcProtocol, rpc::spawned},
+        mock::{MockResponse, MockServer, ping},
+    };
+
+    let server = MockServer::start().await.unwrap();
+    server
+        .handle("Spawned", "subscribeToEvents", |_| {
+            MockResponse::result(serde_json::json!({}))
+        })
+        .await;
+    server.handle_ping(None).await;
+
+    let client = DecthingsClient::new(DecthingsClientOptions {
+        default_protocol: RpcProtocol::Ws,
+        ..server.client_options()
+    });
+    // The async listeners wait until the gate is closed.
+    let gate = Arc::new(tokio::sync::Semaphore::new(0));
+    let received = Arc::new(Mutex::new(vec![]));
+    let (gate_clone, received_clone) = (Arc::clone(&gate), Arc::clone(&received));
+    let drop_oldest = client
+        .on_event_async(
+            EventListenerOptions {
+                capacity: 2,
+                overflow: OverflowPolicy::DropOldest,
+            },
+            move |event| {
+                let (gate, received) = (Arc::clone(&gate_clone), Arc::clone(&received_clone));
+                async move {
+                    gate.acquire().await.ok();
+                    if let DecthingsEvent::Spawned(SpawnedEvent::Stdout { data, .. }) = event {
+                        received.lock().unwrap().push(data[0]);
+                    }
+                }
+            },
+        )
+        .await;
+    let gate_clone = Arc::clone(&gate);
+    let disconnect = client
+        .on_event_async(
+            EventListenerOptions {
+                capacity: 1,
+                overflow: OverflowPolicy::Disconnect,
+            },
+            move |_| {
+                let gate = Arc::clone(&gate_clone);
+                async move {
+                    gate.acquire().await.ok();
+                }
+            },
+        )
+        .await;
+    let (tx, mut rx) = tokio::sync::mpsc::unbounded_channel();
+    let _disposer = client
+        .on_event(move |_| {
+            tx.send(()).ok();
+        })
+        .await;
+
+    client
+        .spawned
+        .subscribe_to_events(spawned::SpawnedSubscribeToEventsParams {
+            spawned_command_id: "cmd",
+        })
+        .await
+        .unwrap();
+    for i in 1..=5u8 {
+        server
+            .push_event(
+                "Spawned",
+                serde_json::json!({ "event": "stdout", "params": { "spawnedCommandId": "cmd" } }),
+                [vec![i]],
+            )
+            .await;
+    }
+    for _ in 0..5 {
+        rx.recv().await.unwrap();
+    }
+
+    // Responses are not held up by the blocked listeners.
+    ping(&client).await.unwrap();
+    assert!(disconnect.is_disconnected());
+    assert_eq!(disconnect.dropped_events(), 1);
+    assert!(!drop_oldest.is_disconnected());
+
+    gate.close();
+    while received.lock().unwrap().last() != Some(&5) {
+        tokio::time::sleep(std::time::Duration::from_millis(10)).await;
+    }
+    // The oldest events were dropped, apart from the one which was already being handled.
+    let received = received.lock().unwrap().clone();
+    assert!(received.ends_with(&[4, 5]));
+    assert_eq!(received.len() as u64 + drop_oldest.dropped_events(), 5);
+
+    // Disposing the disconnected listener does not remove a listener which was added after it.
+    let (new_tx, mut new_rx) = tokio::sync::mpsc::unbounded_channel();
+    let _new_disposer = client
+        .on_event(move |_| {
+            new_tx.send(()).ok();
+        })
+        .await;
+    disconnect.dispose().await;
+    server
+        .push_event(
+            "Spawned",
+            serde_json::json!({ "event": "stdout", "params": { "spawnedCommandId": "cmd" } }),
+            [vec![6]],
+        )
+        .await;
+    new_rx.recv().await.unwrap();
+}
+
+#[cfg(test)]
+#[tokio::test]
+async fn test_listener_removes_itself() {
+    let listeners = Arc::new(EventListeners::new());
+    let calls = Arc::new(std::sync::atomic::AtomicU32::new(0));
+    let disposer = Arc::new(Mutex::new(None::<EventListenerDisposer>));
+    let added = Arc::new(Mutex::new(vec![]));
+
+    let listeners_clone = Arc::clone(&listeners);
+    let calls_clone = Arc::clone(&calls);
+    let disposer_clone = Arc::clone(&disposer);
+    let added_clone = Arc::clone(&added);
+    *disposer.lock().unwrap() = Some(listeners.add(move |_| {
+        calls_clone.fetch_add(1, std::sync::atomic::Ordering::Relaxed);
+        // Adding and removing listeners from inside a listener must not deadlock.
+        added_clone
+            .lock()
+            .unwrap()
+            .push(listeners_clone.add(|_| {}));
+        if let Some(disposer) = disposer_clone.lock().unwrap().take() {
+            disposer.remove();
+        }
+    }));
+
+    listeners.call(&DecthingsEvent::SubscriptionsRemoved).await;
+    listeners.call(&DecthingsEvent::SubscriptionsRemoved).await;
+    assert_eq!(calls.load(std::sync::atomic::Ordering::Relaxed), 1);
+    assert_eq!(listeners.listeners.lock().unwrap().len(), 1);
+}
@@ -0,0 +1,182 @@
+use std::{
+    collections::HashMap,
+    sync::Arc,
+    time::{Duration, Instant},
+};
+
+/// Limits which the client applies to its own requests, to avoid being rate limited by Decthings.
+/// Requests which would exceed a limit wait until they are allowed, in the order they were made.
+/// See `DecthingsClientOptions::request_limits`.
+#[derive(Debug, Clone, Default)]
+pub struct RequestLimits {
+    /// Maximum rate at which requests are sent. Default: no limit.
+    pub rate: Option<RateLimit>,
+    /// Maximum number of requests which have been sent but have not yet received a response.
+    /// Default: no limit.
+    pub max_in_flight: Option<usize>,
+}
+
+/// A token bucket: each request takes a token, and tokens are added at a fixed rate up to a
+/// maximum.
+#[derive(Debug, Clone, Copy)]
+pub struct RateLimit {
+    /// Number of requests per second, on average.
+    pub per_second: f64,
+    /// Maximum number of requests which can be sent at once after a period without requests.
+    pub burst: u32,
+}
+
+struct TokenBucket {
+    limit: RateLimit,
+    tokens: f64,
+    updated_at: Instant,
+}
+
+impl TokenBucket {
+    /// Wait until a token is available and take it.
+    async fn take(&mut self) {
+        let burst = f64::from(self.limit.burst.max(1));
+        let now = Instant::now();
+        self.tokens = (self.tokens
+            + now.duration_since(self.updated_at).as_secs_f64() * self.limit.per_second)
+            .min(burst);
+        self.updated_at = now;
+        if self.tokens < 1.0 {
+            let wait = Duration::from_secs_f64((1.0 - self.tokens) / self.limit.per_second);
+            tokio::time::sleep(wait).await;
+            self.tokens = 1.0;
+            self.updated_at += wait;
+        }
+        self.tokens -= 1.0;
+    }
+}
+
+struct Limiter {
+    in_flight: Option<Arc<tokio::sync::Semaphore>>,
+    // The lock is held while waiting for a token, so that waiting requests take tokens in order.
+    rate: Option<tokio::sync::Mutex<TokenBucket>>,
+}
+
+impl Limiter {
+    fn new(limits: &RequestLimits) -> Option<Self> {
+        let limiter = Self {
+            in_flight: limits
+                .max_in_flight
+                .map(|max| Arc::new(tokio::sync::Semaphore::new(max.max(1)))),
+            rate: limits
+                .rate
+                .filter(|rate| rate.per_second > 0.0)
+                .map(|limit| {
+                    tokio::sync::Mutex::new(TokenBucket {
+                        limit,
+                        tokens: f64::from(limit.burst.max(1)),
+                        updated_at: Instant::now(),
+                    })
+                }),
+        };
+        (limiter.in_flight.is_some() || limiter.rate.is_some()).then_some(limiter)
+    }
+
+    async fn acquire(&self) -> Option<tokio::sync::OwnedSemaphorePermit> {
+        // A slot is taken before the token, so that requests which wait for a slot do not use up
+        // tokens and are then sent all at once.
+        let permit = match &self.in_flight {
+            Some(semaphore) => Some(
+                Arc::clone(semaphore)
+                    .acquire_owned()
+                    .await
+                    .expect("semaphore is never closed"),
+            ),
+            None => None,
+        };
+        if let Some(rate) = &self.rate {
+            rate.lock().await.take().await;
+        }
+        permit
+    }
+}
+
+/// Held while a request is in flight.
+pub(crate) struct LimitPermit {
+    _api: Option<tokio::sync::OwnedSemaphorePermit>,
+    _global: Option<tokio::sync::OwnedSemaphorePermit>,
+}
+
+/// The limits of a client, which are shared by all clones of it.
+pub(crate) struct Limiters {
+    global: Option<Limiter>,
+    apis: HashMap<String, Limiter>,
+}
+
+impl Limiters {
+    pub(crate) fn new(global: &RequestLimits, apis: &HashMap<String, RequestLimits>) -> Self {
+        Self {
+            global: Limiter::new(global),
+            apis: apis
+                .iter()
+                .filter_map(|(api, limits)| Some((api.clone(), Limiter::new(limits)?)))
+                .collect(),
+        }
+    }
+
+    /// Wait until a request to `api` is allowed. The limits of the API are applied before the
+    /// global limits, so that requests waiting for a busy API do not hold up other APIs.
+    pub(crate) async fn acquire(&self, api: &str) -> LimitPermit {
+        let api_permit = match self.apis.get(api) {
+            Some(limiter) => limiter.acquire().await,
+            None => None,
+        };
+        let global_permit = match &self.global {
+            Some(limiter) => limiter.acquire().await,
+            None => None,
+        };
+        LimitPermit {
+            _api: api_permit,
+            _global: global_permit,
+        }
+    }
+}
+
+#[cfg(feature = "mock")]
+#[tokio::test]
+async fn test_request_limits() {
+    use crate::{
+        client::{DecthingsClient, DecthingsClientOptions},
+        mock::{MockServer, ping},
+    };
+
+    let server = MockServer::start().await.unwrap();
+    server.handle_ping(Some(Duration::from_millis(50))).await;
+
+    // One request at a time, so three requests take at least three delays.
+    let client = DecthingsClient::new(DecthingsClientOptions {
+        api_request_limits: HashMap::from([(
+            "Test".to_string(),
+            RequestLimits {
+                rate: None,
+                max_in_flight: Some(1),
+            },
+        )]),
+        ..server.client_options()
+    });
+    let started = Instant::now();
+    let results = futures::future::join_all((0..3).map(|_| ping(&client))).await;
+    assert!(results.iter().all(|res| res.is_ok()));
+    assert!(started.elapsed() >= Duration::from_millis(150));
+
+    // 20 requests per second without bursts, so the fourth request is sent after 150 ms.
+    let client = DecthingsClient::new(DecthingsClientOptions {
+        request_limits: RequestLimits {
+            rate: Some(RateLimit {
+                per_second: 20.0,
+                burst: 1,
+            }),
+            max_in_flight: None,
+        },
+        ..server.client_options()
+    });
+    let started = Instant::now();
+    let results = futures::future::join_all((0..4).map(|_| ping(&client))).await;
+    assert!(results.iter().all(|res| res.is_ok()));
+    assert!(started.elapsed() >= Duration::from_millis(200));
+}
@@ -0,0 +1,225 @@
+use std::{
+    sync::Arc,
+    time::{Duration, Instant},
+};
+
+use super::{CompressionStats, DecthingsClientError, RpcProtocol};
+
+/// The raw result of a request: The JSON response and the blobs, or an error.
+pub type RpcResult = Result<(bytes::Bytes, Vec<bytes::Bytes>), DecthingsClientError>;
+
+/// A request which is about to be sent, as seen by [`Middleware::on_request`].
+#[derive(Debug)]
+pub struct RpcRequest<'a> {
+    pub api: &'a str,
+    pub method: &'a str,
+    /// The params, serialized as JSON.
+    pub params: &'a [u8],
+    /// The blobs which are sent after the params.
+    pub data: &'a [bytes::Bytes],
+    /// The protocol the request will be sent over. Either RpcProtocol::Http or RpcProtocol::Ws.
+    pub protocol: RpcProtocol,
+    /// The API key which will be sent with the request. Can be changed or removed.
+    pub api_key: Option<Arc<str>>,
+    /// Headers which will be sent with the request. Only used for HTTP, since WebSocket requests
+    /// are sent over a connection which is already open.
+    pub extra_headers: http::HeaderMap<http::HeaderValue>,
+}
+
+/// The result of a request, as seen by [`Middleware::on_response`].
+#[derive(Debug)]
+pub struct RpcResponse<'a> {
+    pub api: &'a str,
+    pub method: &'a str,
+    pub protocol: RpcProtocol,
+    /// Time since the request was passed to the first middleware.
+    pub elapsed: Duration,
+    /// How much the request body was compressed, or None if it was sent uncompressed. WebSocket
+    /// requests are never compressed.
+    pub request_compression: Option<CompressionStats>,
+    /// The result, which can be replaced. For the streamed methods, such as
+    /// `ModelRpc::get_weights_streamed`, the blobs are not included.
+    pub result: RpcResult,
+}
+
+impl RpcResponse<'_> {
+    /// Total size in bytes of the JSON response and the blobs, or zero if the request failed.
+    pub fn response_size(&self) -> usize {
+        match &self.result {
+            Ok((response, blobs)) => response.len() + blobs.iter().map(|x| x.len()).sum::<usize>(),
+            Err(_) => 0,
+        }
+    }
+}
+
+/// Observes and changes each request sent by the client. Set using
+/// `DecthingsClientOptions::middleware`.
+///
+/// `on_request` is called for each middleware in the order they were added, before each attempt
+/// of a request. `on_response` is then called in the reverse order when the result is available.
+pub trait Middleware: Send + Sync {
+    /// Called before a request is sent. Return a result to skip sending the request and use the
+    /// result instead. The middleware after this one will then not see the request, but
+    /// `on_response` is still called for all middleware.
+    fn on_request(&self, request: &mut RpcRequest<'_>) -> Option<RpcResult> {
+        let _ = request;
+        None
+    }
+
+    /// Called when the result of a request is available, before it is returned to the caller.
+    fn on_response(&self, response: &mut RpcResponse<'_>) {
+        let _ = response;
+    }
+}
+
+pub(crate) fn on_request(
+    middleware: &[Arc<dyn Middleware>],
+    request: &mut RpcRequest<'_>,
+) -> Option<RpcResult> {
+    middleware.iter().find_map(|m| m.on_request(request))
+}
+
+/// Calls `on_response` for each middleware. Owns everything it needs, so that it can be moved
+/// into the callback of a WebSocket request.
+pub(crate) struct ResponseHook {
+    middleware: Arc<[Arc<dyn Middleware>]>,
+    api: String,
+    method: String,
+    protocol: RpcProtocol,
+    started: Instant,
+    request_compression: Option<CompressionStats>,
+}
+
+impl ResponseHook {
+    /// Returns None if there is no middleware.
+    pub(crate) fn new(
+        middleware: &Arc<[Arc<dyn Middleware>]>,
+        api: &str,
+        method: &str,
+        protocol: RpcProtocol,
+        started: Instant,
+    ) -> Option<Self> {
+        if middleware.is_empty() {
+            return None;
+        }
+        Some(Self {
+            middleware: Arc::clone(middleware),
+            api: api.to_owned(),
+            method: method.to_owned(),
+            protocol,
+            started,
+            request_compression: None,
+        })
+    }
+
+    pub(crate) fn with_request_compression(mut self, stats: Option<CompressionStats>) -> Self {
+        self.request_compression = stats;
+        self
+    }
+
+    pub(crate) fn call(self, result: RpcResult) -> RpcResult {
+        let mut response = RpcResponse {
+            api: &self.api,
+            method: &self.method,
+            protocol: self.protocol,
+            elapsed: self.started.elapsed(),
+            request_compression: self.request_compression,
+            result,
+        };
+        for m in self.middleware.iter().rev() {
+            m.on_response(&mut response);
+        }
+        response.result
+    }
+}
+
+#[cfg(all(feature = "mock", feature = "fs"))]
+#[tokio::test]
+async fn test_middleware() {
+    use crate::{
+        client::{DecthingsClient, DecthingsClientOptions, rpc::fs},
+        mock::{MockResponse, MockServer},
+    };
+    use std::sync::Mutex;
+
+    #[derive(Default)]
+    struct Recorder {
+        seen: Mutex<Vec<String>>,
+    }
+
+    impl Middleware for Recorder {
+        fn on_request(&self, request: &mut RpcRequest<'_>) -> Option<RpcResult> {
+            self.seen.lock().unwrap().push(format!(
+                "{}/{} {} {:?} {:?}",
+                request.api,
+                request.method,
+                std::str::from_utf8(request.params).unwrap(),
+                request.data.iter().map(|x| x.len()).collect::<Vec<_>>(),
+                request.protocol,
+            ));
+            request.api_key = Some("replaced".into());
+            if request.method == "readlink" {
+                return Some(Err(DecthingsClientError::InvalidMessage));
+            }
+            None
+        }
+
+        fn on_response(&self, response: &mut RpcResponse<'_>) {
+            self.seen.lock().unwrap().push(format!(
+                "{}/{} {}",
+                response.api,
+                response.method,
+                response.response_size()
+            ));
+        }
+    }
+
+    let server = MockServer::start().await.unwrap();
+    server
+        .handle("FS", "write", |req| {
+            assert_eq!(req.api_key.as_deref(), Some("replaced"));
+            MockResponse::result(serde_json::json!({ "bytesWritten": 4 }))
+        })
+        .await;
+
+    let recorder = Arc::new(Recorder::default());
+    let client = DecthingsClient::new(DecthingsClientOptions {
+        api_key: Some("key".to_string()),
+        middleware: vec![recorder.clone()],
+        ..server.client_options()
+    });
+    client
+        .fs
+        .write(fs::WriteParams {
+            model_id: "m",
+            inode: 1,
+            data: bytes::Bytes::from_static(b"data"),
+            offset: 0,
+            truncate: None,
+        })
+        .await
+        .unwrap();
+    assert!(matches!(
+        client
+            .fs
+            .readlink(fs::ReadlinkParams {
+                model_id: "m",
+                version_id: None,
+                inode: 1,
+            })
+            .await,
+        Err(crate::client::DecthingsRpcError::Request(
+            DecthingsClientError::InvalidMessage
+        ))
+    ));
+
+    assert_eq!(
+        *recorder.seen.lock().unwrap(),
+        [
+            r#"FS/write {"modelId":"m","inode":1,"offset":0} [4] Http"#,
+            r#"FS/write 29"#,
+            r#"FS/readlink {"modelId":"m","inode":1} [] Http"#,
+            r#"FS/readlink 0"#,
+        ]
+    );
+}
@@ -1,4 +1,10 @@
+#[cfg(feature = "blocking")]
+pub mod blocking;
+mod builder;
+pub(crate) mod compression;
+mod credentials;
 mod error;
+mod limit;
 
 #[cfg(feature = "events")]
 pub mod event;
@@ -8,8 +14,16 @@ mod espidf_http_impl;
 #[cfg(target_os = "espidf")]
 use espidf_http_impl::*;
 
+mod middleware;
 mod parameter;
-mod protocol;
+pub(crate) mod protocol;
+mod proxy;
+mod retry;
+mod streaming;
+mod tls;
+#[cfg(feature = "tracing")]
+mod trace;
+mod transport;
 
 #[cfg(not(target_os = "espidf"))]
 mod reqwest_http_impl;
@@ -26,12 +40,28 @@ use tokio::sync::RwLock;
 
 pub use ndarray;
 
-pub use error::{DecthingsClientError, DecthingsRpcError};
+pub use builder::{ConfigError, DecthingsClientBuilder};
+pub use compression::{Compression, CompressionAlgorithm, CompressionStats};
+pub use credentials::{Credential, CredentialFuture, CredentialProvider};
+pub use error::{DecthingsClientError, DecthingsErrorCode, DecthingsRpcError};
+pub use limit::{RateLimit, RequestLimits};
+pub use middleware::{Middleware, RpcRequest, RpcResponse, RpcResult};
 pub use parameter::*;
+pub use protocol::{DecodeLimits, DeserializeError, DeserializeErrorKind};
+pub use proxy::Proxy;
+pub use retry::RetryPolicy;
+pub use streaming::{ByteStream, ResponseBlobs};
+pub use tls::{Certificate, Identity, TlsError};
+pub use transport::{Transport, TransportFuture, TransportStreamFuture};
+#[cfg(feature = "events")]
+pub use websocket::Keepalive;
+
+type SharedResultCallback<F> = Arc<std::sync::Mutex<Option<F>>>;
 
 struct StateModification {
+    /// Event subscriptions to add, as (api, id).
     #[cfg(feature = "events")]
-    add_events: Vec<String>,
+    add_events: Vec<(&'static str, String)>,
     #[cfg(feature = "events")]
     remove_events: Vec<String>,
 }
@@ -47,29 +77,133 @@ impl StateModification {
     }
 }
 
-#[derive(Debug, Clone)]
+#[derive(Clone)]
 pub struct DecthingsClientOptions {
     #[cfg(feature = "events")]
-    /// Server address to use for WebSocket API. Defaults to "wss://api.decthings.com/v0/ws`
+    /// Server address to use for WebSocket API. Defaults to `wss://api.decthings.com/v0/ws`.
     pub ws_server_address: String,
 
-    /// Server address to use for HTTP API. Defaults to `https://api.decthings.com/v0`
+    /// Server address to use for HTTP API. Defaults to `https://api.decthings.com/v0`. Not used if
+    /// `transport` is set.
     pub http_server_address: String,
     /// Optional API key. Some methods require this to be set.
     pub api_key: Option<String>,
+    /// Provides the API key for each request, for keys which expire or are rotated. If set,
+    /// `api_key` and `DecthingsClient::set_api_key` are not used. See [`CredentialProvider`].
+    pub credential_provider: Option<Arc<dyn CredentialProvider>>,
+    #[cfg(feature = "events")]
+    /// If true, the client reconnects when the WebSocket closes unexpectedly and subscribes again
+    /// to all events which were active. A `DecthingsEvent::Resubscribed` event is emitted when
+    /// done. Connection failures are retried according to `retry_policy`. Defaults to false.
+    pub ws_auto_reconnect: bool,
+    #[cfg(feature = "events")]
+    /// If set, the client pings Decthings regularly and closes the WebSocket if no answer is
+    /// received in time. Requests which are waiting for a response then fail with
+    /// `DecthingsClientError::ConnectionLost`, and `DecthingsEvent::SubscriptionsRemoved` is
+    /// emitted. Defaults to `Keepalive::default()`.
+    pub ws_keepalive: Option<Keepalive>,
+
     /// Additional headers to add to each request.
     pub extra_headers: http::HeaderMap<http::HeaderValue>,
+    /// Custom transport to use for HTTP requests. If not set, the default HTTP client will be used
+    /// and requests will be sent to `http_server_address`.
+    pub transport: Option<Arc<dyn Transport>>,
+    /// Maximum time to wait for a request to finish, including retries. When exceeded, the request
+    /// fails with `DecthingsClientError::Timeout`. Can be overridden for each API using
+    /// `with_timeout`, for example `client.model.with_timeout(...)`. Defaults to no timeout.
+    pub timeout: Option<std::time::Duration>,
+    /// If set, requests which fail with a retryable error are sent again. Non-idempotent methods
+    /// such as "Model"/"train" are not retried unless `RetryPolicy::retry_non_idempotent` is set.
+    pub retry_policy: Option<RetryPolicy>,
+    /// Limits for all requests made by this client, applied before requests are sent. Each
+    /// attempt of a retried request counts as a request. Defaults to no limits.
+    pub request_limits: RequestLimits,
+    /// Limits for the requests to a specific API, such as "Model", "Dataset" or "FS". These apply
+    /// in addition to `request_limits`.
+    pub api_request_limits: std::collections::HashMap<String, RequestLimits>,
+    /// Limits for the responses and events received from Decthings. A message which exceeds them
+    /// fails with `DecthingsClientError::InvalidFrame`, or with `DecthingsClientError::Decompress`
+    /// if a compressed response expands beyond them. Defaults to no limits.
+    pub decode_limits: DecodeLimits,
+    /// Protocol used to send requests. Can be overridden for each API using `with_protocol`, for
+    /// example `client.fs.with_protocol(RpcProtocol::Ws)`. Methods which subscribe to events always
+    /// use WebSocket. `RpcProtocol::WsIfAvailableOtherwiseNone` is treated as
+    /// `RpcProtocol::Http`. Defaults to `RpcProtocol::Http`.
+    pub default_protocol: RpcProtocol,
+    /// Middleware which can observe and change each request and its result. See [`Middleware`].
+    pub middleware: Vec<Arc<dyn Middleware>>,
+    /// Additional root certificates to trust for HTTP and WebSocket connections, for example the
+    /// certificate of a proxy which inspects TLS traffic. Requires the "native-tls" or
+    /// "rustls-tls" feature.
+    pub root_certificates: Vec<Certificate>,
+    /// Client certificate to present for HTTP and WebSocket connections (mutual TLS). Requires the
+    /// "native-tls" or "rustls-tls" feature.
+    pub client_identity: Option<Identity>,
+    /// Proxy to use for HTTP and WebSocket connections. If not set, HTTP requests use the proxy
+    /// from the environment variables, like `HTTPS_PROXY`, and WebSocket connections are direct.
+    /// Use `Proxy::from_env` to use the environment for both.
+    pub proxy: Option<Proxy>,
+    /// Compression of HTTP request and response bodies. Requires the "gzip" or "zstd" feature.
+    /// Defaults to no compression.
+    pub compression: Option<Compression>,
+}
+
+impl std::fmt::Debug for DecthingsClientOptions {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let mut s = f.debug_struct("DecthingsClientOptions");
+        #[cfg(feature = "events")]
+        s.field("ws_server_address", &self.ws_server_address)
+            .field("ws_auto_reconnect", &self.ws_auto_reconnect)
+            .field("ws_keepalive", &self.ws_keepalive);
+        s.field("http_server_address", &self.http_server_address)
+            .field("api_key", &self.api_key)
+            .field(
+                "credential_provider",
+                &self.credential_provider.as_ref().map(|_| "<custom>"),
+            )
+            .field("extra_headers", &self.extra_headers)
+            .field("transport", &self.transport.as_ref().map(|_| "<custom>"))
+            .field("timeout", &self.timeout)
+            .field("retry_policy", &self.retry_policy)
+            .field("request_limits", &self.request_limits)
+            .field("api_request_limits", &self.api_request_limits)
+            .field("decode_limits", &self.decode_limits)
+            .field("default_protocol", &self.default_protocol)
+            .field("middleware", &self.middleware.len())
+            .field("root_certificates", &self.root_certificates.len())
+            .field("client_identity", &self.client_identity)
+            .field("proxy", &self.proxy)
+            .field("compression", &self.compression)
+            .finish()
+    }
 }
 
 impl std::default::Default for DecthingsClientOptions {
     fn default() -> Self {
         Self {
             #[cfg(feature = "events")]
-            ws_server_address: "wss://api.decthings.com/v0/v0".to_string(),
+            ws_server_address: "wss://api.decthings.com/v0/ws".to_string(),
+            #[cfg(feature = "events")]
+            ws_auto_reconnect: false,
+            #[cfg(feature = "events")]
+            ws_keepalive: Some(Keepalive::default()),
 
             http_server_address: "https://api.decthings.com/v0".to_string(),
             api_key: None,
+            credential_provider: None,
             extra_headers: http::HeaderMap::new(),
+            transport: None,
+            timeout: None,
+            retry_policy: None,
+            request_limits: RequestLimits::default(),
+            api_request_limits: std::collections::HashMap::new(),
+            decode_limits: DecodeLimits::default(),
+            default_protocol: RpcProtocol::Http,
+            middleware: vec![],
+            root_certificates: vec![],
+            client_identity: None,
+            proxy: None,
+            compression: None,
         }
     }
 }
@@ -87,13 +221,141 @@ pub enum RpcProtocol {
     WsIfAvailableOtherwiseNone,
 }
 
+/// What to do after an attempt of a request has finished.
+enum NextAttempt {
+    Done,
+    /// Retry according to the retry policy.
+    Retry,
+    /// The API key was rejected at the given time. Fetch a new one and send the request again.
+    RefreshCredentials(std::time::Instant),
+}
+
+/// Decides what to do after an attempt of a request, before its result is passed on. This is
+/// called from the WebSocket callback, so it cannot wait for anything.
+#[derive(Clone)]
+struct AttemptPolicy {
+    /// None if this is the last attempt.
+    retry_policy: Option<Arc<RetryPolicy>>,
+    refresh_credentials: bool,
+}
+
+impl AttemptPolicy {
+    /// `res` is the JSON part of the response, or the error.
+    fn next(&self, res: Result<&[u8], &DecthingsClientError>) -> NextAttempt {
+        if self.refresh_credentials && res.is_ok_and(credentials::is_bad_credentials_response) {
+            return NextAttempt::RefreshCredentials(std::time::Instant::now());
+        }
+        if self
+            .retry_policy
+            .as_ref()
+            .is_some_and(|policy| policy.should_retry(res))
+        {
+            return NextAttempt::Retry;
+        }
+        NextAttempt::Done
+    }
+}
+
+/// A response received over HTTP, either read in full or with the blobs read while they are
+/// downloaded.
+trait HttpResponse: Sized + Send {
+    /// Send the request and read the response, within `DecthingsClientOptions::decode_limits`.
+    fn receive(
+        rpc: &DecthingsClientRpc,
+        api: &str,
+        method: &str,
+        body: Vec<bytes::Bytes>,
+        api_key: Option<Arc<str>>,
+        extra_headers: Arc<http::HeaderMap<http::HeaderValue>>,
+    ) -> impl std::future::Future<Output = Result<Self, DecthingsClientError>> + Send;
+
+    /// Wrap a response which was provided by a middleware.
+    fn from_full(response: (bytes::Bytes, Vec<bytes::Bytes>)) -> Self;
+
+    /// Pass the response to the middleware.
+    fn call_hook(
+        res: Result<Self, DecthingsClientError>,
+        hook: middleware::ResponseHook,
+    ) -> Result<Self, DecthingsClientError>;
+}
+
+impl HttpResponse for (bytes::Bytes, Vec<bytes::Bytes>) {
+    async fn receive(
+        rpc: &DecthingsClientRpc,
+        api: &str,
+        method: &str,
+        body: Vec<bytes::Bytes>,
+        api_key: Option<Arc<str>>,
+        extra_headers: Arc<http::HeaderMap<http::HeaderValue>>,
+    ) -> Result<Self, DecthingsClientError> {
+        let response_body = rpc
+            .http
+            .get(api, method, body, api_key, extra_headers)
+            .await?;
+        protocol::deserialize_for_http(response_body, &rpc.decode_limits).map_err(|e| {
+            #[cfg(feature = "tracing")]
+            tracing::warn!(error = %e, "Received an invalid HTTP response body");
+            DecthingsClientError::InvalidFrame(e)
+        })
+    }
+
+    fn from_full(response: (bytes::Bytes, Vec<bytes::Bytes>)) -> Self {
+        response
+    }
+
+    fn call_hook(
+        res: Result<Self, DecthingsClientError>,
+        hook: middleware::ResponseHook,
+    ) -> Result<Self, DecthingsClientError> {
+        hook.call(res)
+    }
+}
+
+#[cfg(any(feature = "dataset", feature = "debug", feature = "model"))]
+impl HttpResponse for (bytes::Bytes, ResponseBlobs) {
+    async fn receive(
+        rpc: &DecthingsClientRpc,
+        api: &str,
+        method: &str,
+        body: Vec<bytes::Bytes>,
+        api_key: Option<Arc<str>>,
+        extra_headers: Arc<http::HeaderMap<http::HeaderValue>>,
+    ) -> Result<Self, DecthingsClientError> {
+        let stream = rpc
+            .http
+            .get_streamed(api, method, body, api_key, extra_headers)
+            .await?;
+        ResponseBlobs::from_http_stream(stream, rpc.decode_limits).await
+    }
+
+    fn from_full((response, blobs): (bytes::Bytes, Vec<bytes::Bytes>)) -> Self {
+        (response, ResponseBlobs::from_blobs(blobs))
+    }
+
+    // The middleware only sees the JSON part of the response, since the blobs have not been
+    // received yet.
+    fn call_hook(
+        res: Result<Self, DecthingsClientError>,
+        hook: middleware::ResponseHook,
+    ) -> Result<Self, DecthingsClientError> {
+        match res {
+            Ok((response, blobs)) => hook
+                .call(Ok((response, vec![])))
+                .map(|(response, _)| (response, blobs)),
+            Err(e) => hook.call(Err(e)).map(Self::from_full),
+        }
+    }
+}
+
 #[derive(Clone)]
 pub(crate) struct DecthingsClientRpc {
     #[cfg(feature = "events")]
-    ws_server_address: String,
+    ws_connect: Arc<websocket::ConnectOptions>,
+    #[cfg(feature = "events")]
+    ws_auto_reconnect: bool,
 
-    http_server_address: String,
     api_key: Arc<RwLock<Option<Arc<str>>>>,
+    credentials: Option<Arc<credentials::CredentialCache>>,
     extra_headers: Arc<http::HeaderMap<http::HeaderValue>>,
 
     #[cfg(feature = "events")]
@@ -102,17 +364,48 @@ pub(crate) struct DecthingsClientRpc {
     #[cfg(feature = "events")]
     ws: Arc<RwLock<(u64, Option<(u64, Arc<websocket::DecthingsClientWebsocket>)>)>>,
 
-    http: HttpImpl,
+    http: Arc<dyn Transport>,
+    timeout: Option<std::time::Duration>,
+    retry_policy: Option<Arc<RetryPolicy>>,
+    limits: Arc<limit::Limiters>,
+    decode_limits: DecodeLimits,
+    compression: Option<Compression>,
+    protocol: RpcProtocol,
+    middleware: Arc<[Arc<dyn Middleware>]>,
 }
 
 impl DecthingsClientRpc {
     fn new(options: DecthingsClientOptions) -> Self {
+        let tls = tls::TlsConfig {
+            root_certificates: options.root_certificates,
+            client_identity: options.client_identity,
+        };
+        let http = options.transport.unwrap_or_else(|| {
+            Arc::new(HttpImpl::new(
+                options.http_server_address,
+                &tls,
+                options.proxy.as_ref(),
+                options.compression.is_some(),
+                &options.decode_limits,
+            ))
+        });
         Self {
             #[cfg(feature = "events")]
-            ws_server_address: options.ws_server_address,
+            ws_connect: Arc::new(websocket::ConnectOptions {
+                address: options.ws_server_address,
+                #[cfg(any(feature = "native-tls", feature = "rustls-tls"))]
+                tls,
+                proxy: options.proxy,
+                keepalive: options.ws_keepalive,
+                decode_limits: options.decode_limits,
+            }),
+            #[cfg(feature = "events")]
+            ws_auto_reconnect: options.ws_auto_reconnect,
 
-            http_server_address: options.http_server_address,
             api_key: Arc::new(RwLock::new(options.api_key.map(Arc::from))),
+            credentials: options
+                .credential_provider
+                .map(|provider| Arc::new(credentials::CredentialCache::new(provider))),
             extra_headers: Arc::new(options.extra_headers),
 
             #[cfg(feature = "events")]
@@ -121,7 +414,90 @@ impl DecthingsClientRpc {
             #[cfg(feature = "events")]
             ws: Arc::new(RwLock::new((0, None))),
 
-            http: HttpImpl::default(),
+            http,
+            timeout: options.timeout,
+            retry_policy: options.retry_policy.map(Arc::new),
+            limits: Arc::new(limit::Limiters::new(
+                &options.request_limits,
+                &options.api_request_limits,
+            )),
+            decode_limits: options.decode_limits,
+            compression: options.compression,
+            protocol: options.default_protocol,
+            middleware: options.middleware.into(),
+        }
+    }
+
+    // Used by the API modules, which can all be disabled.
+    #[cfg_attr(
+        not(any(
+            feature = "dataset",
+            feature = "debug",
+            feature = "fs",
+            feature = "image",
+            feature = "language",
+            feature = "model",
+            feature = "persistent-launcher",
+            feature = "spawned",
+            feature = "terminal"
+        )),
+        allow(dead_code)
+    )]
+    fn with_timeout(&self, timeout: Option<std::time::Duration>) -> Self {
+        Self {
+            timeout,
+            ..self.clone()
+        }
+    }
+
+    #[cfg_attr(
+        not(any(
+            feature = "dataset",
+            feature = "debug",
+            feature = "fs",
+            feature = "image",
+            feature = "language",
+            feature = "model",
+            feature = "persistent-launcher",
+            feature = "spawned",
+            feature = "terminal"
+        )),
+        allow(dead_code)
+    )]
+    fn with_retry_policy(&self, retry_policy: Option<RetryPolicy>) -> Self {
+        Self {
+            retry_policy: retry_policy.map(Arc::new),
+            ..self.clone()
+        }
+    }
+
+    // The language API is always used over WebSocket.
+    #[cfg_attr(
+        not(any(
+            feature = "dataset",
+            feature = "debug",
+            feature = "fs",
+            feature = "image",
+            feature = "model",
+            feature = "persistent-launcher",
+            feature = "spawned",
+            feature = "terminal"
+        )),
+        allow(dead_code)
+    )]
+    fn with_protocol(&self, protocol: RpcProtocol) -> Self {
+        Self {
+            protocol,
+            ..self.clone()
+        }
+    }
+
+    /// The protocol to use for requests which do not require a specific protocol.
+    fn protocol(&self) -> RpcProtocol {
+        match self.protocol {
+            #[cfg(feature = "events")]
+            RpcProtocol::WsIfAvailableOtherwiseNone => RpcProtocol::Http,
+            ref protocol => protocol.clone(),
         }
     }
 
@@ -135,7 +511,20 @@ impl DecthingsClientRpc {
         &self,
         cb: impl Fn(&event::DecthingsEvent) + Send + Sync + 'static,
     ) -> event::EventListenerDisposer {
-        self.event_listeners.add(cb).await
+        self.event_listeners.add(cb)
+    }
+
+    #[cfg(all(
+        feature = "events",
+        any(
+            feature = "debug",
+            feature = "language",
+            feature = "spawned",
+            feature = "terminal"
+        )
+    ))]
+    fn event_stream<T: event::SessionEvent>(&self, id: String) -> event::EventStream<T> {
+        event::EventStream::new(&self.event_listeners, id, self.ws_auto_reconnect)
     }
 
     #[cfg(feature = "events")]
@@ -161,41 +550,132 @@ impl DecthingsClientRpc {
         ws_mut.0 += 1;
 
         let event_listeners_clone = Arc::clone(&self.event_listeners);
+        let reconnect_rpc = self.ws_auto_reconnect.then(|| self.clone());
         let sock = Arc::new(websocket::DecthingsClientWebsocket::connect(
             &self.extra_headers,
-            move || async move {
+            move |subscriptions| async move {
                 let mut ws_clone_lock = ws_clone.write().await;
-                if let Some(inner_ws_clone) = ws_clone_lock.1.as_mut() {
-                    if inner_ws_clone.0 == id {
-                        ws_clone_lock.1 = None;
-                    }
+                if let Some(inner_ws_clone) = ws_clone_lock.1.as_mut()
+                    && inner_ws_clone.0 == id
+                {
+                    ws_clone_lock.1 = None;
                 }
+                drop(ws_clone_lock);
                 event_listeners_clone
                     .call(&event::DecthingsEvent::SubscriptionsRemoved)
                     .await;
+                if let Some(rpc) = reconnect_rpc
+                    && !subscriptions.is_empty()
+                {
+                    tokio::spawn(rpc.resubscribe(subscriptions));
+                }
             },
             move || {
                 let ws_clone3 = Arc::clone(&ws_clone2);
                 async move {
                     let mut ws_clone_lock = ws_clone3.write().await;
-                    if let Some(inner_ws_clone) = ws_clone_lock.1.as_mut() {
-                        if inner_ws_clone.1.is_unused().await {
-                            ws_clone_lock.1 = None;
-                        }
+                    if let Some(inner_ws_clone) = ws_clone_lock.1.as_mut()
+                        && inner_ws_clone.1.is_unused().await
+                    {
+                        ws_clone_lock.1 = None;
                     }
                 }
             },
-            &self.ws_server_address,
+            Arc::clone(&self.ws_connect),
             Arc::downgrade(&self.event_listeners),
         ));
         let _ = ws_mut.1.insert((id, Arc::clone(&sock)));
         sock
     }
 
-    /// Call an RPC method on the server.
+    /// Subscribe again to the given events, as (api, id), and emit a Resubscribed event.
+    #[cfg(feature = "events")]
+    fn resubscribe(
+        self,
+        subscriptions: Vec<(&'static str, String)>,
+    ) -> std::pin::Pin<Box<dyn std::future::Future<Output = ()> + Send>> {
+        Box::pin(async move {
+            let mut restored = vec![];
+            let mut failed = vec![];
+            for (api, id) in subscriptions {
+                let subscription = event::EventSubscription {
+                    api: api.to_owned(),
+                    id: id.clone(),
+                };
+                let id_field = match api {
+                    "Debug" => "debugSessionId",
+                    "Spawned" => "spawnedCommandId",
+                    "Terminal" => "terminalSessionId",
+                    _ => {
+                        failed.push((
+                            subscription,
+                            format!("Cannot subscribe to events for the API {api}."),
+                        ));
+                        continue;
+                    }
+                };
+                let mut params = serde_json::Map::new();
+                params.insert(id_field.to_owned(), id.clone().into());
+
+                let (tx, rx) = tokio::sync::oneshot::channel();
+                self.raw_method_call(
+                    api,
+                    "subscribeToEvents",
+                    params,
+                    vec![],
+                    RpcProtocol::Ws,
+                    move |x| {
+                        let res = x.map_err(|e| e.to_string()).and_then(|val| {
+                            let res: rpc::Response<serde_json::Value, serde_json::Value> =
+                                serde_json::from_slice(&val.0).map_err(|e| e.to_string())?;
+                            match res {
+                                rpc::Response::Result(_) => Ok(()),
+                                rpc::Response::Error(e) => Err(e
+                                    .get("code")
+                                    .and_then(|code| code.as_str())
+                                    .unwrap_or("unknown")
+                                    .to_owned()),
+                            }
+                        });
+                        let state_modification = if res.is_ok() {
+                            StateModification {
+                                add_events: vec![(api, id)],
+                                remove_events: vec![],
+                            }
+                        } else {
+                            StateModification::empty()
+                        };
+                        tx.send(res).ok();
+                        state_modification
+                    },
+                )
+                .await;
+                match rx.await {
+                    Ok(Ok(())) => restored.push(subscription),
+                    Ok(Err(e)) => failed.push((subscription, e)),
+                    Err(_) => failed.push((subscription, "The request was cancelled.".to_owned())),
+                }
+            }
+            #[cfg(feature = "tracing")]
+            tracing::info!(
+                restored = restored.len(),
+                failed = failed.len(),
+                "Restored event subscriptions after reconnecting"
+            );
+            self.event_listeners
+                .call(&event::DecthingsEvent::Resubscribed { restored, failed })
+                .await;
+        })
+    }
+
+    /// Call an RPC method on the server, retrying according to the retry policy and giving up
+    /// after the timeout.
     ///
     /// Returns false if the request is not sent (and on_result is not called). This happens if
     /// mode is RpcProtocol::WsIfAvailableOtherwiseNone and no WebSocket is connected.
+    ///
+    /// If the returned future is dropped before the response arrives, the request is cancelled and
+    /// on_result is not called.
     async fn raw_method_call<
         P: serde::Serialize,
         F: FnOnce(
@@ -203,92 +683,411 @@ impl DecthingsClientRpc {
             ) -> StateModification
             + Send
             + 'static,
-        D: AsRef<[u8]>,
     >(
         &self,
         api: &str,
         method: &str,
         params: P,
-        data: impl AsRef<[D]>,
-        #[allow(unused)] mode: RpcProtocol,
+        data: Vec<bytes::Bytes>,
+        mode: RpcProtocol,
         on_result: F,
     ) -> bool {
-        #[cfg(feature = "events")]
-        {
-            let maybe_ws = match mode {
-                RpcProtocol::Http => None,
-                RpcProtocol::Ws => Some(self.get_or_create_socket().await),
-                RpcProtocol::WsIfAvailableOtherwiseNone => {
-                    if let Some(ws) = self.maybe_get_socket().await {
-                        Some(ws)
-                    } else {
-                        return false;
-                    }
+        let params = bytes::Bytes::from(serde_json::to_vec(&params).unwrap());
+
+        #[cfg(feature = "tracing")]
+        let span = trace::rpc_span(api, method, &params, &data);
+        #[cfg(feature = "tracing")]
+        let on_result = {
+            let span = span.clone();
+            move |res: RpcResult| {
+                trace::record_outcome(&span, &res);
+                on_result(res)
+            }
+        };
+
+        // on_result is shared between all attempts and the timeout. Whichever finishes first takes
+        // it and calls it.
+        let on_result = Arc::new(std::sync::Mutex::new(Some(on_result)));
+        let call = self.raw_method_call_with_retries(api, method, &params, &data, mode, &on_result);
+        #[cfg(feature = "tracing")]
+        let call = tracing::Instrument::instrument(call, span);
+        let Some(timeout) = self.timeout else {
+            return call.await;
+        };
+        match tokio::time::timeout(timeout, call).await {
+            Ok(did_call) => did_call,
+            Err(_) => {
+                let on_result = on_result.lock().unwrap().take();
+                if let Some(on_result) = on_result {
+                    on_result(Err(DecthingsClientError::Timeout));
                 }
+                true
+            }
+        }
+    }
+
+    async fn raw_method_call_with_retries<
+        F: FnOnce(
+                Result<(bytes::Bytes, Vec<bytes::Bytes>), DecthingsClientError>,
+            ) -> StateModification
+            + Send
+            + 'static,
+    >(
+        &self,
+        api: &str,
+        method: &str,
+        params: &bytes::Bytes,
+        data: &[bytes::Bytes],
+        mode: RpcProtocol,
+        on_result: &SharedResultCallback<F>,
+    ) -> bool {
+        self.call_with_attempts(api, method, |policy| {
+            let (next_tx, next_rx) = tokio::sync::oneshot::channel();
+            let on_result = Arc::clone(on_result);
+            let mode = mode.clone();
+            async move {
+                let did_call = self
+                    .raw_method_call_once(api, method, params, data, mode, move |res| {
+                        // If this attempt is retried, on_result is kept for the next attempt.
+                        let next = policy.next(res.as_ref().map(|(response, _)| &response[..]));
+                        let done = matches!(next, NextAttempt::Done);
+                        next_tx.send(next).ok();
+                        if !done {
+                            return StateModification::empty();
+                        }
+                        let on_result = on_result.lock().unwrap().take();
+                        match on_result {
+                            Some(on_result) => on_result(res),
+                            None => StateModification::empty(),
+                        }
+                    })
+                    .await;
+                did_call.then_some(())?;
+                Some((next_rx.await.unwrap_or(NextAttempt::Done), ()))
+            }
+        })
+        .await
+        .is_some()
+    }
+
+    /// Make attempts of a request until one is done, refreshing the credentials or waiting
+    /// according to the retry policy in between. Each attempt is made by `attempt`, which returns
+    /// what to do next and its result, or None if the request was not sent.
+    ///
+    /// Returns the result of the last attempt, or None if the request was not sent.
+    async fn call_with_attempts<T, Fut: std::future::Future<Output = Option<(NextAttempt, T)>>>(
+        &self,
+        api: &str,
+        method: &str,
+        mut attempt: impl FnMut(AttemptPolicy) -> Fut,
+    ) -> Option<T> {
+        let retry_policy = self
+            .retry_policy
+            .as_ref()
+            .filter(|policy| policy.applies_to(api, method));
+        let mut attempt_number = 1;
+        // A rejected API key is refreshed once per request.
+        let mut refresh_credentials = self.credentials.is_some();
+        loop {
+            let policy = AttemptPolicy {
+                retry_policy: retry_policy
+                    .filter(|policy| attempt_number < policy.max_attempts)
+                    .cloned(),
+                refresh_credentials,
             };
+            let (next, res) = attempt(policy).await?;
+            match next {
+                NextAttempt::Done => return Some(res),
+                NextAttempt::RefreshCredentials(rejected_at) => {
+                    if let Some(credentials) = &self.credentials {
+                        credentials.invalidate(rejected_at).await;
+                    }
+                    refresh_credentials = false;
+                }
+                NextAttempt::Retry => {
+                    if let Some(retry_policy) = retry_policy {
+                        tokio::time::sleep(retry_policy.backoff(attempt_number)).await;
+                    }
+                    attempt_number += 1;
+                }
+            }
+        }
+    }
+
+    /// Serialize the body of an HTTP request, and compress it if
+    /// `DecthingsClientOptions::compression` applies to it. The Content-Encoding header is added
+    /// to `extra_headers` if it was compressed.
+    fn http_body(
+        &self,
+        params: &bytes::Bytes,
+        data: Vec<bytes::Bytes>,
+        extra_headers: &mut Arc<http::HeaderMap<http::HeaderValue>>,
+    ) -> (Vec<bytes::Bytes>, Option<CompressionStats>) {
+        let body = protocol::serialize_for_http(params.clone(), data);
+        let Some(compression) = &self.compression else {
+            return (body, None);
+        };
+        let (body, stats) = compression::compress(compression, body);
+        if let Some(stats) = &stats {
+            Arc::make_mut(extra_headers).insert(
+                http::header::CONTENT_ENCODING,
+                http::HeaderValue::from_static(stats.algorithm.as_str()),
+            );
+            #[cfg(feature = "tracing")]
+            tracing::Span::current().record("request_compressed_bytes", stats.compressed_size);
+        }
+        (body, stats)
+    }
+
+    /// Send one attempt of a request over HTTP, passing it through the middleware.
+    async fn http_attempt<R: HttpResponse>(
+        &self,
+        api: &str,
+        method: &str,
+        params: &bytes::Bytes,
+        data: &[bytes::Bytes],
+    ) -> Result<R, DecthingsClientError> {
+        let started = std::time::Instant::now();
+        let (api_key, extra_headers, early_result) = self
+            .apply_request_middleware(api, method, params, data, RpcProtocol::Http)
+            .await;
+        let mut request_compression = None;
+        let res = match early_result {
+            Some(res) => res.map(R::from_full),
+            None => {
+                async {
+                    #[cfg(feature = "tracing")]
+                    trace::record_protocol(&RpcProtocol::Http);
+
+                    // Cloning the blobs only increments reference counts.
+                    let mut extra_headers = extra_headers;
+                    let (body, stats) = self.http_body(params, data.to_vec(), &mut extra_headers);
+                    request_compression = stats;
+                    R::receive(self, api, method, body, api_key, extra_headers).await
+                }
+                .await
+            }
+        };
+        let hook = middleware::ResponseHook::new(
+            &self.middleware,
+            api,
+            method,
+            RpcProtocol::Http,
+            started,
+        )
+        .map(|hook| hook.with_request_compression(request_compression));
+        match hook {
+            Some(hook) => R::call_hook(res, hook),
+            None => res,
+        }
+    }
+
+    /// Call an RPC method on the server, and read the blobs of the response while they are
+    /// received instead of waiting for the whole response. Returns the JSON part of the response
+    /// and the blobs.
+    ///
+    /// The timeout and retry policy apply until the JSON part has been received. WebSocket
+    /// messages can only be received in full, so if the protocol is RpcProtocol::Ws, the whole
+    /// response is received before this returns.
+    #[cfg(any(feature = "dataset", feature = "debug", feature = "model"))]
+    async fn raw_method_call_streamed<P: serde::Serialize>(
+        &self,
+        api: &str,
+        method: &str,
+        params: P,
+        data: Vec<bytes::Bytes>,
+    ) -> Result<(bytes::Bytes, ResponseBlobs), DecthingsClientError> {
+        #[cfg(feature = "events")]
+        if let RpcProtocol::Ws = self.protocol() {
+            let (tx, rx) = tokio::sync::oneshot::channel();
+            self.raw_method_call(api, method, params, data, RpcProtocol::Ws, |res| {
+                tx.send(res).ok();
+                StateModification::empty()
+            })
+            .await;
+            let (response, blobs) = rx.await.unwrap()?;
+            return Ok((response, ResponseBlobs::from_blobs(blobs)));
+        }
 
+        let params = bytes::Bytes::from(serde_json::to_vec(&params).unwrap());
+        #[cfg(feature = "tracing")]
+        let span = trace::rpc_span(api, method, &params, &data);
+        let (params, data) = (&params, &data);
+        let call = self.call_with_attempts(api, method, |policy| async move {
+            // Held until the JSON part has been received.
+            let _permit = self.limits.acquire(api).await;
+            let res = self
+                .http_attempt::<(bytes::Bytes, ResponseBlobs)>(api, method, params, data)
+                .await;
+            let next = policy.next(res.as_ref().map(|(response, _)| &response[..]));
+            Some((next, res))
+        });
+        #[cfg(feature = "tracing")]
+        let call = tracing::Instrument::instrument(call, span.clone());
+        let res = match self.timeout {
+            Some(timeout) => tokio::time::timeout(timeout, call)
+                .await
+                .unwrap_or(Some(Err(DecthingsClientError::Timeout))),
+            None => call.await,
+        }
+        .expect("HTTP requests are always sent");
+        #[cfg(feature = "tracing")]
+        match &res {
+            Ok((response, _)) => trace::record_response(&span, response, response.len()),
+            Err(e) => trace::record_error(&span, e),
+        }
+        res
+    }
+
+    async fn raw_method_call_once<
+        F: FnOnce(
+                Result<(bytes::Bytes, Vec<bytes::Bytes>), DecthingsClientError>,
+            ) -> StateModification
+            + Send
+            + 'static,
+    >(
+        &self,
+        api: &str,
+        method: &str,
+        params: &bytes::Bytes,
+        data: &[bytes::Bytes],
+        #[allow(unused)] mode: RpcProtocol,
+        on_result: F,
+    ) -> bool {
+        #[cfg(feature = "events")]
+        let maybe_ws = match mode {
+            RpcProtocol::Http => None,
+            RpcProtocol::Ws => Some(self.get_or_create_socket().await),
+            RpcProtocol::WsIfAvailableOtherwiseNone => {
+                if let Some(ws) = self.maybe_get_socket().await {
+                    Some(ws)
+                } else {
+                    return false;
+                }
+            }
+        };
+        // Held until the response has been received.
+        let _permit = self.limits.acquire(api).await;
+        #[cfg(feature = "events")]
+        {
             if let Some(ws) = maybe_ws {
                 // Send over WebSocket
-                let api_key = {
-                    let locked = self.api_key.read().await;
-                    locked.clone()
-                };
-                ws.call(
+                let started = std::time::Instant::now();
+                let (api_key, _, early_result) = self
+                    .apply_request_middleware(api, method, params, data, RpcProtocol::Ws)
+                    .await;
+                let hook = middleware::ResponseHook::new(
+                    &self.middleware,
                     api,
                     method,
-                    params,
-                    api_key.as_deref(),
-                    data,
-                    Box::new(move |x| on_result(x.map_err(|e| e.into()))),
-                )
-                .await;
+                    RpcProtocol::Ws,
+                    started,
+                );
+                let on_result = move |res| {
+                    on_result(match hook {
+                        Some(hook) => hook.call(res),
+                        None => res,
+                    })
+                };
+                if let Some(res) = early_result {
+                    on_result(res);
+                    return true;
+                }
+
+                let params: &serde_json::value::RawValue =
+                    serde_json::from_slice(params).expect("params are valid JSON");
+                let (done_tx, done_rx) = tokio::sync::oneshot::channel();
+                let id = ws
+                    .call(
+                        api,
+                        method,
+                        params,
+                        api_key.as_deref(),
+                        data,
+                        Box::new(move |x| {
+                            let state_modification = on_result(x.map_err(|e| e.into()));
+                            done_tx.send(()).ok();
+                            state_modification
+                        }),
+                    )
+                    .await;
+                #[cfg(feature = "tracing")]
+                {
+                    trace::record_protocol(&RpcProtocol::Ws);
+                    tracing::Span::current().record("request_id", id);
+                }
+
+                // Cancel the request if this future is dropped before the response arrives.
+                let mut guard = websocket::CancelOnDrop::new(&ws, id);
+                done_rx.await.ok();
+                guard.disarm();
                 return true;
             }
         }
 
         // Send over HTTP
-        let res = async {
-            let body = protocol::serialize_for_http(params, data.as_ref());
-            drop(data);
-
-            let api_key_locked = self.api_key.read().await;
-            let api_key = api_key_locked.clone();
-            drop(api_key_locked);
-
-            let response_body = self
-                .http
-                .get(
-                    &self.http_server_address,
-                    api,
-                    method,
-                    body,
-                    api_key,
-                    self.extra_headers.clone(),
-                )
-                .await?;
+        on_result(self.http_attempt(api, method, params, data).await);
+        true
+    }
 
-            let deserialized = protocol::deserialize_for_http(response_body)
-                .map_err(|_| DecthingsClientError::InvalidMessage)?;
-            Ok(deserialized)
+    /// Pass a request which is about to be sent to each middleware. Returns the API key and headers
+    /// to send, and a result if one of the middleware provided it or the API key could not be
+    /// fetched.
+    async fn apply_request_middleware(
+        &self,
+        api: &str,
+        method: &str,
+        params: &[u8],
+        data: &[bytes::Bytes],
+        protocol: RpcProtocol,
+    ) -> (
+        Option<Arc<str>>,
+        Arc<http::HeaderMap<http::HeaderValue>>,
+        Option<middleware::RpcResult>,
+    ) {
+        let api_key = match &self.credentials {
+            Some(credentials) => match credentials.get().await {
+                Ok(api_key) => Some(api_key),
+                Err(e) => return (None, Arc::clone(&self.extra_headers), Some(Err(e))),
+            },
+            None => self.api_key.read().await.clone(),
+        };
+        if self.middleware.is_empty() {
+            return (api_key, Arc::clone(&self.extra_headers), None);
         }
-        .await;
-
-        on_result(res);
-        true
+        let mut request = RpcRequest {
+            api,
+            method,
+            params,
+            data,
+            protocol,
+            api_key,
+            extra_headers: (*self.extra_headers).clone(),
+        };
+        let res = middleware::on_request(&self.middleware, &mut request);
+        (request.api_key, Arc::new(request.extra_headers), res)
     }
 }
 
 pub struct DecthingsClient {
     rpc: DecthingsClientRpc,
+    #[cfg(feature = "dataset")]
     pub dataset: rpc::dataset::DatasetRpc,
+    #[cfg(feature = "debug")]
     pub debug: rpc::debug::DebugRpc,
+    #[cfg(feature = "fs")]
     pub fs: rpc::fs::FsRpc,
+    #[cfg(feature = "image")]
     pub image: rpc::image::ImageRpc,
-    #[cfg(feature = "events")]
+    #[cfg(feature = "language")]
     pub language: rpc::language::LanguageRpc,
+    #[cfg(feature = "model")]
     pub model: rpc::model::ModelRpc,
+    #[cfg(feature = "persistent-launcher")]
     pub persistent_launcher: rpc::persistent_launcher::PersistentLauncherRpc,
+    #[cfg(feature = "spawned")]
     pub spawned: rpc::spawned::SpawnedRpc,
+    #[cfg(feature = "terminal")]
     pub terminal: rpc::terminal::TerminalRpc,
 }
 
@@ -299,18 +1098,32 @@ impl Default for DecthingsClient {
 }
 
 impl DecthingsClient {
+    /// Create a client using a builder, which can read the configuration from the environment
+    /// and from a config file.
+    pub fn builder() -> DecthingsClientBuilder {
+        DecthingsClientBuilder::new()
+    }
+
     pub fn new(options: DecthingsClientOptions) -> Self {
         let rpc = DecthingsClientRpc::new(options);
         Self {
+            #[cfg(feature = "dataset")]
             dataset: rpc::dataset::DatasetRpc::new(rpc.clone()),
+            #[cfg(feature = "debug")]
             debug: rpc::debug::DebugRpc::new(rpc.clone()),
+            #[cfg(feature = "fs")]
             fs: rpc::fs::FsRpc::new(rpc.clone()),
+            #[cfg(feature = "image")]
             image: rpc::image::ImageRpc::new(rpc.clone()),
-            #[cfg(feature = "events")]
+            #[cfg(feature = "language")]
             language: rpc::language::LanguageRpc::new(rpc.clone()),
+            #[cfg(feature = "model")]
             model: rpc::model::ModelRpc::new(rpc.clone()),
+            #[cfg(feature = "persistent-launcher")]
             persistent_launcher: rpc::persistent_launcher::PersistentLauncherRpc::new(rpc.clone()),
+            #[cfg(feature = "spawned")]
             spawned: rpc::spawned::SpawnedRpc::new(rpc.clone()),
+            #[cfg(feature = "terminal")]
             terminal: rpc::terminal::TerminalRpc::new(rpc.clone()),
             rpc,
         }
@@ -319,26 +1132,28 @@ impl DecthingsClient {
     /// Call an RPC method on the server.
     ///
     /// You most likely want to use the helper classes (client.model, client.dataset, etc.) instead.
-    pub async fn raw_method_call<P: serde::Serialize, D: AsRef<[u8]>>(
+    /// Pass the blobs in `data` as `bytes::Bytes` to avoid copying them.
+    pub async fn raw_method_call<P: serde::Serialize, D: Into<bytes::Bytes>>(
         &self,
         api: &str,
         method: &str,
         params: P,
-        data: impl AsRef<[D]>,
+        data: impl IntoIterator<Item = D>,
     ) -> Result<(bytes::Bytes, Vec<bytes::Bytes>), DecthingsClientError> {
+        let data = data.into_iter().map(Into::into).collect();
         let (tx, rx) = tokio::sync::oneshot::channel();
         self.rpc
-            .raw_method_call(api, method, params, data, RpcProtocol::Http, |res| {
+            .raw_method_call(api, method, params, data, self.rpc.protocol(), |res| {
                 tx.send(res).ok();
-                StateModification {
-                    add_events: vec![],
-                    remove_events: vec![],
-                }
+                StateModification::empty()
             })
             .await;
         rx.await.unwrap()
     }
 
+    /// Add a listener which receives every event. The listener is called directly when the event
+    /// is received, so it must return quickly: other events and responses on the same WebSocket
+    /// connection wait until it does. Use `on_event_async` for listeners which do more work.
     #[cfg(feature = "events")]
     pub async fn on_event(
         &self,
@@ -347,7 +1162,96 @@ impl DecthingsClient {
         self.rpc.on_event(Box::new(cb)).await
     }
 
+    /// Add an async listener which receives every event. Events are queued for the listener and
+    /// handled one at a time by a separate task, so a slow listener does not delay other events
+    /// or responses. What happens when the queue is full is set using `options`, and the number
+    /// of dropped events can be read using `EventListenerDisposer::dropped_events`.
+    #[cfg(feature = "events")]
+    pub async fn on_event_async<F, Fut>(
+        &self,
+        options: event::EventListenerOptions,
+        cb: F,
+    ) -> event::EventListenerDisposer
+    where
+        F: Fn(event::DecthingsEvent) -> Fut + Send + Sync + 'static,
+        Fut: std::future::Future<Output = ()> + Send + 'static,
+    {
+        self.rpc.event_listeners.add_async(options, cb)
+    }
+
+    /// Replace the API key. Has no effect if `DecthingsClientOptions::credential_provider` is set.
     pub async fn set_api_key(&self, api_key: String) {
         self.rpc.set_api_key(api_key).await;
     }
 }
+
+#[cfg(feature = "mock")]
+#[tokio::test]
+async fn test_timeout() {
+    use crate::mock::{MockServer, ping};
+    use std::time::Duration;
+
+    let server = MockServer::start().await.unwrap();
+    server.handle_ping(Some(Duration::from_secs(10))).await;
+
+    for protocol in [RpcProtocol::Http, RpcProtocol::Ws] {
+        let client = DecthingsClient::new(DecthingsClientOptions {
+            timeout: Some(Duration::from_millis(50)),
+            default_protocol: protocol,
+            ..server.client_options()
+        });
+        assert!(matches!(
+            ping(&client).await,
+            Err(DecthingsClientError::Timeout)
+        ));
+    }
+
+    // The timed out request is removed from the WebSocket, so the unused socket is closed.
+    for _ in 0..100 {
+        if server.connected_websockets().await == 0 {
+            return;
+        }
+        tokio::time::sleep(Duration::from_millis(10)).await;
+    }
+    panic!("the WebSocket was not closed");
+}
+
+#[cfg(all(feature = "mock", feature = "fs"))]
+#[tokio::test]
+async fn test_protocol() {
+    use crate::mock::{MockProtocol, MockResponse, MockServer};
+
+    let server = MockServer::start().await.unwrap();
+    let protocols = Arc::new(std::sync::Mutex::new(vec![]));
+    let protocols_clone = Arc::clone(&protocols);
+    server
+        .handle("FS", "read", move |req| {
+            protocols_clone.lock().unwrap().push(req.protocol);
+            MockResponse::result(serde_json::json!({})).with_data([&b"hello"[..]])
+        })
+        .await;
+
+    let client = DecthingsClient::new(DecthingsClientOptions {
+        default_protocol: RpcProtocol::Ws,
+        ..server.client_options()
+    });
+    let params = || rpc::fs::ReadParams {
+        model_id: "model",
+        version_id: None,
+        inode: 1,
+        offset: 0,
+        count: 5,
+    };
+    client.fs.read(params()).await.unwrap();
+    client
+        .fs
+        .with_protocol(RpcProtocol::Http)
+        .read(params())
+        .await
+        .unwrap();
+
+    assert_eq!(
+        *protocols.lock().unwrap(),
+        [MockProtocol::Ws, MockProtocol::Http]
+    );
+}
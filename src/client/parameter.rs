@@ -41,9 +41,10 @@ pub struct DecthingsParameterProvider<'a> {
     pub data: DecthingsParameterProviderData<'a>,
 }
 
+#[cfg(any(feature = "debug", feature = "model"))]
 pub(crate) fn serialize_parameter_provider_list<'a, 'b: 'a>(
     list: impl Iterator<Item = &'a DecthingsParameterProvider<'b>>,
-) -> Vec<Vec<u8>> {
+) -> Vec<bytes::Bytes> {
     list.filter_map(|x| match &x.data {
         DecthingsParameterProviderData::Dataset { .. } => None,
         DecthingsParameterProviderData::Data(d) => Some(d),
@@ -53,7 +54,7 @@ pub(crate) fn serialize_parameter_provider_list<'a, 'b: 'a>(
         for element in x {
             element.serialize_append(&mut res);
         }
-        res
+        res.into()
     })
     .collect()
 }
@@ -21,42 +21,40 @@ impl<'a, P: serde::Serialize + 'a> RequestMessage<'a, P> {
     }
 }
 
-// Message protocol:
-// 1. u8 specifying number of blobs
-// 2. Varint specifying length of JSON data
-// 3. One varint for each blob, specifying the length
-// 4. JSON data
-// 5. Blobs
-pub(crate) fn serialize_for_http<P: serde::Serialize>(
-    params: P,
-    data: &[impl AsRef<[u8]>],
-) -> Vec<u8> {
-    let msg_buf = serde_json::to_vec(&params).unwrap();
-
-    let total_length = 1
-        + crate::varint::get_varint_u64_len(msg_buf.len() as u64) as usize
-        + data
-            .iter()
-            .map(|x| crate::varint::get_varint_u64_len(x.as_ref().len() as u64) as usize)
-            .sum::<usize>()
-        + msg_buf.len()
-        + data.iter().map(|x| x.as_ref().len()).sum::<usize>();
-
-    let mut ret = Vec::with_capacity(total_length);
+/// Serialize the header which precedes the JSON data in a request. See serialize_for_http.
+fn serialize_request_header(json_len: usize, data: &[impl AsRef<[u8]>], ret: &mut Vec<u8>) {
     ret.push(
         data.len()
             .try_into()
             .expect("Cannot send more than u8::MAX blobs in one request."),
     );
-    crate::varint::append_varint_u64(msg_buf.len() as u64, &mut ret);
+    crate::varint::append_varint_u64(json_len as u64, ret);
     for blob in data {
-        crate::varint::append_varint_u64(blob.as_ref().len() as u64, &mut ret);
-    }
-    ret.extend_from_slice(&msg_buf);
-    for blob in data {
-        ret.extend_from_slice(blob.as_ref());
+        crate::varint::append_varint_u64(blob.as_ref().len() as u64, ret);
     }
+}
 
+// Message protocol:
+// 1. u8 specifying number of blobs
+// 2. Varint specifying length of JSON data
+// 3. One varint for each blob, specifying the length
+// 4. JSON data
+// 5. Blobs
+//
+// The params are passed already serialized as JSON. The message is returned as a list of
+// segments which should be sent in order: The header (1-3), the JSON data and then each blob.
+// Neither the JSON data nor the blobs are copied.
+pub(crate) fn serialize_for_http(
+    params: bytes::Bytes,
+    data: Vec<bytes::Bytes>,
+) -> Vec<bytes::Bytes> {
+    let mut header = Vec::with_capacity(1 + 10 * (data.len() + 1));
+    serialize_request_header(params.len(), &data, &mut header);
+
+    let mut ret = Vec::with_capacity(data.len() + 2);
+    ret.push(header.into());
+    ret.push(params);
+    ret.extend(data);
     ret
 }
 
@@ -68,6 +66,8 @@ pub(crate) fn serialize_for_http<P: serde::Serialize>(
 // 4. One varint for each blob, specifying the length
 // 5. JSON data
 // 6. Blobs
+//
+// tungstenite requires each message to be a single Vec, so the blobs are copied once into it.
 pub(crate) fn serialize_for_websocket<P: serde::Serialize>(
     id: u32,
     message: RequestMessage<'_, P>,
@@ -86,15 +86,7 @@ pub(crate) fn serialize_for_websocket<P: serde::Serialize>(
 
     let mut ret = Vec::with_capacity(total_length);
     ret.extend_from_slice(&id.to_be_bytes());
-    ret.push(
-        data.len()
-            .try_into()
-            .expect("Cannot send more than u8::MAX blobs in one request."),
-    );
-    crate::varint::append_varint_u64(msg_buf.len() as u64, &mut ret);
-    for blob in data {
-        crate::varint::append_varint_u64(blob.as_ref().len() as u64, &mut ret);
-    }
+    serialize_request_header(msg_buf.len(), data, &mut ret);
     ret.extend_from_slice(&msg_buf);
     for blob in data {
         ret.extend_from_slice(blob.as_ref());
@@ -103,50 +95,224 @@ pub(crate) fn serialize_for_websocket<P: serde::Serialize>(
     ret
 }
 
-// Message protocol:
-// 1. Varint specifying length of JSON data
-// 2. JSON data
-// Repeated:
-// 3. Varint encoding length of next blob
-// 4. Next blob
-pub(crate) fn deserialize_for_http(
-    data: bytes::Bytes,
-) -> Result<(bytes::Bytes, Vec<bytes::Bytes>), ()> {
-    if data.is_empty() {
-        return Err(());
+/// Limits which are applied to the messages received from Decthings, so that a corrupted or
+/// malicious message is rejected before it uses a lot of memory. See
+/// `DecthingsClientOptions::decode_limits`.
+///
+/// If both limits are set, a WebSocket message which is larger than the limits allow in total is
+/// rejected before it is received in full. The WebSocket connection is then closed, since the
+/// request the message belongs to is not known.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct DecodeLimits {
+    /// Maximum number of blobs in one message. Default: no limit.
+    pub max_blobs: Option<usize>,
+    /// Maximum size in bytes of the JSON data and of each blob. Default: no limit.
+    pub max_blob_size: Option<u64>,
+}
+
+/// Returned when a message received from Decthings could not be decoded.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DeserializeError {
+    /// Position in the message of the field which could not be read.
+    pub offset: u64,
+    pub kind: DeserializeErrorKind,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum DeserializeErrorKind {
+    /// The message ended in the middle of a field. Contains the name of the field.
+    UnexpectedEnd(&'static str),
+    /// The first byte of a WebSocket message is neither 0 (response) nor 1 (event).
+    UnknownMessageType(u8),
+    /// A length does not fit in memory on this platform.
+    LengthOverflow(u64),
+    /// The message has more blobs than `DecodeLimits::max_blobs`.
+    TooManyBlobs { limit: usize },
+    /// The JSON data or a blob is larger than `DecodeLimits::max_blob_size`.
+    BlobTooLarge { size: u64, limit: u64 },
+}
+
+impl std::fmt::Display for DeserializeError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match &self.kind {
+            DeserializeErrorKind::UnexpectedEnd(field) => {
+                write!(f, "message ended while reading {field}")?
+            }
+            DeserializeErrorKind::UnknownMessageType(ty) => write!(f, "unknown message type {ty}")?,
+            DeserializeErrorKind::LengthOverflow(len) => write!(f, "length {len} is too large")?,
+            DeserializeErrorKind::TooManyBlobs { limit } => {
+                write!(f, "message has more than {limit} blobs")?
+            }
+            DeserializeErrorKind::BlobTooLarge { size, limit } => write!(
+                f,
+                "blob of {size} bytes is larger than the limit of {limit} bytes"
+            )?,
+        }
+        write!(f, " at offset {}", self.offset)
+    }
+}
+
+impl std::error::Error for DeserializeError {}
+
+impl DecodeLimits {
+    /// Maximum size in bytes of the JSON data and the blobs of a message, including their lengths,
+    /// or None if there is no limit.
+    #[cfg(any(feature = "events", not(target_os = "espidf")))]
+    pub(crate) fn max_body_size(&self) -> Option<u64> {
+        let segments = u64::try_from(self.max_blobs?).ok()?.checked_add(1)?;
+        // Each segment starts with a varint of at most 9 bytes.
+        self.max_blob_size?.checked_add(9)?.checked_mul(segments)
     }
-    let v_length = crate::varint::get_serialized_varint_u64_len(&data) as usize;
-    if data.len() < v_length {
-        return Err(());
+
+    /// Maximum size in bytes of a whole WebSocket message, or None if there is no limit.
+    #[cfg(feature = "events")]
+    pub(crate) fn max_message_size(&self) -> Option<u64> {
+        // The WebSocket header is at most 257 bytes: the message type, and a request id or an API
+        // name with its length.
+        self.max_body_size()?.checked_add(257)
+    }
+
+    /// Check the length of the JSON data or of a blob read at `offset`.
+    pub(crate) fn check_size(&self, size: u64, offset: u64) -> Result<(), DeserializeError> {
+        match self.max_blob_size {
+            Some(limit) if size > limit => Err(DeserializeError {
+                offset,
+                kind: DeserializeErrorKind::BlobTooLarge { size, limit },
+            }),
+            _ => Ok(()),
+        }
+    }
+
+    /// Check that another blob is allowed after `count` blobs, at `offset`.
+    pub(crate) fn check_count(&self, count: usize, offset: u64) -> Result<(), DeserializeError> {
+        match self.max_blobs {
+            Some(limit) if count >= limit => Err(DeserializeError {
+                offset,
+                kind: DeserializeErrorKind::TooManyBlobs { limit },
+            }),
+            _ => Ok(()),
+        }
+    }
+}
+
+/// Reads the fields of a message, checking that each one is within bounds.
+struct Reader<'a> {
+    data: &'a bytes::Bytes,
+    pos: usize,
+}
+
+impl Reader<'_> {
+    fn error(&self, kind: DeserializeErrorKind) -> DeserializeError {
+        DeserializeError {
+            offset: self.pos as u64,
+            kind,
+        }
     }
-    let (length, _) = crate::varint::deserialize_varint_u64(&data);
-    let length = length as usize;
 
-    if data.len() < v_length + length {
-        return Err(());
+    fn is_empty(&self) -> bool {
+        self.pos == self.data.len()
     }
 
-    let first_blob = data.slice(v_length..v_length + length);
+    #[cfg(any(feature = "events", feature = "mock"))]
+    fn u8(&mut self, field: &'static str) -> Result<u8, DeserializeError> {
+        let value = *self
+            .data
+            .get(self.pos)
+            .ok_or_else(|| self.error(DeserializeErrorKind::UnexpectedEnd(field)))?;
+        self.pos += 1;
+        Ok(value)
+    }
 
-    let mut blobs = Vec::new();
-    let mut pos = v_length + length;
-    while pos < data.len() {
-        let blob_v_length = crate::varint::get_serialized_varint_u64_len(&data[pos..]) as usize;
-        if data.len() < pos + blob_v_length {
-            return Err(());
+    fn bytes(&mut self, len: usize, field: &'static str) -> Result<bytes::Bytes, DeserializeError> {
+        if self.data.len() - self.pos < len {
+            return Err(self.error(DeserializeErrorKind::UnexpectedEnd(field)));
         }
-        let (blob_length, _) = crate::varint::deserialize_varint_u64(&data[pos..]);
-        let blob_length = blob_length as usize;
+        let value = self.data.slice(self.pos..self.pos + len);
+        self.pos += len;
+        Ok(value)
+    }
+
+    #[cfg(any(feature = "events", feature = "mock"))]
+    fn u32(&mut self, field: &'static str) -> Result<u32, DeserializeError> {
+        let value = self.bytes(4, field)?;
+        Ok(u32::from_be_bytes(value[..].try_into().unwrap()))
+    }
+
+    /// Read a varint length, followed by that many bytes.
+    fn segment(
+        &mut self,
+        field: &'static str,
+        limits: &DecodeLimits,
+    ) -> Result<bytes::Bytes, DeserializeError> {
+        let len = self.length(field, limits)?;
+        self.bytes(len, field)
+    }
+
+    /// Read a varint length, and check it against `limits`.
+    fn length(
+        &mut self,
+        field: &'static str,
+        limits: &DecodeLimits,
+    ) -> Result<usize, DeserializeError> {
+        let (len, varint_len) = crate::varint::deserialize_varint_u64(&self.data[self.pos..])
+            .ok_or_else(|| self.error(DeserializeErrorKind::UnexpectedEnd(field)))?;
+        limits.check_size(len, self.pos as u64)?;
+        let len = usize::try_from(len)
+            .map_err(|_| self.error(DeserializeErrorKind::LengthOverflow(len)))?;
+        self.pos += varint_len as usize;
+        Ok(len)
+    }
 
-        if data.len() < pos + blob_v_length + blob_length {
-            return Err(());
+    /// Read the JSON data and the blobs which follow it until the end of the message.
+    fn response_body(
+        &mut self,
+        limits: &DecodeLimits,
+    ) -> Result<(bytes::Bytes, Vec<bytes::Bytes>), DeserializeError> {
+        let json = self.segment("JSON data", limits)?;
+        let mut blobs = Vec::new();
+        while !self.is_empty() {
+            limits.check_count(blobs.len(), self.pos as u64)?;
+            blobs.push(self.segment("blob", limits)?);
         }
+        Ok((json, blobs))
+    }
+}
 
-        blobs.push(data.slice(pos + blob_v_length..pos + blob_v_length + blob_length));
-        pos += blob_length + blob_v_length;
+// Message protocol:
+// 1. Varint specifying length of JSON data
+// 2. JSON data
+// Repeated:
+// 3. Varint encoding length of next blob
+// 4. Next blob
+pub(crate) fn deserialize_for_http(
+    data: bytes::Bytes,
+    limits: &DecodeLimits,
+) -> Result<(bytes::Bytes, Vec<bytes::Bytes>), DeserializeError> {
+    Reader {
+        data: &data,
+        pos: 0,
     }
+    .response_body(limits)
+}
 
-    Ok((first_blob, blobs))
+#[derive(serde::Deserialize)]
+struct ErrorCode<'a> {
+    #[serde(borrow)]
+    code: std::borrow::Cow<'a, str>,
+}
+
+#[derive(serde::Deserialize)]
+struct MaybeErrorResponse<'a> {
+    #[serde(borrow)]
+    error: Option<ErrorCode<'a>>,
+}
+
+/// Returns the error code of a JSON response, or None if the response is not an error.
+pub(crate) fn peek_error_code(response: &[u8]) -> Option<std::borrow::Cow<'_, str>> {
+    serde_json::from_slice::<MaybeErrorResponse<'_>>(response)
+        .ok()?
+        .error
+        .map(|error| error.code)
 }
 
 #[cfg(feature = "events")]
@@ -166,78 +332,268 @@ pub enum RpcResponseOrEvent {
 // Repeated:
 // 7. Varint encoding length of next blob
 // 8. Next blob
+//
+// Returns an error if the header (1-3) could not be read. Otherwise the header is returned along
+// with the result of reading the rest, so that the error can be passed to the request it belongs
+// to.
 pub(crate) fn deserialize_for_websocket(
     data: bytes::Bytes,
-) -> Result<(RpcResponseOrEvent, bytes::Bytes, Vec<bytes::Bytes>), ()> {
-    if data.is_empty() {
-        return Err(());
-    }
-
-    let first_byte = data[0];
-    let mut pos = 1;
-    let rpc_response_or_event = if first_byte == 0 {
-        // Response message
-        if data.len() < pos + 4 {
-            return Err(());
+    limits: &DecodeLimits,
+) -> Result<
+    (
+        RpcResponseOrEvent,
+        Result<(bytes::Bytes, Vec<bytes::Bytes>), DeserializeError>,
+    ),
+    DeserializeError,
+> {
+    let mut reader = Reader {
+        data: &data,
+        pos: 0,
+    };
+    let rpc_response_or_event = match reader.u8("message type")? {
+        0 => RpcResponseOrEvent::RpcResponse(reader.u32("request id")?),
+        1 => {
+            let api_length = reader.u8("API name length")?;
+            RpcResponseOrEvent::Event(reader.bytes(api_length.into(), "API name")?)
         }
-        let id = u32::from_be_bytes(data[pos..pos + 4].try_into().unwrap());
-        pos += 4;
-
-        RpcResponseOrEvent::RpcResponse(id)
-    } else {
-        if data.len() < pos + 1 {
-            return Err(());
+        ty => {
+            return Err(DeserializeError {
+                offset: 0,
+                kind: DeserializeErrorKind::UnknownMessageType(ty),
+            });
         }
-        let api_length = data[pos] as usize;
-        pos += 1;
+    };
+    Ok((rpc_response_or_event, reader.response_body(limits)))
+}
 
-        if data.len() < pos + api_length {
-            return Err(());
-        }
-        let api_bytes = data.slice(pos..pos + api_length);
-        pos += api_length;
+#[cfg(feature = "mock")]
+fn deserialize_request_body(
+    reader: &mut Reader<'_>,
+) -> Result<(bytes::Bytes, Vec<bytes::Bytes>), DeserializeError> {
+    let limits = DecodeLimits::default();
+    let num_blobs = reader.u8("blob count")?;
+    let lengths = (0..=num_blobs)
+        .map(|_| reader.length("length", &limits))
+        .collect::<Result<Vec<_>, _>>()?;
+    let mut segments = lengths
+        .into_iter()
+        .map(|length| reader.bytes(length, "blob"))
+        .collect::<Result<Vec<_>, _>>()?;
+    let first_blob = segments.remove(0);
+    Ok((first_blob, segments))
+}
 
-        RpcResponseOrEvent::Event(api_bytes)
+#[cfg(feature = "mock")]
+/// Server side of `serialize_for_http`.
+pub(crate) fn deserialize_request_for_http(
+    data: bytes::Bytes,
+) -> Result<(bytes::Bytes, Vec<bytes::Bytes>), ()> {
+    let mut reader = Reader {
+        data: &data,
+        pos: 0,
     };
+    let res = deserialize_request_body(&mut reader).map_err(|_| ())?;
+    reader.is_empty().then_some(res).ok_or(())
+}
 
-    if data.len() < pos + 1 {
-        return Err(());
-    }
+#[cfg(feature = "mock")]
+/// Server side of `serialize_for_websocket`.
+pub(crate) fn deserialize_request_for_websocket(
+    data: bytes::Bytes,
+) -> Result<(u32, bytes::Bytes, Vec<bytes::Bytes>), ()> {
+    let mut reader = Reader {
+        data: &data,
+        pos: 0,
+    };
+    let id = reader.u32("request id").map_err(|_| ())?;
+    let (first_blob, blobs) = deserialize_request_body(&mut reader).map_err(|_| ())?;
+    reader
+        .is_empty()
+        .then_some((id, first_blob, blobs))
+        .ok_or(())
+}
 
-    let v_length = crate::varint::get_serialized_varint_u64_len(&data[pos..]) as usize;
-    if data.len() < pos + v_length {
-        return Err(());
+#[cfg(feature = "mock")]
+fn append_response_body(json: &[u8], data: &[bytes::Bytes], ret: &mut Vec<u8>) {
+    crate::varint::append_varint_u64(json.len() as u64, ret);
+    ret.extend_from_slice(json);
+    for blob in data {
+        crate::varint::append_varint_u64(blob.len() as u64, ret);
+        ret.extend_from_slice(blob);
     }
-    let (length, _) = crate::varint::deserialize_varint_u64(&data[pos..]);
-    let length = length as usize;
+}
+
+#[cfg(feature = "mock")]
+/// Server side of `deserialize_for_http`.
+pub(crate) fn serialize_response_for_http(json: &[u8], data: &[bytes::Bytes]) -> Vec<u8> {
+    let mut ret = vec![];
+    append_response_body(json, data, &mut ret);
+    ret
+}
+
+#[cfg(feature = "mock")]
+/// Server side of `deserialize_for_websocket`, for RPC responses.
+pub(crate) fn serialize_response_for_websocket(
+    id: u32,
+    json: &[u8],
+    data: &[bytes::Bytes],
+) -> Vec<u8> {
+    let mut ret = vec![0];
+    ret.extend_from_slice(&id.to_be_bytes());
+    append_response_body(json, data, &mut ret);
+    ret
+}
+
+#[cfg(feature = "mock")]
+/// Server side of `deserialize_for_websocket`, for events.
+pub(crate) fn serialize_event_for_websocket(
+    api: &str,
+    json: &[u8],
+    data: &[bytes::Bytes],
+) -> Vec<u8> {
+    let mut ret = vec![1];
+    ret.push(
+        api.len()
+            .try_into()
+            .expect("The api name cannot be longer than u8::MAX bytes."),
+    );
+    ret.extend_from_slice(api.as_bytes());
+    append_response_body(json, data, &mut ret);
+    ret
+}
 
-    pos += v_length;
+#[cfg(feature = "mock")]
+#[test]
+fn test_serialize_for_http_segments() {
+    let blob = bytes::Bytes::from(vec![7u8; 300]);
+    let segments = serialize_for_http(br#"{"a":1}"#.to_vec().into(), vec![blob.clone()]);
 
-    if data.len() < pos + length {
-        return Err(());
+    // The blob is passed on without being copied.
+    assert_eq!(segments.len(), 3);
+    assert_eq!(segments[2].as_ptr(), blob.as_ptr());
+
+    let body: Vec<u8> = segments.concat();
+    let (params, data) = deserialize_request_for_http(body.into()).unwrap();
+    assert_eq!(&params[..], br#"{"a":1}"#);
+    assert_eq!(data, vec![blob]);
+}
+
+#[cfg(test)]
+fn encode_response_body(json: &[u8], blobs: &[Vec<u8>]) -> Vec<u8> {
+    let mut ret = vec![];
+    crate::varint::append_varint_u64(json.len() as u64, &mut ret);
+    ret.extend_from_slice(json);
+    for blob in blobs {
+        crate::varint::append_varint_u64(blob.len() as u64, &mut ret);
+        ret.extend_from_slice(blob);
     }
+    ret
+}
 
-    let first_blob = data.slice(pos..pos + length);
-    pos += length;
+#[test]
+fn test_deserialize_error_offset() {
+    let limits = DecodeLimits::default();
+    // A blob length which is cut off after the first of its three bytes.
+    let mut body = encode_response_body(b"{}", &[vec![1, 2]]);
+    body.push(253);
+    let err = deserialize_for_http(body.into(), &limits).unwrap_err();
+    assert_eq!(err.offset, 6);
+    assert_eq!(err.kind, DeserializeErrorKind::UnexpectedEnd("blob"));
+    assert_eq!(
+        err.to_string(),
+        "message ended while reading blob at offset 6"
+    );
 
-    let mut blobs = Vec::new();
-    while pos < data.len() {
-        let blob_v_length = crate::varint::get_serialized_varint_u64_len(&data[pos..]) as usize;
-        if data.len() < pos + blob_v_length {
-            return Err(());
+    let body = bytes::Bytes::from(encode_response_body(b"{}", &[vec![0; 300], vec![]]));
+    let err = deserialize_for_http(
+        body.clone(),
+        &DecodeLimits {
+            max_blobs: None,
+            max_blob_size: Some(256),
+        },
+    )
+    .unwrap_err();
+    assert_eq!(err.offset, 3);
+    assert_eq!(
+        err.kind,
+        DeserializeErrorKind::BlobTooLarge {
+            size: 300,
+            limit: 256
         }
-        let (blob_length, _) = crate::varint::deserialize_varint_u64(&data[pos..]);
-        let blob_length = blob_length as usize;
+    );
+    let err = deserialize_for_http(
+        body,
+        &DecodeLimits {
+            max_blobs: Some(1),
+            max_blob_size: None,
+        },
+    )
+    .unwrap_err();
+    assert_eq!(err.offset, 306);
+    assert_eq!(err.kind, DeserializeErrorKind::TooManyBlobs { limit: 1 });
+}
 
-        pos += blob_v_length;
+#[cfg(test)]
+proptest::proptest! {
+    #[test]
+    fn prop_deserialize_arbitrary_bytes(
+        data in proptest::collection::vec(proptest::num::u8::ANY, 0..64),
+    ) {
+        let len = data.len() as u64;
+        let data = bytes::Bytes::from(data);
+        if let Err(e) = deserialize_for_http(data.clone(), &DecodeLimits::default()) {
+            proptest::prop_assert!(e.offset <= len);
+        }
+        #[cfg(feature = "events")]
+        if let Err(e) | Ok((_, Err(e))) = deserialize_for_websocket(data, &DecodeLimits::default()) {
+            proptest::prop_assert!(e.offset <= len);
+        }
+    }
 
-        if data.len() < pos + blob_length {
-            return Err(());
+    #[test]
+    fn prop_deserialize_roundtrip(
+        json in proptest::collection::vec(proptest::num::u8::ANY, 0..300),
+        blobs in proptest::collection::vec(
+            proptest::collection::vec(proptest::num::u8::ANY, 0..300),
+            0..4,
+        ),
+        cut in proptest::num::usize::ANY,
+        chunk_size in 1..50usize,
+    ) {
+        let limits = DecodeLimits::default();
+        let body = encode_response_body(&json, &blobs);
+        let (res_json, res_blobs) = deserialize_for_http(body.clone().into(), &limits).unwrap();
+        proptest::prop_assert_eq!(&res_json[..], &json[..]);
+        proptest::prop_assert_eq!(&res_blobs, &blobs);
+
+        // A truncated message either fails or contains only the blobs before the cut.
+        let cut = cut % body.len();
+        let truncated = bytes::Bytes::copy_from_slice(&body[..cut]);
+        if let Ok((res_json, res_blobs)) = deserialize_for_http(truncated, &limits) {
+            proptest::prop_assert_eq!(&res_json[..], &json[..]);
+            proptest::prop_assert!(res_blobs.len() <= blobs.len());
+            proptest::prop_assert_eq!(&res_blobs[..], &blobs[..res_blobs.len()]);
         }
 
-        blobs.push(data.slice(pos..pos + blob_length));
-        pos += blob_length;
+        // Reading the message as a stream of chunks gives the same result.
+        let chunks = body
+            .chunks(chunk_size)
+            .map(|chunk| Ok(bytes::Bytes::copy_from_slice(chunk)))
+            .collect::<Vec<_>>();
+        let streamed = futures::executor::block_on(async {
+            let (json, mut response_blobs) = super::ResponseBlobs::from_http_stream(
+                Box::pin(futures::stream::iter(chunks)),
+                limits,
+            )
+            .await?;
+            let mut blobs = vec![];
+            while response_blobs.next_blob().await?.is_some() {
+                blobs.push(response_blobs.read_blob().await?.to_vec());
+            }
+            Ok::<_, super::DecthingsClientError>((json, blobs))
+        })
+        .unwrap();
+        proptest::prop_assert_eq!(&streamed.0[..], &json[..]);
+        proptest::prop_assert_eq!(&streamed.1, &blobs);
     }
-
-    Ok((rpc_response_or_event, first_blob, blobs))
 }
@@ -1,39 +1,152 @@
 use std::sync::Arc;
 
-use super::DecthingsClientError;
+use futures::TryStreamExt;
+
+use super::{
+    ByteStream, DecodeLimits, DecthingsClientError, Transport, TransportFuture,
+    TransportStreamFuture,
+    compression::{self, Decoder},
+    proxy::Proxy,
+    tls::TlsConfig,
+};
 
-#[derive(Clone, Default)]
 pub(crate) struct HttpImpl {
     client: reqwest::Client,
+    http_server_address: String,
+    accept_encoding: Option<&'static str>,
+    max_decompressed_size: Option<u64>,
 }
 
 impl HttpImpl {
-    pub async fn get(
+    /// Panics if the TLS backend could not be initialized, like `reqwest::Client::new`. If
+    /// `accept_compressed` is set, Decthings is asked to compress responses, which are then not
+    /// decompressed beyond the size `decode_limits` allows.
+    pub fn new(
+        http_server_address: String,
+        tls: &TlsConfig,
+        proxy: Option<&Proxy>,
+        accept_compressed: bool,
+        decode_limits: &DecodeLimits,
+    ) -> Self {
+        let builder = match proxy {
+            Some(proxy) => reqwest::Client::builder().proxy(proxy.to_reqwest()),
+            None => reqwest::Client::builder(),
+        };
+        Self {
+            client: tls
+                .configure_reqwest(builder)
+                .and_then(|builder| builder.build())
+                .expect("failed to create HTTP client"),
+            http_server_address,
+            accept_encoding: compression::accept_encoding().filter(|_| accept_compressed),
+            max_decompressed_size: decode_limits.max_body_size(),
+        }
+    }
+
+    async fn send(
         &self,
-        http_server_address: &str,
         api: &str,
         method: &str,
-        body: Vec<u8>,
+        body: Vec<bytes::Bytes>,
         api_key: Option<Arc<str>>,
         extra_headers: Arc<http::HeaderMap<http::HeaderValue>>,
-    ) -> Result<bytes::Bytes, DecthingsClientError> {
+    ) -> Result<reqwest::Response, DecthingsClientError> {
+        // The segments are streamed as they are, so the body is never copied into one buffer.
+        let content_length = body.iter().map(|segment| segment.len()).sum::<usize>();
         let mut builder = self
             .client
-            .post(format!("{}/{}/{}", http_server_address, api, method))
+            .post(format!("{}/{}/{}", self.http_server_address, api, method))
             .header(reqwest::header::CONTENT_TYPE, "application/octet-stream")
-            .body(body);
+            .header(reqwest::header::CONTENT_LENGTH, content_length)
+            .body(reqwest::Body::wrap_stream(futures::stream::iter(
+                body.into_iter().map(Ok::<_, std::convert::Infallible>),
+            )));
         if let Some(api_key) = api_key {
             builder = builder.bearer_auth(api_key);
         }
+        if let Some(accept_encoding) = self.accept_encoding {
+            builder = builder.header(reqwest::header::ACCEPT_ENCODING, accept_encoding);
+        }
 
         for (key, value) in &*extra_headers {
             builder = builder.header(key, value);
         }
         let result = builder.send().await;
-        let response = result
+        #[cfg(feature = "tracing")]
+        match &result {
+            Ok(response) => tracing::trace!(
+                url = %response.url(),
+                status = response.status().as_u16(),
+                "HTTP response received"
+            ),
+            Err(e) => tracing::debug!(error = %e, "HTTP request failed"),
+        }
+        result
             .map_err(DecthingsClientError::Http)?
             .error_for_status()
-            .map_err(DecthingsClientError::Http)?;
-        response.bytes().await.map_err(DecthingsClientError::Http)
+            .map_err(DecthingsClientError::Http)
+    }
+}
+
+/// The decoder for the Content-Encoding of a response, or None if it is not compressed.
+fn response_decoder(
+    response: &reqwest::Response,
+    max_size: Option<u64>,
+) -> Result<Option<Decoder>, DecthingsClientError> {
+    let content_encoding = response
+        .headers()
+        .get(reqwest::header::CONTENT_ENCODING)
+        .map(|value| value.to_str().unwrap_or_default());
+    Decoder::new(content_encoding, max_size).map_err(DecthingsClientError::Decompress)
+}
+
+impl Transport for HttpImpl {
+    fn get<'a>(
+        &'a self,
+        api: &'a str,
+        method: &'a str,
+        body: Vec<bytes::Bytes>,
+        api_key: Option<Arc<str>>,
+        extra_headers: Arc<http::HeaderMap<http::HeaderValue>>,
+    ) -> TransportFuture<'a> {
+        Box::pin(async move {
+            let response = self.send(api, method, body, api_key, extra_headers).await?;
+            let decoder = response_decoder(&response, self.max_decompressed_size)?;
+            let body = response.bytes().await.map_err(DecthingsClientError::Http)?;
+            let Some(mut decoder) = decoder else {
+                return Ok(body);
+            };
+            let decompressed = decoder
+                .write(&body)
+                .and_then(|head| Ok([head, decoder.finish()?].concat()))
+                .map_err(DecthingsClientError::Decompress)?;
+            #[cfg(feature = "tracing")]
+            tracing::debug!(
+                compressed_bytes = body.len(),
+                decompressed_bytes = decompressed.len(),
+                "HTTP response decompressed"
+            );
+            Ok(decompressed.into())
+        })
+    }
+
+    fn get_streamed<'a>(
+        &'a self,
+        api: &'a str,
+        method: &'a str,
+        body: Vec<bytes::Bytes>,
+        api_key: Option<Arc<str>>,
+        extra_headers: Arc<http::HeaderMap<http::HeaderValue>>,
+    ) -> TransportStreamFuture<'a> {
+        Box::pin(async move {
+            let response = self.send(api, method, body, api_key, extra_headers).await?;
+            let decoder = response_decoder(&response, self.max_decompressed_size)?;
+            let stream =
+                Box::pin(response.bytes_stream().map_err(DecthingsClientError::Http)) as ByteStream;
+            Ok(match decoder {
+                Some(decoder) => compression::decode_stream(stream, decoder),
+                None => stream,
+            })
+        })
     }
 }
@@ -0,0 +1,233 @@
+use std::{
+    hash::{BuildHasher, Hasher},
+    time::Duration,
+};
+
+use super::DecthingsClientError;
+
+/// Methods which have a different effect if they are sent twice, for example because they create
+/// something, append data or run user code. If the response to one of these is lost, the request
+/// may already have had an effect, so they are not retried unless
+/// `RetryPolicy::retry_non_idempotent` is set. Methods which only read, update to a given value or
+/// delete are not listed. Model/evaluate is not listed since it does not change any state.
+const NON_IDEMPOTENT_METHODS: &[(&str, &str)] = &[
+    ("Dataset", "createDataset"),
+    ("Dataset", "addEntries"),
+    ("Dataset", "addEntriesToNeedsReview"),
+    ("Dataset", "finalizeNeedsReviewEntries"),
+    ("Dataset", "removeEntries"),
+    ("Dataset", "removeNeedsReviewEntries"),
+    ("Debug", "launchDebugSession"),
+    ("Debug", "callInitializeWeights"),
+    ("Debug", "callInstantiateModel"),
+    ("Debug", "callTrain"),
+    ("Debug", "callEvaluate"),
+    ("Debug", "callGetWeights"),
+    ("Debug", "sendToRemoteInspector"),
+    ("FS", "mknod"),
+    ("FS", "symlink"),
+    ("FS", "mkdir"),
+    ("FS", "link"),
+    ("FS", "rename"),
+    ("FS", "copy"),
+    ("FS", "write"),
+    ("Image", "createRepository"),
+    ("Language", "startLanguageServer"),
+    ("Language", "writeToLanguageServer"),
+    ("Model", "createModel"),
+    ("Model", "createModelVersion"),
+    ("Model", "createModelVersionUploadWeights"),
+    ("Model", "train"),
+    ("PersistentLauncher", "createPersistentLauncher"),
+    ("Spawned", "spawnCommand"),
+    ("Spawned", "spawnCommandForModel"),
+    ("Spawned", "writeToSpawnedCommand"),
+    ("Terminal", "launchTerminalSession"),
+    ("Terminal", "writeToTerminalSession"),
+];
+
+/// Controls how failed requests are retried. See `DecthingsClientOptions::retry_policy`.
+#[derive(Debug, Clone)]
+pub struct RetryPolicy {
+    /// Maximum number of attempts, including the first one. Default: 3.
+    pub max_attempts: u32,
+    /// Delay before the first retry. The delay is doubled for each following retry. Default:
+    /// 200 milliseconds.
+    pub initial_backoff: Duration,
+    /// Upper limit for the delay between two attempts. Default: 10 seconds.
+    pub max_backoff: Duration,
+    /// If true, each delay is multiplied by a random factor between 0.5 and 1 so that many clients
+    /// do not retry at the same time. Default: true.
+    pub jitter: bool,
+    /// Error codes returned by Decthings which should be retried. Default: "too_many_requests" and
+    /// "server_overloaded".
+    pub retryable_codes: Vec<String>,
+    /// If true, requests which fail before a response is received (DecthingsClientError::Http,
+    /// DecthingsClientError::WebSocketConnect and so on) are retried. Default: true.
+    pub retry_transport_errors: bool,
+    /// If true, methods which are not idempotent, such as "Model"/"train" or
+    /// "Model"/"createModel", are also retried. Default: false. To allow this for some requests
+    /// only, use `with_retry_policy` on the API, such as `ModelRpc::with_retry_policy`.
+    pub retry_non_idempotent: bool,
+}
+
+impl Default for RetryPolicy {
+    fn default() -> Self {
+        Self {
+            max_attempts: 3,
+            initial_backoff: Duration::from_millis(200),
+            max_backoff: Duration::from_secs(10),
+            jitter: true,
+            retryable_codes: vec![
+                "too_many_requests".to_string(),
+                "server_overloaded".to_string(),
+            ],
+            retry_transport_errors: true,
+            retry_non_idempotent: false,
+        }
+    }
+}
+
+impl RetryPolicy {
+    pub(crate) fn applies_to(&self, api: &str, method: &str) -> bool {
+        self.max_attempts > 1
+            && (self.retry_non_idempotent || !NON_IDEMPOTENT_METHODS.contains(&(api, method)))
+    }
+
+    /// `res` is the JSON part of the response, or the error.
+    pub(crate) fn should_retry(&self, res: Result<&[u8], &DecthingsClientError>) -> bool {
+        match res {
+            Ok(response) => self.should_retry_response(response),
+            Err(e) => self.should_retry_error(e),
+        }
+    }
+
+    /// Whether a response with the given JSON part should be retried.
+    fn should_retry_response(&self, response: &[u8]) -> bool {
+        super::protocol::peek_error_code(response)
+            .is_some_and(|error_code| self.retryable_codes.iter().any(|code| *code == error_code))
+    }
+
+    fn should_retry_error(&self, error: &DecthingsClientError) -> bool {
+        match error {
+            // The same response would be received again.
+            DecthingsClientError::ParseResponseFailed(_)
+            | DecthingsClientError::InvalidMessage
+            | DecthingsClientError::InvalidFrame(_)
+            | DecthingsClientError::Decompress(_) => false,
+            _ => self.retry_transport_errors,
+        }
+    }
+
+    /// Delay to wait after the given attempt (starting at 1) failed.
+    pub(crate) fn backoff(&self, attempt: u32) -> Duration {
+        let delay = self
+            .initial_backoff
+            .saturating_mul(2u32.saturating_pow(attempt.saturating_sub(1)))
+            .min(self.max_backoff);
+        if self.jitter {
+            let random = std::collections::hash_map::RandomState::new()
+                .build_hasher()
+                .finish();
+            delay.mul_f64(0.5 + (random as f64 / u64::MAX as f64) / 2.0)
+        } else {
+            delay
+        }
+    }
+}
+
+#[test]
+fn test_should_retry() {
+    let policy = RetryPolicy {
+        jitter: false,
+        ..Default::default()
+    };
+    let response = |json: &'static str| Ok(json.as_bytes());
+
+    assert!(policy.should_retry(response(r#"{"error":{"code":"too_many_requests"}}"#)));
+    assert!(policy.should_retry(response(r#"{"error":{"code":"server_overloaded"}}"#)));
+    assert!(!policy.should_retry(response(r#"{"error":{"code":"model_not_found"}}"#)));
+    assert!(!policy.should_retry(response(r#"{"result":{}}"#)));
+    assert!(!policy.should_retry(Err(&DecthingsClientError::InvalidMessage)));
+
+    assert!(policy.applies_to("Model", "getModels"));
+    assert!(!policy.applies_to("Model", "train"));
+    assert!(!policy.applies_to("FS", "write"));
+    assert!(policy.applies_to("Model", "evaluate"));
+
+    assert_eq!(policy.backoff(1), Duration::from_millis(200));
+    assert_eq!(policy.backoff(3), Duration::from_millis(800));
+    assert_eq!(policy.backoff(20), Duration::from_secs(10));
+}
+
+#[cfg(all(feature = "mock", feature = "model"))]
+#[tokio::test]
+async fn test_retry_with_mock() {
+    use crate::mock::{MockResponse, MockServer};
+    use std::sync::{
+        Arc,
+        atomic::{AtomicU32, Ordering},
+    };
+
+    let server = MockServer::start().await.unwrap();
+    let calls = Arc::new(AtomicU32::new(0));
+    let calls_clone = Arc::clone(&calls);
+    server
+        .handle("Model", "getModels", move |_| {
+            if calls_clone.fetch_add(1, Ordering::Relaxed) < 2 {
+                MockResponse::error_code("too_many_requests")
+            } else {
+                MockResponse::result(
+                    serde_json::json!({ "models": [], "total": 0, "offset": 0, "limit": 0 }),
+                )
+            }
+        })
+        .await;
+
+    let client_options = crate::client::DecthingsClientOptions {
+        retry_policy: Some(RetryPolicy {
+            initial_backoff: Duration::from_millis(1),
+            ..Default::default()
+        }),
+        ..server.client_options()
+    };
+    let client = crate::client::DecthingsClient::new(client_options.clone());
+
+    let params = || crate::client::rpc::model::GetModelsParams::<&str> {
+        offset: None,
+        limit: None,
+        filter: None,
+        sort: None,
+        sort_direction: None,
+    };
+    client.model.get_models(params()).await.unwrap();
+    assert_eq!(calls.load(Ordering::Relaxed), 3);
+
+    // Retries can be disabled for a single API.
+    calls.store(0, Ordering::Relaxed);
+    let err = client
+        .model
+        .with_retry_policy(None)
+        .get_models(params())
+        .await
+        .unwrap_err();
+    assert_eq!(err.code(), Some("too_many_requests"));
+    assert_eq!(calls.load(Ordering::Relaxed), 1);
+
+    // A response which exceeds the decode limits would be received again, so it is not retried.
+    let client = crate::client::DecthingsClient::new(crate::client::DecthingsClientOptions {
+        decode_limits: crate::client::DecodeLimits {
+            max_blob_size: Some(2),
+            ..Default::default()
+        },
+        ..client_options
+    });
+    calls.store(2, Ordering::Relaxed);
+    let err = client.model.get_models(params()).await.unwrap_err();
+    assert!(matches!(
+        err,
+        crate::client::DecthingsRpcError::Request(DecthingsClientError::InvalidFrame(_))
+    ));
+    assert!(!err.is_retryable());
+    assert_eq!(calls.load(Ordering::Relaxed), 3);
+}
@@ -15,8 +15,8 @@ pub use response::*;
 /// serialized using *.serialize()* on the DecthingsTensor. The data is serialized in order by
 /// sorted key, and grouped by element. That is, one element for each key is serialized and added
 /// after each other, before moving on to the next element. Each element is returned as a separate
-/// vec.
-fn serialize_add_dataset_data(data: &[DataToAddForKey<'_>]) -> Result<Vec<Vec<u8>>, String> {
+/// blob.
+fn serialize_add_dataset_data(data: &[DataToAddForKey<'_>]) -> Result<Vec<bytes::Bytes>, String> {
     if data.is_empty() {
         return Err(
             "Failed to serialize data: Got zero keys, but a dataset always has at least one key."
@@ -28,11 +28,11 @@ fn serialize_add_dataset_data(data: &[DataToAddForKey<'_>]) -> Result<Vec<Vec<u8
     for x in data.iter() {
         if x.data.len() != num_entries {
             return Err(format!(
-                    "Failed to serialize data: All keys must contain the same amount of data. Key {} had {num_entries} elements, but key {} had {} elements.",
-                    data[0].key,
-                    x.key,
-                    x.data.len()
-                ));
+                "Failed to serialize data: All keys must contain the same amount of data. Key {} had {num_entries} elements, but key {} had {} elements.",
+                data[0].key,
+                x.key,
+                x.data.len()
+            ));
         }
     }
 
@@ -54,7 +54,7 @@ fn serialize_add_dataset_data(data: &[DataToAddForKey<'_>]) -> Result<Vec<Vec<u8
     for i in 0..num_entries {
         for &key in &sorted_keys {
             let element = &data.iter().find(|x| x.key == key).unwrap().data[i];
-            res.push(element.serialize());
+            res.push(element.serialize().into());
         }
     }
 
@@ -70,18 +70,43 @@ impl DatasetRpc {
         Self { rpc }
     }
 
+    /// Returns a copy of this API which uses the given timeout for each request, instead of
+    /// `DecthingsClientOptions::timeout`. Pass None to wait without a timeout.
+    pub fn with_timeout(&self, timeout: Option<std::time::Duration>) -> Self {
+        Self {
+            rpc: self.rpc.with_timeout(timeout),
+        }
+    }
+
+    /// Returns a copy of this API which retries requests according to the given policy, instead
+    /// of `DecthingsClientOptions::retry_policy`. Pass None to never retry.
+    pub fn with_retry_policy(&self, retry_policy: Option<crate::client::RetryPolicy>) -> Self {
+        Self {
+            rpc: self.rpc.with_retry_policy(retry_policy),
+        }
+    }
+
+    /// Returns a copy of this API which sends requests using the given protocol, instead of
+    /// `DecthingsClientOptions::default_protocol`. Methods which subscribe to events always use
+    /// WebSocket.
+    pub fn with_protocol(&self, protocol: crate::client::RpcProtocol) -> Self {
+        Self {
+            rpc: self.rpc.with_protocol(protocol),
+        }
+    }
+
     pub async fn create_dataset(
         &self,
         params: CreateDatasetParams<'_>,
     ) -> Result<CreateDatasetResult, crate::client::DecthingsRpcError<CreateDatasetError>> {
         let (tx, rx) = tokio::sync::oneshot::channel();
         self.rpc
-            .raw_method_call::<_, _, &[u8]>(
+            .raw_method_call(
                 "Dataset",
                 "createDataset",
                 params,
-                &[],
-                crate::client::RpcProtocol::Http,
+                vec![],
+                self.rpc.protocol(),
                 |x| {
                     tx.send(x).ok();
                     StateModification::empty()
@@ -107,12 +132,12 @@ impl DatasetRpc {
     ) -> Result<UpdateDatasetResult, crate::client::DecthingsRpcError<UpdateDatasetError>> {
         let (tx, rx) = tokio::sync::oneshot::channel();
         self.rpc
-            .raw_method_call::<_, _, &[u8]>(
+            .raw_method_call(
                 "Dataset",
                 "updateDataset",
                 params,
-                &[],
-                crate::client::RpcProtocol::Http,
+                vec![],
+                self.rpc.protocol(),
                 |x| {
                     tx.send(x).ok();
                     StateModification::empty()
@@ -138,12 +163,12 @@ impl DatasetRpc {
     ) -> Result<DeleteDatasetResult, crate::client::DecthingsRpcError<DeleteDatasetError>> {
         let (tx, rx) = tokio::sync::oneshot::channel();
         self.rpc
-            .raw_method_call::<_, _, &[u8]>(
+            .raw_method_call(
                 "Dataset",
                 "deleteDataset",
                 params,
-                &[],
-                crate::client::RpcProtocol::Http,
+                vec![],
+                self.rpc.protocol(),
                 |x| {
                     tx.send(x).ok();
                     StateModification::empty()
@@ -169,12 +194,12 @@ impl DatasetRpc {
     ) -> Result<GetDatasetsResult, crate::client::DecthingsRpcError<GetDatasetsError>> {
         let (tx, rx) = tokio::sync::oneshot::channel();
         self.rpc
-            .raw_method_call::<_, _, &[u8]>(
+            .raw_method_call(
                 "Dataset",
                 "getDatasets",
                 params,
-                &[],
-                crate::client::RpcProtocol::Http,
+                vec![],
+                self.rpc.protocol(),
                 |x| {
                     tx.send(x).ok();
                     StateModification::empty()
@@ -211,7 +236,7 @@ impl DatasetRpc {
                 "addEntries",
                 params,
                 serialized,
-                crate::client::RpcProtocol::Http,
+                self.rpc.protocol(),
                 |x| {
                     tx.send(x).ok();
                     StateModification::empty()
@@ -251,7 +276,7 @@ impl DatasetRpc {
                 "addEntriesToNeedsReview",
                 params,
                 serialized,
-                crate::client::RpcProtocol::Http,
+                self.rpc.protocol(),
                 |x| {
                     tx.send(x).ok();
                     StateModification::empty()
@@ -297,7 +322,7 @@ impl DatasetRpc {
                         "The number of indexes to remove must equal the number of elements to add. Attempted to remove {} indexes and add {} elements.",
                         params.indexes.len(),
                         params.keys[0].data.len()
-                    )
+                    ),
                 },
             ));
         }
@@ -307,7 +332,7 @@ impl DatasetRpc {
                 "finalizeNeedsReviewEntries",
                 params,
                 serialized,
-                crate::client::RpcProtocol::Http,
+                self.rpc.protocol(),
                 |x| {
                     tx.send(x).ok();
                     StateModification::empty()
@@ -335,12 +360,12 @@ impl DatasetRpc {
     ) -> Result<GetEntriesResult, crate::client::DecthingsRpcError<GetEntriesError>> {
         let (tx, rx) = tokio::sync::oneshot::channel();
         self.rpc
-            .raw_method_call::<_, _, &[u8]>(
+            .raw_method_call(
                 "Dataset",
                 "getEntries",
                 params,
-                &[],
-                crate::client::RpcProtocol::Http,
+                vec![],
+                self.rpc.protocol(),
                 |x| {
                     tx.send(x).ok();
                     StateModification::empty()
@@ -386,6 +411,28 @@ impl DatasetRpc {
             })
     }
 
+    /// Like `get_entries`, but the entries are read while they are downloaded instead of being
+    /// kept in memory.
+    pub async fn get_entries_streamed(
+        &self,
+        params: GetEntriesParams<'_>,
+    ) -> Result<GetEntriesStreamedResult, crate::client::DecthingsRpcError<GetEntriesError>> {
+        let (response, blobs) = self
+            .rpc
+            .raw_method_call_streamed("Dataset", "getEntries", params, vec![])
+            .await?;
+        let res: super::Response<InnerGetEntriesResult, GetEntriesError> =
+            serde_json::from_slice(&response)?;
+        match res {
+            super::Response::Result(val) => Ok(GetEntriesStreamedResult {
+                keys: val.keys,
+                indexes: val.indexes,
+                data: blobs,
+            }),
+            super::Response::Error(val) => Err(crate::client::DecthingsRpcError::Rpc(val)),
+        }
+    }
+
     pub async fn get_needs_review_entries(
         &self,
         params: GetNeedsReviewEntriesParams<'_>,
@@ -395,12 +442,12 @@ impl DatasetRpc {
     > {
         let (tx, rx) = tokio::sync::oneshot::channel();
         self.rpc
-            .raw_method_call::<_, _, &[u8]>(
+            .raw_method_call(
                 "Dataset",
                 "getNeedsReviewEntries",
                 params,
-                &[],
-                crate::client::RpcProtocol::Http,
+                vec![],
+                self.rpc.protocol(),
                 |x| {
                     tx.send(x).ok();
                     StateModification::empty()
@@ -453,12 +500,12 @@ impl DatasetRpc {
     ) -> Result<RemoveEntriesResult, crate::client::DecthingsRpcError<RemoveEntriesError>> {
         let (tx, rx) = tokio::sync::oneshot::channel();
         self.rpc
-            .raw_method_call::<_, _, &[u8]>(
+            .raw_method_call(
                 "Dataset",
                 "removeEntries",
                 params,
-                &[],
-                crate::client::RpcProtocol::Http,
+                vec![],
+                self.rpc.protocol(),
                 |x| {
                     tx.send(x).ok();
                     StateModification::empty()
@@ -487,12 +534,12 @@ impl DatasetRpc {
     > {
         let (tx, rx) = tokio::sync::oneshot::channel();
         self.rpc
-            .raw_method_call::<_, _, &[u8]>(
+            .raw_method_call(
                 "Dataset",
                 "removeNeedsReviewEntries",
                 params,
-                &[],
-                crate::client::RpcProtocol::Http,
+                vec![],
+                self.rpc.protocol(),
                 |x| {
                     tx.send(x).ok();
                     StateModification::empty()
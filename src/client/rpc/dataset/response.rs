@@ -14,7 +14,7 @@ pub struct CreateDatasetResult {
 }
 
 #[derive(Debug, Clone, Deserialize)]
-#[serde(rename_all = "snake_case", tag = "code")]
+#[serde(rename_all = "snake_case", tag = "code", remote = "Self")]
 pub enum CreateDatasetError {
     NameAlreadyUsed,
     OrganizationNotFound,
@@ -29,14 +29,31 @@ pub enum CreateDatasetError {
         parameter_name: String,
         reason: String,
     },
+    /// An error code which is not known by this version of the client.
+    #[serde(skip)]
+    Unrecognized {
+        raw: serde_json::Value,
+    },
 }
 
+impl_decthings_error_code!(CreateDatasetError {
+    NameAlreadyUsed => "name_already_used", "name already used";
+    OrganizationNotFound => "organization_not_found", "organization not found";
+    AccessDenied => "access_denied", "access denied";
+    QuotaExceeded => "quota_exceeded", "quota exceeded";
+    BadCredentials => "bad_credentials", "invalid API key";
+    TooManyRequests => "too_many_requests", "too many requests";
+    PaymentRequired => "payment_required", "payment required";
+    Unknown => "unknown", "unknown error";
+    InvalidParameter { parameter_name, reason } => "invalid_parameter", "parameter '{parameter_name}' invalid: {reason}";
+});
+
 #[derive(Debug, Clone, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct UpdateDatasetResult {}
 
 #[derive(Debug, Clone, Deserialize)]
-#[serde(rename_all = "snake_case", tag = "code")]
+#[serde(rename_all = "snake_case", tag = "code", remote = "Self")]
 pub enum UpdateDatasetError {
     DatasetNotFound,
     AccessDenied,
@@ -50,14 +67,30 @@ pub enum UpdateDatasetError {
         parameter_name: String,
         reason: String,
     },
+    /// An error code which is not known by this version of the client.
+    #[serde(skip)]
+    Unrecognized {
+        raw: serde_json::Value,
+    },
 }
 
+impl_decthings_error_code!(UpdateDatasetError {
+    DatasetNotFound => "dataset_not_found", "dataset not found";
+    AccessDenied => "access_denied", "access denied";
+    NameAlreadyUsed => "name_already_used", "name already used";
+    BadCredentials => "bad_credentials", "invalid API key";
+    TooManyRequests => "too_many_requests", "too many requests";
+    PaymentRequired => "payment_required", "payment required";
+    Unknown => "unknown", "unknown error";
+    InvalidParameter { parameter_name, reason } => "invalid_parameter", "parameter '{parameter_name}' invalid: {reason}";
+});
+
 #[derive(Debug, Clone, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct DeleteDatasetResult {}
 
 #[derive(Debug, Clone, Deserialize)]
-#[serde(rename_all = "snake_case", tag = "code")]
+#[serde(rename_all = "snake_case", tag = "code", remote = "Self")]
 pub enum DeleteDatasetError {
     DatasetNotFound,
     AccessDenied,
@@ -70,10 +103,25 @@ pub enum DeleteDatasetError {
         parameter_name: String,
         reason: String,
     },
+    /// An error code which is not known by this version of the client.
+    #[serde(skip)]
+    Unrecognized {
+        raw: serde_json::Value,
+    },
 }
 
+impl_decthings_error_code!(DeleteDatasetError {
+    DatasetNotFound => "dataset_not_found", "dataset not found";
+    AccessDenied => "access_denied", "access denied";
+    BadCredentials => "bad_credentials", "invalid API key";
+    TooManyRequests => "too_many_requests", "too many requests";
+    PaymentRequired => "payment_required", "payment required";
+    Unknown => "unknown", "unknown error";
+    InvalidParameter { parameter_name, reason } => "invalid_parameter", "parameter '{parameter_name}' invalid: {reason}";
+});
+
 #[derive(Debug, Clone, Deserialize)]
-#[serde(rename_all = "camelCase", tag = "type")]
+#[serde(rename_all = "camelCase", tag = "type", remote = "Self")]
 pub enum DatasetOwner {
     #[serde(rename_all = "camelCase")]
     User { user_id: String, username: String },
@@ -82,15 +130,27 @@ pub enum DatasetOwner {
         organization_id: String,
         organization_name: String,
     },
+    /// An owner which is not known by this version of the client.
+    #[serde(skip)]
+    Unrecognized { raw: serde_json::Value },
 }
 
+impl_deserialize_unrecognized!(DatasetOwner, "type", ["user", "organization"]);
+
 #[derive(Debug, Clone, Deserialize)]
-#[serde(rename_all = "camelCase")]
+#[serde(rename_all = "camelCase", remote = "Self")]
 pub enum DatasetAccess {
     Read,
     Readwrite,
+    /// An access level which is not known by this version of the client.
+    #[serde(skip)]
+    Unrecognized {
+        raw: serde_json::Value,
+    },
 }
 
+impl_deserialize_unrecognized!(DatasetAccess, ["read", "readwrite"]);
+
 #[derive(Debug, Clone, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct DatasetEntries {
@@ -129,7 +189,7 @@ pub struct GetDatasetsResult {
 }
 
 #[derive(Debug, Clone, Deserialize)]
-#[serde(rename_all = "snake_case", tag = "code")]
+#[serde(rename_all = "snake_case", tag = "code", remote = "Self")]
 pub enum GetDatasetsError {
     BadCredentials,
     TooManyRequests,
@@ -140,8 +200,21 @@ pub enum GetDatasetsError {
         parameter_name: String,
         reason: String,
     },
+    /// An error code which is not known by this version of the client.
+    #[serde(skip)]
+    Unrecognized {
+        raw: serde_json::Value,
+    },
 }
 
+impl_decthings_error_code!(GetDatasetsError {
+    BadCredentials => "bad_credentials", "invalid API key";
+    TooManyRequests => "too_many_requests", "too many requests";
+    PaymentRequired => "payment_required", "payment required";
+    Unknown => "unknown", "unknown error";
+    InvalidParameter { parameter_name, reason } => "invalid_parameter", "parameter '{parameter_name}' invalid: {reason}";
+});
+
 #[derive(Debug, Clone, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct AddEntriesResult {
@@ -150,7 +223,7 @@ pub struct AddEntriesResult {
 }
 
 #[derive(Debug, Clone, Deserialize)]
-#[serde(rename_all = "snake_case", tag = "code")]
+#[serde(rename_all = "snake_case", tag = "code", remote = "Self")]
 pub enum AddEntriesError {
     DatasetNotFound,
     AccessDenied,
@@ -170,8 +243,26 @@ pub enum AddEntriesError {
         parameter_name: String,
         reason: String,
     },
+    /// An error code which is not known by this version of the client.
+    #[serde(skip)]
+    Unrecognized {
+        raw: serde_json::Value,
+    },
 }
 
+impl_decthings_error_code!(AddEntriesError {
+    DatasetNotFound => "dataset_not_found", "dataset not found";
+    AccessDenied => "access_denied", "access denied";
+    LimitExceeded => "limit_exceeded", "limit exceeded";
+    QuotaExceeded => "quota_exceeded", "quota exceeded";
+    IncorrectVersionId { dataset_version_id } => "incorrect_version_id", "incorrect dataset version id, the current version is '{dataset_version_id}'";
+    BadCredentials => "bad_credentials", "invalid API key";
+    TooManyRequests => "too_many_requests", "too many requests";
+    PaymentRequired => "payment_required", "payment required";
+    Unknown => "unknown", "unknown error";
+    InvalidParameter { parameter_name, reason } => "invalid_parameter", "parameter '{parameter_name}' invalid: {reason}";
+});
+
 #[derive(Debug, Clone, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct AddEntriesToNeedsReviewResult {
@@ -180,7 +271,7 @@ pub struct AddEntriesToNeedsReviewResult {
 }
 
 #[derive(Debug, Clone, Deserialize)]
-#[serde(rename_all = "snake_case", tag = "code")]
+#[serde(rename_all = "snake_case", tag = "code", remote = "Self")]
 pub enum AddEntriesToNeedsReviewError {
     DatasetNotFound,
     AccessDenied,
@@ -200,8 +291,26 @@ pub enum AddEntriesToNeedsReviewError {
         parameter_name: String,
         reason: String,
     },
+    /// An error code which is not known by this version of the client.
+    #[serde(skip)]
+    Unrecognized {
+        raw: serde_json::Value,
+    },
 }
 
+impl_decthings_error_code!(AddEntriesToNeedsReviewError {
+    DatasetNotFound => "dataset_not_found", "dataset not found";
+    AccessDenied => "access_denied", "access denied";
+    LimitExceeded => "limit_exceeded", "limit exceeded";
+    QuotaExceeded => "quota_exceeded", "quota exceeded";
+    IncorrectVersionId { dataset_version_id } => "incorrect_version_id", "incorrect dataset version id, the current version is '{dataset_version_id}'";
+    BadCredentials => "bad_credentials", "invalid API key";
+    TooManyRequests => "too_many_requests", "too many requests";
+    PaymentRequired => "payment_required", "payment required";
+    Unknown => "unknown", "unknown error";
+    InvalidParameter { parameter_name, reason } => "invalid_parameter", "parameter '{parameter_name}' invalid: {reason}";
+});
+
 #[derive(Debug, Clone, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct FinalizeNeedsReviewEntriesResult {
@@ -212,7 +321,7 @@ pub struct FinalizeNeedsReviewEntriesResult {
 }
 
 #[derive(Debug, Clone, Deserialize)]
-#[serde(rename_all = "snake_case", tag = "code")]
+#[serde(rename_all = "snake_case", tag = "code", remote = "Self")]
 pub enum FinalizeNeedsReviewEntriesError {
     DatasetNotFound,
     IndexOutOfRange,
@@ -232,8 +341,26 @@ pub enum FinalizeNeedsReviewEntriesError {
         parameter_name: String,
         reason: String,
     },
+    /// An error code which is not known by this version of the client.
+    #[serde(skip)]
+    Unrecognized {
+        raw: serde_json::Value,
+    },
 }
 
+impl_decthings_error_code!(FinalizeNeedsReviewEntriesError {
+    DatasetNotFound => "dataset_not_found", "dataset not found";
+    IndexOutOfRange => "index_out_of_range", "index out of range";
+    AccessDenied => "access_denied", "access denied";
+    QuotaExceeded => "quota_exceeded", "quota exceeded";
+    IncorrectVersionId { dataset_version_id } => "incorrect_version_id", "incorrect dataset version id, the current version is '{dataset_version_id}'";
+    BadCredentials => "bad_credentials", "invalid API key";
+    TooManyRequests => "too_many_requests", "too many requests";
+    PaymentRequired => "payment_required", "payment required";
+    Unknown => "unknown", "unknown error";
+    InvalidParameter { parameter_name, reason } => "invalid_parameter", "parameter '{parameter_name}' invalid: {reason}";
+});
+
 #[derive(Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub(super) struct InnerGetEntriesResult {
@@ -258,8 +385,17 @@ pub struct GetEntriesResult {
     pub keys: Vec<KeyData>,
 }
 
+#[derive(Debug)]
+pub struct GetEntriesStreamedResult {
+    pub keys: Vec<String>,
+    pub indexes: Vec<u32>,
+    /// For each index in `indexes`, one blob for each key in `keys`. Each blob is a serialized
+    /// tensor, which can be parsed using `OwnedDecthingsTensor::from_bytes`.
+    pub data: crate::client::ResponseBlobs,
+}
+
 #[derive(Debug, Clone, Deserialize)]
-#[serde(rename_all = "snake_case", tag = "code")]
+#[serde(rename_all = "snake_case", tag = "code", remote = "Self")]
 pub enum GetEntriesError {
     DatasetNotFound,
     #[serde(rename_all = "camelCase")]
@@ -276,8 +412,23 @@ pub enum GetEntriesError {
         parameter_name: String,
         reason: String,
     },
+    /// An error code which is not known by this version of the client.
+    #[serde(skip)]
+    Unrecognized {
+        raw: serde_json::Value,
+    },
 }
 
+impl_decthings_error_code!(GetEntriesError {
+    DatasetNotFound => "dataset_not_found", "dataset not found";
+    IncorrectVersionId { dataset_version_id } => "incorrect_version_id", "incorrect dataset version id, the current version is '{dataset_version_id}'";
+    BadCredentials => "bad_credentials", "invalid API key";
+    TooManyRequests => "too_many_requests", "too many requests";
+    PaymentRequired => "payment_required", "payment required";
+    Unknown => "unknown", "unknown error";
+    InvalidParameter { parameter_name, reason } => "invalid_parameter", "parameter '{parameter_name}' invalid: {reason}";
+});
+
 #[derive(Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub(super) struct InnerGetNeedsReviewEntriesResult {
@@ -291,7 +442,7 @@ pub struct GetNeedsReviewEntriesResult {
 }
 
 #[derive(Debug, Clone, Deserialize)]
-#[serde(rename_all = "snake_case", tag = "code")]
+#[serde(rename_all = "snake_case", tag = "code", remote = "Self")]
 pub enum GetNeedsReviewEntriesError {
     DatasetNotFound,
     #[serde(rename_all = "camelCase")]
@@ -308,8 +459,23 @@ pub enum GetNeedsReviewEntriesError {
         parameter_name: String,
         reason: String,
     },
+    /// An error code which is not known by this version of the client.
+    #[serde(skip)]
+    Unrecognized {
+        raw: serde_json::Value,
+    },
 }
 
+impl_decthings_error_code!(GetNeedsReviewEntriesError {
+    DatasetNotFound => "dataset_not_found", "dataset not found";
+    IncorrectVersionId { dataset_version_id } => "incorrect_version_id", "incorrect dataset version id, the current version is '{dataset_version_id}'";
+    BadCredentials => "bad_credentials", "invalid API key";
+    TooManyRequests => "too_many_requests", "too many requests";
+    PaymentRequired => "payment_required", "payment required";
+    Unknown => "unknown", "unknown error";
+    InvalidParameter { parameter_name, reason } => "invalid_parameter", "parameter '{parameter_name}' invalid: {reason}";
+});
+
 #[derive(Debug, Clone, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct RemoveEntriesResult {
@@ -321,7 +487,7 @@ pub struct RemoveEntriesResult {
 }
 
 #[derive(Debug, Clone, Deserialize)]
-#[serde(rename_all = "snake_case", tag = "code")]
+#[serde(rename_all = "snake_case", tag = "code", remote = "Self")]
 pub enum RemoveEntriesError {
     DatasetNotFound,
     IndexOutOfRange,
@@ -340,8 +506,25 @@ pub enum RemoveEntriesError {
         parameter_name: String,
         reason: String,
     },
+    /// An error code which is not known by this version of the client.
+    #[serde(skip)]
+    Unrecognized {
+        raw: serde_json::Value,
+    },
 }
 
+impl_decthings_error_code!(RemoveEntriesError {
+    DatasetNotFound => "dataset_not_found", "dataset not found";
+    IndexOutOfRange => "index_out_of_range", "index out of range";
+    AccessDenied => "access_denied", "access denied";
+    IncorrectVersionId { dataset_version_id } => "incorrect_version_id", "incorrect dataset version id, the current version is '{dataset_version_id}'";
+    BadCredentials => "bad_credentials", "invalid API key";
+    TooManyRequests => "too_many_requests", "too many requests";
+    PaymentRequired => "payment_required", "payment required";
+    Unknown => "unknown", "unknown error";
+    InvalidParameter { parameter_name, reason } => "invalid_parameter", "parameter '{parameter_name}' invalid: {reason}";
+});
+
 #[derive(Debug, Clone, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct RemoveNeedsReviewEntriesResult {
@@ -351,7 +534,7 @@ pub struct RemoveNeedsReviewEntriesResult {
 }
 
 #[derive(Debug, Clone, Deserialize)]
-#[serde(rename_all = "snake_case", tag = "code")]
+#[serde(rename_all = "snake_case", tag = "code", remote = "Self")]
 pub enum RemoveNeedsReviewEntriesError {
     DatasetNotFound,
     IndexOutOfRange,
@@ -370,4 +553,21 @@ pub enum RemoveNeedsReviewEntriesError {
         parameter_name: String,
         reason: String,
     },
+    /// An error code which is not known by this version of the client.
+    #[serde(skip)]
+    Unrecognized {
+        raw: serde_json::Value,
+    },
 }
+
+impl_decthings_error_code!(RemoveNeedsReviewEntriesError {
+    DatasetNotFound => "dataset_not_found", "dataset not found";
+    IndexOutOfRange => "index_out_of_range", "index out of range";
+    AccessDenied => "access_denied", "access denied";
+    IncorrectVersionId { dataset_version_id } => "incorrect_version_id", "incorrect dataset version id, the current version is '{dataset_version_id}'";
+    BadCredentials => "bad_credentials", "invalid API key";
+    TooManyRequests => "too_many_requests", "too many requests";
+    PaymentRequired => "payment_required", "payment required";
+    Unknown => "unknown", "unknown error";
+    InvalidParameter { parameter_name, reason } => "invalid_parameter", "parameter '{parameter_name}' invalid: {reason}";
+});
@@ -15,6 +15,31 @@ impl DebugRpc {
         Self { rpc }
     }
 
+    /// Returns a copy of this API which uses the given timeout for each request, instead of
+    /// `DecthingsClientOptions::timeout`. Pass None to wait without a timeout.
+    pub fn with_timeout(&self, timeout: Option<std::time::Duration>) -> Self {
+        Self {
+            rpc: self.rpc.with_timeout(timeout),
+        }
+    }
+
+    /// Returns a copy of this API which retries requests according to the given policy, instead
+    /// of `DecthingsClientOptions::retry_policy`. Pass None to never retry.
+    pub fn with_retry_policy(&self, retry_policy: Option<crate::client::RetryPolicy>) -> Self {
+        Self {
+            rpc: self.rpc.with_retry_policy(retry_policy),
+        }
+    }
+
+    /// Returns a copy of this API which sends requests using the given protocol, instead of
+    /// `DecthingsClientOptions::default_protocol`. Methods which subscribe to events always use
+    /// WebSocket.
+    pub fn with_protocol(&self, protocol: crate::client::RpcProtocol) -> Self {
+        Self {
+            rpc: self.rpc.with_protocol(protocol),
+        }
+    }
+
     pub async fn launch_debug_session(
         &self,
         params: LaunchDebugSessionParams<'_>,
@@ -27,19 +52,19 @@ impl DebugRpc {
         let protocol = if subscribe_to_events {
             crate::client::RpcProtocol::Ws
         } else {
-            crate::client::RpcProtocol::Http
+            self.rpc.protocol()
         };
 
         #[cfg(not(feature = "events"))]
-        let protocol = crate::client::RpcProtocol::Http;
+        let protocol = self.rpc.protocol();
 
         let (tx, rx) = tokio::sync::oneshot::channel();
         self.rpc
-            .raw_method_call::<_, _, &[u8]>(
+            .raw_method_call(
                 "Debug",
                 "launchDebugSession",
                 params,
-                &[],
+                vec![],
                 protocol,
                 move |x| {
                     match x {
@@ -61,7 +86,7 @@ impl DebugRpc {
                                     #[cfg(feature = "events")]
                                     if subscribe_to_events {
                                         return StateModification {
-                                            add_events: vec![debug_session_id],
+                                            add_events: vec![("Debug", debug_session_id)],
                                             remove_events: vec![],
                                         };
                                     }
@@ -93,12 +118,12 @@ impl DebugRpc {
     {
         let (tx, rx) = tokio::sync::oneshot::channel();
         self.rpc
-            .raw_method_call::<_, _, &[u8]>(
+            .raw_method_call(
                 "Debug",
                 "getDebugSessions",
                 params,
-                &[],
-                crate::client::RpcProtocol::Http,
+                vec![],
+                self.rpc.protocol(),
                 |x| {
                     tx.send(x).ok();
                     StateModification::empty()
@@ -132,12 +157,12 @@ impl DebugRpc {
 
         let (tx, rx) = tokio::sync::oneshot::channel();
         self.rpc
-            .raw_method_call::<_, _, &[u8]>(
+            .raw_method_call(
                 "Debug",
                 "terminateDebugSession",
                 params,
-                &[],
-                crate::client::RpcProtocol::Http,
+                vec![],
+                self.rpc.protocol(),
                 move |x| {
                     match x {
                         Ok(val) => {
@@ -193,7 +218,7 @@ impl DebugRpc {
                 "callInitializeWeights",
                 params,
                 serialized,
-                crate::client::RpcProtocol::Http,
+                self.rpc.protocol(),
                 |x| {
                     tx.send(x).ok();
                     StateModification::empty()
@@ -217,7 +242,7 @@ impl DebugRpc {
 
     pub async fn call_instantiate_model(
         &self,
-        params: CallInstantiateModelParams<'_, impl AsRef<[u8]>>,
+        params: CallInstantiateModelParams<'_, impl Clone + Into<bytes::Bytes>>,
     ) -> Result<
         CallInstantiateModelResult,
         crate::client::DecthingsRpcError<CallInstantiateModelError>,
@@ -232,8 +257,8 @@ impl DebugRpc {
                 "Debug",
                 "callInstantiateModel",
                 params,
-                serialized.iter().map(|x| &x.data).collect::<Vec<_>>(),
-                crate::client::RpcProtocol::Http,
+                serialized.iter().map(|x| x.data.clone().into()).collect(),
+                self.rpc.protocol(),
                 |x| {
                     tx.send(x).ok();
                     StateModification::empty()
@@ -267,7 +292,7 @@ impl DebugRpc {
                 "callTrain",
                 params,
                 serialized,
-                crate::client::RpcProtocol::Http,
+                self.rpc.protocol(),
                 |x| {
                     tx.send(x).ok();
                     StateModification::empty()
@@ -296,12 +321,12 @@ impl DebugRpc {
     > {
         let (tx, rx) = tokio::sync::oneshot::channel();
         self.rpc
-            .raw_method_call::<_, _, &[u8]>(
+            .raw_method_call(
                 "Debug",
                 "getTrainingStatus",
                 params,
-                &[],
-                crate::client::RpcProtocol::Http,
+                vec![],
+                self.rpc.protocol(),
                 |x| {
                     tx.send(x).ok();
                     StateModification::empty()
@@ -332,12 +357,12 @@ impl DebugRpc {
     > {
         let (tx, rx) = tokio::sync::oneshot::channel();
         self.rpc
-            .raw_method_call::<_, _, &[u8]>(
+            .raw_method_call(
                 "Debug",
                 "getTrainingMetrics",
                 params,
-                &[],
-                crate::client::RpcProtocol::Http,
+                vec![],
+                self.rpc.protocol(),
                 |x| {
                     tx.send(x).ok();
                     StateModification::empty()
@@ -379,12 +404,12 @@ impl DebugRpc {
     > {
         let (tx, rx) = tokio::sync::oneshot::channel();
         self.rpc
-            .raw_method_call::<_, _, &[u8]>(
+            .raw_method_call(
                 "Debug",
                 "cancelTrainingSession",
                 params,
-                &[],
-                crate::client::RpcProtocol::Http,
+                vec![],
+                self.rpc.protocol(),
                 |x| {
                     tx.send(x).ok();
                     StateModification::empty()
@@ -418,7 +443,7 @@ impl DebugRpc {
                 "callEvaluate",
                 params,
                 serialized,
-                crate::client::RpcProtocol::Http,
+                self.rpc.protocol(),
                 |x| {
                     tx.send(x).ok();
                     StateModification::empty()
@@ -455,12 +480,12 @@ impl DebugRpc {
     ) -> Result<CallGetWeightsResult, crate::client::DecthingsRpcError<CallGetWeightsError>> {
         let (tx, rx) = tokio::sync::oneshot::channel();
         self.rpc
-            .raw_method_call::<_, _, &[u8]>(
+            .raw_method_call(
                 "Debug",
                 "callGetWeights",
                 params,
-                &[],
-                crate::client::RpcProtocol::Http,
+                vec![],
+                self.rpc.protocol(),
                 |x| {
                     tx.send(x).ok();
                     StateModification::empty()
@@ -489,12 +514,12 @@ impl DebugRpc {
     {
         let (tx, rx) = tokio::sync::oneshot::channel();
         self.rpc
-            .raw_method_call::<_, _, &[u8]>(
+            .raw_method_call(
                 "Debug",
                 "downloadWeightData",
                 params,
-                &[],
-                crate::client::RpcProtocol::Http,
+                vec![],
+                self.rpc.protocol(),
                 |x| {
                     tx.send(x).ok();
                     StateModification::empty()
@@ -523,21 +548,53 @@ impl DebugRpc {
             })
     }
 
+    /// Like `download_weight_data`, but the weight data is read while it is downloaded instead of
+    /// being kept in memory.
+    pub async fn download_weight_data_streamed(
+        &self,
+        params: DownloadWeightDataParams<'_, impl AsRef<str>>,
+    ) -> Result<
+        DownloadWeightDataStreamedResult,
+        crate::client::DecthingsRpcError<DownloadWeightDataError>,
+    > {
+        let (response, blobs) = self
+            .rpc
+            .raw_method_call_streamed("Debug", "downloadWeightData", params, vec![])
+            .await?;
+        let res: super::Response<DownloadWeightDataResult, DownloadWeightDataError> =
+            serde_json::from_slice(&response)?;
+        match res {
+            super::Response::Result(val) => Ok(DownloadWeightDataStreamedResult {
+                keys: val.data.into_iter().map(|x| x.key).collect(),
+                data: blobs,
+            }),
+            super::Response::Error(val) => Err(crate::client::DecthingsRpcError::Rpc(val)),
+        }
+    }
+
     pub async fn send_to_remote_inspector(
         &self,
-        params: SendToRemoteInspectorParams<'_, impl AsRef<[u8]>>,
+        params: SendToRemoteInspectorParams<'_, impl Into<bytes::Bytes>>,
     ) -> Result<
         SendToRemoteInspectorResult,
         crate::client::DecthingsRpcError<SendToRemoteInspectorError>,
     > {
+        let SendToRemoteInspectorParams {
+            debug_session_id,
+            data,
+        } = params;
+        let params = SendToRemoteInspectorParams {
+            debug_session_id,
+            data: bytes::Bytes::new(),
+        };
         let (tx, rx) = tokio::sync::oneshot::channel();
         self.rpc
             .raw_method_call(
                 "Debug",
                 "sendToRemoteInspector",
                 &params,
-                [&params.data],
-                crate::client::RpcProtocol::Http,
+                vec![data.into()],
+                self.rpc.protocol(),
                 |x| {
                     tx.send(x).ok();
                     StateModification::empty()
@@ -559,6 +616,20 @@ impl DebugRpc {
             })
     }
 
+    /// Returns a stream of the events of the debug session `debug_session_id`, which ends after the Exit
+    /// event. See [`EventStream`](crate::client::event::EventStream).
+    ///
+    /// Only events received after this call are included. To not miss any, pass
+    /// `subscribe_to_events: Some(false)` to `launch_debug_session`, create the stream and then call
+    /// `subscribe_to_events`.
+    #[cfg(feature = "events")]
+    pub fn events(
+        &self,
+        debug_session_id: impl Into<String>,
+    ) -> crate::client::event::EventStream<DebugEvent> {
+        self.rpc.event_stream(debug_session_id.into())
+    }
+
     #[cfg(feature = "events")]
     pub async fn subscribe_to_events(
         &self,
@@ -570,11 +641,11 @@ impl DebugRpc {
         let (tx, rx) = tokio::sync::oneshot::channel();
         let debug_session_id_owned = params.debug_session_id.to_owned();
         self.rpc
-            .raw_method_call::<_, _, &[u8]>(
+            .raw_method_call(
                 "Debug",
                 "subscribeToEvents",
                 params,
-                &[],
+                vec![],
                 crate::client::RpcProtocol::Ws,
                 move |x| {
                     match x {
@@ -590,7 +661,7 @@ impl DebugRpc {
                                 Ok(super::Response::Result(val)) => {
                                     tx.send(Ok(val)).ok();
                                     return StateModification {
-                                        add_events: vec![debug_session_id_owned],
+                                        add_events: vec![("Debug", debug_session_id_owned)],
                                         remove_events: vec![],
                                     };
                                 }
@@ -626,11 +697,11 @@ impl DebugRpc {
         let debug_session_id_owned = params.debug_session_id.to_owned();
         let did_call = self
             .rpc
-            .raw_method_call::<_, _, &[u8]>(
+            .raw_method_call(
                 "Debug",
                 "unsubscribeFromEvents",
                 params,
-                &[],
+                vec![],
                 crate::client::RpcProtocol::WsIfAvailableOtherwiseNone,
                 move |x| {
                     match x {
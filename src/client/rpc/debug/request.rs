@@ -1,6 +1,6 @@
 use serde::Serialize;
 
-use crate::client::{rpc::ExecutionLocationProvider, DecthingsParameterProvider};
+use crate::client::{DecthingsParameterProvider, rpc::ExecutionLocationProvider};
 
 #[derive(Debug, Clone, Serialize)]
 #[serde(rename_all = "camelCase")]
@@ -57,14 +57,15 @@ pub struct CallInitializeWeightsParams<'a> {
 }
 
 #[derive(Debug, Clone)]
-pub struct WeightKeyData<'a, D: AsRef<[u8]>> {
+pub struct WeightKeyData<'a, D: Clone + Into<bytes::Bytes>> {
     pub key: &'a str,
+    /// The data is cloned before it is sent, so pass `bytes::Bytes` to avoid copying it.
     pub data: D,
 }
 
 #[derive(Debug, Clone, Serialize)]
 #[serde(rename_all = "camelCase", tag = "type")]
-pub enum WeightDataProvider<'a, D: AsRef<[u8]>> {
+pub enum WeightDataProvider<'a, D: Clone + Into<bytes::Bytes>> {
     #[serde(rename_all = "camelCase")]
     Data {
         #[serde(skip_serializing)]
@@ -77,7 +78,7 @@ pub enum WeightDataProvider<'a, D: AsRef<[u8]>> {
 #[derive(Debug, Clone, Serialize)]
 #[serde(rename_all = "camelCase")]
 #[serde(bound(serialize = ""))]
-pub struct CallInstantiateModelParams<'a, D: AsRef<[u8]>> {
+pub struct CallInstantiateModelParams<'a, D: Clone + Into<bytes::Bytes>> {
     /// The debug session's id.
     pub debug_session_id: &'a str,
     /// Data to use as weights.
@@ -167,9 +168,10 @@ pub struct DownloadWeightDataParams<'a, S: AsRef<str>> {
 
 #[derive(Debug, Clone, Serialize)]
 #[serde(rename_all = "camelCase")]
-pub struct SendToRemoteInspectorParams<'a, T: AsRef<[u8]>> {
+pub struct SendToRemoteInspectorParams<'a, T: Into<bytes::Bytes>> {
     /// The debug session's id.
     pub debug_session_id: &'a str,
+    /// Data to write. Pass `bytes::Bytes` to avoid copying it.
     #[serde(skip_serializing)]
     pub data: T,
 }
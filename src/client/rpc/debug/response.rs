@@ -1,13 +1,13 @@
 use crate::{
-    client::rpc::{ExecutionLocation, ParameterDefinitions, WeightKeyData},
     client::DecthingsParameter,
+    client::rpc::{ExecutionLocation, ParameterDefinitions, WeightKeyData},
     tensor::OwnedDecthingsTensor,
 };
 use serde::Deserialize;
 
 #[cfg(feature = "events")]
 #[derive(Debug, Clone, Deserialize)]
-#[serde(rename_all = "snake_case", tag = "code")]
+#[serde(rename_all = "snake_case", tag = "code", remote = "Self")]
 pub enum DebugSessionTerminatedReason {
     TerminatedOnRequest,
     LauncherTerminated,
@@ -23,11 +23,35 @@ pub enum DebugSessionTerminatedReason {
     Exception {
         exception_details: Option<String>,
     },
+    /// A reason which is not known by this version of the client.
+    #[serde(skip)]
+    Unrecognized {
+        raw: serde_json::Value,
+    },
 }
 
+#[cfg(feature = "events")]
+impl_deserialize_unrecognized!(
+    DebugSessionTerminatedReason,
+    "code",
+    [
+        "terminated_on_request",
+        "launcher_terminated",
+        "inactive_timeout",
+        "unknown",
+        "code_terminated",
+        "exception"
+    ]
+);
+
 #[cfg(feature = "events")]
 #[derive(Debug, Clone, Deserialize)]
-#[serde(rename_all = "camelCase", tag = "event", content = "params")]
+#[serde(
+    rename_all = "camelCase",
+    tag = "event",
+    content = "params",
+    remote = "Self"
+)]
 pub enum DebugEvent {
     #[serde(rename_all = "camelCase")]
     Exit {
@@ -54,8 +78,24 @@ pub enum DebugEvent {
         #[serde(skip_deserializing)]
         data: bytes::Bytes,
     },
+    /// An event which is not known by this version of the client.
+    #[serde(skip)]
+    Unrecognized { raw: serde_json::Value },
 }
 
+#[cfg(feature = "events")]
+impl_deserialize_unrecognized!(
+    DebugEvent,
+    "event",
+    [
+        "exit",
+        "stdout",
+        "stderr",
+        "initialized",
+        "remoteInspectorData"
+    ]
+);
+
 #[derive(Debug, Clone, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct LaunchDebugSessionResult {
@@ -64,7 +104,7 @@ pub struct LaunchDebugSessionResult {
 }
 
 #[derive(Debug, Clone, Deserialize)]
-#[serde(rename_all = "snake_case", tag = "code")]
+#[serde(rename_all = "snake_case", tag = "code", remote = "Self")]
 pub enum LaunchDebugSessionError {
     ModelNotFound,
     InvalidExecutorType,
@@ -82,17 +122,47 @@ pub enum LaunchDebugSessionError {
         parameter_name: String,
         reason: String,
     },
+    /// An error code which is not known by this version of the client.
+    #[serde(skip)]
+    Unrecognized {
+        raw: serde_json::Value,
+    },
 }
 
+impl_decthings_error_code!(LaunchDebugSessionError {
+    ModelNotFound => "model_not_found", "model not found";
+    InvalidExecutorType => "invalid_executor_type", "invalid executor type";
+    PersistentLauncherNotFound => "persistent_launcher_not_found", "persistent launcher not found";
+    QuotaExceeded => "quota_exceeded", "quota exceeded";
+    ServerOverloaded => "server_overloaded", "server overloaded";
+    InvalidExecutable => "invalid_executable", "invalid executable";
+    ReadExecutableFileFailed => "read_executable_file_failed", "failed to read executable file";
+    BadCredentials => "bad_credentials", "invalid API key";
+    TooManyRequests => "too_many_requests", "too many requests";
+    PaymentRequired => "payment_required", "payment required";
+    Unknown => "unknown", "unknown error";
+    InvalidParameter { parameter_name, reason } => "invalid_parameter", "parameter '{parameter_name}' invalid: {reason}";
+});
+
 #[derive(Debug, Clone, Deserialize)]
-#[serde(rename_all = "camelCase")]
+#[serde(rename_all = "camelCase", remote = "Self")]
 pub enum DebugTrainingSessionState {
     Starting,
     Running,
     Completed,
     Failed,
+    /// A state which is not known by this version of the client.
+    #[serde(skip)]
+    Unrecognized {
+        raw: serde_json::Value,
+    },
 }
 
+impl_deserialize_unrecognized!(
+    DebugTrainingSessionState,
+    ["starting", "running", "completed", "failed"]
+);
+
 #[derive(Debug, Clone, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct DebugTrainingSession {
@@ -119,7 +189,7 @@ pub struct GetDebugSessionsResult {
 }
 
 #[derive(Debug, Clone, Deserialize)]
-#[serde(rename_all = "snake_case", tag = "code")]
+#[serde(rename_all = "snake_case", tag = "code", remote = "Self")]
 pub enum GetDebugSessionsError {
     BadCredentials,
     TooManyRequests,
@@ -130,14 +200,27 @@ pub enum GetDebugSessionsError {
         parameter_name: String,
         reason: String,
     },
+    /// An error code which is not known by this version of the client.
+    #[serde(skip)]
+    Unrecognized {
+        raw: serde_json::Value,
+    },
 }
 
+impl_decthings_error_code!(GetDebugSessionsError {
+    BadCredentials => "bad_credentials", "invalid API key";
+    TooManyRequests => "too_many_requests", "too many requests";
+    PaymentRequired => "payment_required", "payment required";
+    Unknown => "unknown", "unknown error";
+    InvalidParameter { parameter_name, reason } => "invalid_parameter", "parameter '{parameter_name}' invalid: {reason}";
+});
+
 #[derive(Debug, Clone, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct TerminateDebugSessionResult {}
 
 #[derive(Debug, Clone, Deserialize)]
-#[serde(rename_all = "snake_case", tag = "code")]
+#[serde(rename_all = "snake_case", tag = "code", remote = "Self")]
 pub enum TerminateDebugSessionError {
     DebugSessionNotFound,
     BadCredentials,
@@ -149,8 +232,22 @@ pub enum TerminateDebugSessionError {
         parameter_name: String,
         reason: String,
     },
+    /// An error code which is not known by this version of the client.
+    #[serde(skip)]
+    Unrecognized {
+        raw: serde_json::Value,
+    },
 }
 
+impl_decthings_error_code!(TerminateDebugSessionError {
+    DebugSessionNotFound => "debug_session_not_found", "debug session not found";
+    BadCredentials => "bad_credentials", "invalid API key";
+    TooManyRequests => "too_many_requests", "too many requests";
+    PaymentRequired => "payment_required", "payment required";
+    Unknown => "unknown", "unknown error";
+    InvalidParameter { parameter_name, reason } => "invalid_parameter", "parameter '{parameter_name}' invalid: {reason}";
+});
+
 #[derive(Debug, Clone, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct WeightKey {
@@ -166,7 +263,7 @@ pub struct CallInitializeWeightsResult {
 }
 
 #[derive(Debug, Clone, Deserialize)]
-#[serde(rename_all = "snake_case", tag = "code")]
+#[serde(rename_all = "snake_case", tag = "code", remote = "Self")]
 pub enum CallInitializeWeightsError {
     DebugSessionNotFound,
     DebugSessionTerminated,
@@ -192,8 +289,26 @@ pub enum CallInitializeWeightsError {
         parameter_name: String,
         reason: String,
     },
+    /// An error code which is not known by this version of the client.
+    #[serde(skip)]
+    Unrecognized {
+        raw: serde_json::Value,
+    },
 }
 
+impl_decthings_error_code!(CallInitializeWeightsError {
+    DebugSessionNotFound => "debug_session_not_found", "debug session not found";
+    DebugSessionTerminated => "debug_session_terminated", "debug session has terminated";
+    DatasetNotFound { dataset_id } => "dataset_not_found", "dataset '{dataset_id}' not found";
+    DatasetKeyNotFound { dataset_id, dataset_key } => "dataset_key_not_found", "key '{dataset_key}' not found in dataset '{dataset_id}'";
+    Exception { exception_details } => "exception", "exception thrown in model code: {}", exception_details.as_deref().unwrap_or("no details");
+    BadCredentials => "bad_credentials", "invalid API key";
+    TooManyRequests => "too_many_requests", "too many requests";
+    PaymentRequired => "payment_required", "payment required";
+    Unknown => "unknown", "unknown error";
+    InvalidParameter { parameter_name, reason } => "invalid_parameter", "parameter '{parameter_name}' invalid: {reason}";
+});
+
 #[derive(Debug, Clone, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct CallInstantiateModelResult {
@@ -202,7 +317,7 @@ pub struct CallInstantiateModelResult {
 }
 
 #[derive(Debug, Clone, Deserialize)]
-#[serde(rename_all = "snake_case", tag = "code")]
+#[serde(rename_all = "snake_case", tag = "code", remote = "Self")]
 pub enum CallInstantiateModelError {
     DebugSessionNotFound,
     DebugSessionTerminated,
@@ -220,8 +335,25 @@ pub enum CallInstantiateModelError {
         parameter_name: String,
         reason: String,
     },
+    /// An error code which is not known by this version of the client.
+    #[serde(skip)]
+    Unrecognized {
+        raw: serde_json::Value,
+    },
 }
 
+impl_decthings_error_code!(CallInstantiateModelError {
+    DebugSessionNotFound => "debug_session_not_found", "debug session not found";
+    DebugSessionTerminated => "debug_session_terminated", "debug session has terminated";
+    WeightDataNotFound => "weight_data_not_found", "weight data not found";
+    Exception { exception_details } => "exception", "exception thrown in model code: {}", exception_details.as_deref().unwrap_or("no details");
+    BadCredentials => "bad_credentials", "invalid API key";
+    TooManyRequests => "too_many_requests", "too many requests";
+    PaymentRequired => "payment_required", "payment required";
+    Unknown => "unknown", "unknown error";
+    InvalidParameter { parameter_name, reason } => "invalid_parameter", "parameter '{parameter_name}' invalid: {reason}";
+});
+
 #[derive(Debug, Clone, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct CallTrainResult {
@@ -230,7 +362,7 @@ pub struct CallTrainResult {
 }
 
 #[derive(Debug, Clone, Deserialize)]
-#[serde(rename_all = "snake_case", tag = "code")]
+#[serde(rename_all = "snake_case", tag = "code", remote = "Self")]
 pub enum CallTrainError {
     DebugSessionNotFound,
     InstantiatedModelNotFound,
@@ -257,8 +389,27 @@ pub enum CallTrainError {
         parameter_name: String,
         reason: String,
     },
+    /// An error code which is not known by this version of the client.
+    #[serde(skip)]
+    Unrecognized {
+        raw: serde_json::Value,
+    },
 }
 
+impl_decthings_error_code!(CallTrainError {
+    DebugSessionNotFound => "debug_session_not_found", "debug session not found";
+    InstantiatedModelNotFound => "instantiated_model_not_found", "instantiated model not found";
+    DebugSessionTerminated => "debug_session_terminated", "debug session has terminated";
+    DatasetNotFound { dataset_id } => "dataset_not_found", "dataset '{dataset_id}' not found";
+    DatasetKeyNotFound { dataset_id, dataset_key } => "dataset_key_not_found", "key '{dataset_key}' not found in dataset '{dataset_id}'";
+    Exception { exception_details } => "exception", "exception thrown in model code: {}", exception_details.as_deref().unwrap_or("no details");
+    BadCredentials => "bad_credentials", "invalid API key";
+    TooManyRequests => "too_many_requests", "too many requests";
+    PaymentRequired => "payment_required", "payment required";
+    Unknown => "unknown", "unknown error";
+    InvalidParameter { parameter_name, reason } => "invalid_parameter", "parameter '{parameter_name}' invalid: {reason}";
+});
+
 #[derive(Debug, Clone, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct DebugTrainingSessionMetrics {
@@ -267,7 +418,7 @@ pub struct DebugTrainingSessionMetrics {
 }
 
 #[derive(Debug, Clone, Deserialize)]
-#[serde(rename_all = "snake_case", tag = "code")]
+#[serde(rename_all = "snake_case", tag = "code", remote = "Self")]
 pub enum DebugTrainingSessionFailReason {
     Unknown,
     Cancelled,
@@ -276,10 +427,21 @@ pub enum DebugTrainingSessionFailReason {
     Exception {
         exception_details: Option<String>,
     },
+    /// A code which is not known by this version of the client.
+    #[serde(skip)]
+    Unrecognized {
+        raw: serde_json::Value,
+    },
 }
 
+impl_deserialize_unrecognized!(
+    DebugTrainingSessionFailReason,
+    "code",
+    ["unknown", "cancelled", "max_duration_exceeded", "exception"]
+);
+
 #[derive(Debug, Clone, Deserialize)]
-#[serde(rename_all = "camelCase", tag = "state")]
+#[serde(rename_all = "camelCase", tag = "state", remote = "Self")]
 pub enum DebugTrainingSessionStatus {
     #[serde(rename_all = "camelCase")]
     Running { progress: f32 },
@@ -294,8 +456,17 @@ pub enum DebugTrainingSessionStatus {
         train_duration: Option<u64>,
         fail_reason: DebugTrainingSessionFailReason,
     },
+    /// A state which is not known by this version of the client.
+    #[serde(skip)]
+    Unrecognized { raw: serde_json::Value },
 }
 
+impl_deserialize_unrecognized!(
+    DebugTrainingSessionStatus,
+    "state",
+    ["running", "completed", "failed"]
+);
+
 #[derive(Debug, Clone, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct DebugGetTrainingStatusResult {
@@ -306,7 +477,7 @@ pub struct DebugGetTrainingStatusResult {
 }
 
 #[derive(Debug, Clone, Deserialize)]
-#[serde(rename_all = "snake_case", tag = "code")]
+#[serde(rename_all = "snake_case", tag = "code", remote = "Self")]
 pub enum DebugGetTrainingStatusError {
     DebugSessionNotFound,
     TrainingSessionNotFound,
@@ -319,8 +490,23 @@ pub enum DebugGetTrainingStatusError {
         parameter_name: String,
         reason: String,
     },
+    /// An error code which is not known by this version of the client.
+    #[serde(skip)]
+    Unrecognized {
+        raw: serde_json::Value,
+    },
 }
 
+impl_decthings_error_code!(DebugGetTrainingStatusError {
+    DebugSessionNotFound => "debug_session_not_found", "debug session not found";
+    TrainingSessionNotFound => "training_session_not_found", "training session not found";
+    BadCredentials => "bad_credentials", "invalid API key";
+    TooManyRequests => "too_many_requests", "too many requests";
+    PaymentRequired => "payment_required", "payment required";
+    Unknown => "unknown", "unknown error";
+    InvalidParameter { parameter_name, reason } => "invalid_parameter", "parameter '{parameter_name}' invalid: {reason}";
+});
+
 #[derive(Debug, Clone, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct DebugFetchedTrainingMetric {
@@ -344,7 +530,7 @@ pub struct DebugGetTrainingMetricsResult {
 }
 
 #[derive(Debug, Clone, Deserialize)]
-#[serde(rename_all = "snake_case", tag = "code")]
+#[serde(rename_all = "snake_case", tag = "code", remote = "Self")]
 pub enum DebugGetTrainingMetricsError {
     DebugSessionNotFound,
     TrainingSessionNotFound,
@@ -357,14 +543,29 @@ pub enum DebugGetTrainingMetricsError {
         parameter_name: String,
         reason: String,
     },
+    /// An error code which is not known by this version of the client.
+    #[serde(skip)]
+    Unrecognized {
+        raw: serde_json::Value,
+    },
 }
 
+impl_decthings_error_code!(DebugGetTrainingMetricsError {
+    DebugSessionNotFound => "debug_session_not_found", "debug session not found";
+    TrainingSessionNotFound => "training_session_not_found", "training session not found";
+    BadCredentials => "bad_credentials", "invalid API key";
+    TooManyRequests => "too_many_requests", "too many requests";
+    PaymentRequired => "payment_required", "payment required";
+    Unknown => "unknown", "unknown error";
+    InvalidParameter { parameter_name, reason } => "invalid_parameter", "parameter '{parameter_name}' invalid: {reason}";
+});
+
 #[derive(Debug, Clone, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct DebugCancelTrainingSessionResult {}
 
 #[derive(Debug, Clone, Deserialize)]
-#[serde(rename_all = "snake_case", tag = "code")]
+#[serde(rename_all = "snake_case", tag = "code", remote = "Self")]
 pub enum DebugCancelTrainingSessionError {
     DebugSessionNotFound,
     TrainingSessionNotFound,
@@ -378,8 +579,24 @@ pub enum DebugCancelTrainingSessionError {
         parameter_name: String,
         reason: String,
     },
+    /// An error code which is not known by this version of the client.
+    #[serde(skip)]
+    Unrecognized {
+        raw: serde_json::Value,
+    },
 }
 
+impl_decthings_error_code!(DebugCancelTrainingSessionError {
+    DebugSessionNotFound => "debug_session_not_found", "debug session not found";
+    TrainingSessionNotFound => "training_session_not_found", "training session not found";
+    TrainingSessionNotRunning => "training_session_not_running", "training session is not running";
+    BadCredentials => "bad_credentials", "invalid API key";
+    TooManyRequests => "too_many_requests", "too many requests";
+    PaymentRequired => "payment_required", "payment required";
+    Unknown => "unknown", "unknown error";
+    InvalidParameter { parameter_name, reason } => "invalid_parameter", "parameter '{parameter_name}' invalid: {reason}";
+});
+
 #[derive(Debug, Clone, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct CallEvaluateResult {
@@ -387,7 +604,7 @@ pub struct CallEvaluateResult {
 }
 
 #[derive(Debug, Clone, Deserialize)]
-#[serde(rename_all = "snake_case", tag = "code")]
+#[serde(rename_all = "snake_case", tag = "code", remote = "Self")]
 pub enum CallEvaluateError {
     DebugSessionNotFound,
     InstantiatedModelNotFound,
@@ -414,8 +631,27 @@ pub enum CallEvaluateError {
         parameter_name: String,
         reason: String,
     },
+    /// An error code which is not known by this version of the client.
+    #[serde(skip)]
+    Unrecognized {
+        raw: serde_json::Value,
+    },
 }
 
+impl_decthings_error_code!(CallEvaluateError {
+    DebugSessionNotFound => "debug_session_not_found", "debug session not found";
+    InstantiatedModelNotFound => "instantiated_model_not_found", "instantiated model not found";
+    DebugSessionTerminated => "debug_session_terminated", "debug session has terminated";
+    DatasetNotFound { dataset_id } => "dataset_not_found", "dataset '{dataset_id}' not found";
+    DatasetKeyNotFound { dataset_id, dataset_key } => "dataset_key_not_found", "key '{dataset_key}' not found in dataset '{dataset_id}'";
+    Exception { exception_details } => "exception", "exception thrown in model code: {}", exception_details.as_deref().unwrap_or("no details");
+    BadCredentials => "bad_credentials", "invalid API key";
+    TooManyRequests => "too_many_requests", "too many requests";
+    PaymentRequired => "payment_required", "payment required";
+    Unknown => "unknown", "unknown error";
+    InvalidParameter { parameter_name, reason } => "invalid_parameter", "parameter '{parameter_name}' invalid: {reason}";
+});
+
 #[derive(Debug, Clone, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct CallGetWeightsResult {
@@ -424,7 +660,7 @@ pub struct CallGetWeightsResult {
 }
 
 #[derive(Debug, Clone, Deserialize)]
-#[serde(rename_all = "snake_case", tag = "code")]
+#[serde(rename_all = "snake_case", tag = "code", remote = "Self")]
 pub enum CallGetWeightsError {
     DebugSessionNotFound,
     InstantiatedModelNotFound,
@@ -442,8 +678,25 @@ pub enum CallGetWeightsError {
         parameter_name: String,
         reason: String,
     },
+    /// An error code which is not known by this version of the client.
+    #[serde(skip)]
+    Unrecognized {
+        raw: serde_json::Value,
+    },
 }
 
+impl_decthings_error_code!(CallGetWeightsError {
+    DebugSessionNotFound => "debug_session_not_found", "debug session not found";
+    InstantiatedModelNotFound => "instantiated_model_not_found", "instantiated model not found";
+    DebugSessionTerminated => "debug_session_terminated", "debug session has terminated";
+    Exception { exception_details } => "exception", "exception thrown in model code: {}", exception_details.as_deref().unwrap_or("no details");
+    BadCredentials => "bad_credentials", "invalid API key";
+    TooManyRequests => "too_many_requests", "too many requests";
+    PaymentRequired => "payment_required", "payment required";
+    Unknown => "unknown", "unknown error";
+    InvalidParameter { parameter_name, reason } => "invalid_parameter", "parameter '{parameter_name}' invalid: {reason}";
+});
+
 #[derive(Debug, Clone, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct DownloadWeightDataResult {
@@ -451,8 +704,15 @@ pub struct DownloadWeightDataResult {
     pub data: Vec<WeightKeyData>,
 }
 
+#[derive(Debug)]
+pub struct DownloadWeightDataStreamedResult {
+    /// The weight keys. `data` contains one blob for each key, in the same order.
+    pub keys: Vec<String>,
+    pub data: crate::client::ResponseBlobs,
+}
+
 #[derive(Debug, Clone, Deserialize)]
-#[serde(rename_all = "snake_case", tag = "code")]
+#[serde(rename_all = "snake_case", tag = "code", remote = "Self")]
 pub enum DownloadWeightDataError {
     DebugSessionNotFound,
     WeightDataNotFound,
@@ -466,14 +726,30 @@ pub enum DownloadWeightDataError {
         parameter_name: String,
         reason: String,
     },
+    /// An error code which is not known by this version of the client.
+    #[serde(skip)]
+    Unrecognized {
+        raw: serde_json::Value,
+    },
 }
 
+impl_decthings_error_code!(DownloadWeightDataError {
+    DebugSessionNotFound => "debug_session_not_found", "debug session not found";
+    WeightDataNotFound => "weight_data_not_found", "weight data not found";
+    WeightKeyNotFound => "weight_key_not_found", "weight key not found";
+    BadCredentials => "bad_credentials", "invalid API key";
+    TooManyRequests => "too_many_requests", "too many requests";
+    PaymentRequired => "payment_required", "payment required";
+    Unknown => "unknown", "unknown error";
+    InvalidParameter { parameter_name, reason } => "invalid_parameter", "parameter '{parameter_name}' invalid: {reason}";
+});
+
 #[derive(Debug, Clone, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct SendToRemoteInspectorResult {}
 
 #[derive(Debug, Clone, Deserialize)]
-#[serde(rename_all = "snake_case", tag = "code")]
+#[serde(rename_all = "snake_case", tag = "code", remote = "Self")]
 pub enum SendToRemoteInspectorError {
     DebugSessionNotFound,
     NotRemoteInspector,
@@ -486,8 +762,23 @@ pub enum SendToRemoteInspectorError {
         parameter_name: String,
         reason: String,
     },
+    /// An error code which is not known by this version of the client.
+    #[serde(skip)]
+    Unrecognized {
+        raw: serde_json::Value,
+    },
 }
 
+impl_decthings_error_code!(SendToRemoteInspectorError {
+    DebugSessionNotFound => "debug_session_not_found", "debug session not found";
+    NotRemoteInspector => "not_remote_inspector", "debug session was not started with a remote inspector";
+    BadCredentials => "bad_credentials", "invalid API key";
+    TooManyRequests => "too_many_requests", "too many requests";
+    PaymentRequired => "payment_required", "payment required";
+    Unknown => "unknown", "unknown error";
+    InvalidParameter { parameter_name, reason } => "invalid_parameter", "parameter '{parameter_name}' invalid: {reason}";
+});
+
 #[cfg(feature = "events")]
 #[derive(Debug, Clone, Deserialize)]
 #[serde(rename_all = "camelCase")]
@@ -495,7 +786,7 @@ pub struct DebugSubscribeToEventsResult {}
 
 #[cfg(feature = "events")]
 #[derive(Debug, Clone, Deserialize)]
-#[serde(rename_all = "snake_case", tag = "code")]
+#[serde(rename_all = "snake_case", tag = "code", remote = "Self")]
 pub enum DebugSubscribeToEventsError {
     DebugSessionNotFound,
     BadCredentials,
@@ -507,8 +798,23 @@ pub enum DebugSubscribeToEventsError {
         parameter_name: String,
         reason: String,
     },
+    /// An error code which is not known by this version of the client.
+    #[serde(skip)]
+    Unrecognized {
+        raw: serde_json::Value,
+    },
 }
 
+#[cfg(feature = "events")]
+impl_decthings_error_code!(DebugSubscribeToEventsError {
+    DebugSessionNotFound => "debug_session_not_found", "debug session not found";
+    BadCredentials => "bad_credentials", "invalid API key";
+    TooManyRequests => "too_many_requests", "too many requests";
+    PaymentRequired => "payment_required", "payment required";
+    Unknown => "unknown", "unknown error";
+    InvalidParameter { parameter_name, reason } => "invalid_parameter", "parameter '{parameter_name}' invalid: {reason}";
+});
+
 #[cfg(feature = "events")]
 #[derive(Debug, Clone, Deserialize)]
 #[serde(rename_all = "camelCase")]
@@ -516,7 +822,7 @@ pub struct DebugUnsubscribeFromEventsResult {}
 
 #[cfg(feature = "events")]
 #[derive(Debug, Clone, Deserialize)]
-#[serde(rename_all = "snake_case", tag = "code")]
+#[serde(rename_all = "snake_case", tag = "code", remote = "Self")]
 pub enum DebugUnsubscribeFromEventsError {
     NotSubscribed,
     TooManyRequests,
@@ -526,4 +832,17 @@ pub enum DebugUnsubscribeFromEventsError {
         parameter_name: String,
         reason: String,
     },
+    /// An error code which is not known by this version of the client.
+    #[serde(skip)]
+    Unrecognized {
+        raw: serde_json::Value,
+    },
 }
+
+#[cfg(feature = "events")]
+impl_decthings_error_code!(DebugUnsubscribeFromEventsError {
+    NotSubscribed => "not_subscribed", "not subscribed";
+    TooManyRequests => "too_many_requests", "too many requests";
+    Unknown => "unknown", "unknown error";
+    InvalidParameter { parameter_name, reason } => "invalid_parameter", "parameter '{parameter_name}' invalid: {reason}";
+});
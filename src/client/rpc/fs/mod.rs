@@ -6,7 +6,6 @@ use crate::client::StateModification;
 pub use request::*;
 pub use response::*;
 
-
 pub struct FsRpc {
     rpc: crate::client::DecthingsClientRpc,
 }
@@ -16,23 +15,41 @@ impl FsRpc {
         Self { rpc }
     }
 
+    /// Returns a copy of this API which uses the given timeout for each request, instead of
+    /// `DecthingsClientOptions::timeout`. Pass None to wait without a timeout.
+    pub fn with_timeout(&self, timeout: Option<std::time::Duration>) -> Self {
+        Self {
+            rpc: self.rpc.with_timeout(timeout),
+        }
+    }
+
+    /// Returns a copy of this API which retries requests according to the given policy, instead
+    /// of `DecthingsClientOptions::retry_policy`. Pass None to never retry.
+    pub fn with_retry_policy(&self, retry_policy: Option<crate::client::RetryPolicy>) -> Self {
+        Self {
+            rpc: self.rpc.with_retry_policy(retry_policy),
+        }
+    }
+
+    /// Returns a copy of this API which sends requests using the given protocol, instead of
+    /// `DecthingsClientOptions::default_protocol`. Methods which subscribe to events always use
+    /// WebSocket.
+    pub fn with_protocol(&self, protocol: crate::client::RpcProtocol) -> Self {
+        Self {
+            rpc: self.rpc.with_protocol(protocol),
+        }
+    }
+
     pub async fn lookup(
         &self,
         params: LookupParams<'_, impl AsRef<[u8]>>,
     ) -> Result<LookupResult, crate::client::DecthingsRpcError<LookupError>> {
         let (tx, rx) = tokio::sync::oneshot::channel();
         self.rpc
-            .raw_method_call::<_, _, &[u8]>(
-                "FS",
-                "lookup",
-                params,
-                &[],
-                crate::client::RpcProtocol::Http,
-                |x| {
-                    tx.send(x).ok();
-                    StateModification::empty()
-                },
-            )
+            .raw_method_call("FS", "lookup", params, vec![], self.rpc.protocol(), |x| {
+                tx.send(x).ok();
+                StateModification::empty()
+            })
             .await;
         rx.await
             .unwrap()
@@ -52,17 +69,10 @@ impl FsRpc {
     ) -> Result<GetattrResult, crate::client::DecthingsRpcError<GetattrError>> {
         let (tx, rx) = tokio::sync::oneshot::channel();
         self.rpc
-            .raw_method_call::<_, _, &[u8]>(
-                "FS",
-                "getattr",
-                params,
-                &[],
-                crate::client::RpcProtocol::Http,
-                |x| {
-                    tx.send(x).ok();
-                    StateModification::empty()
-                },
-            )
+            .raw_method_call("FS", "getattr", params, vec![], self.rpc.protocol(), |x| {
+                tx.send(x).ok();
+                StateModification::empty()
+            })
             .await;
         rx.await
             .unwrap()
@@ -83,17 +93,10 @@ impl FsRpc {
     ) -> Result<SetattrResult, crate::client::DecthingsRpcError<SetattrError>> {
         let (tx, rx) = tokio::sync::oneshot::channel();
         self.rpc
-            .raw_method_call::<_, _, &[u8]>(
-                "FS",
-                "setattr",
-                params,
-                &[],
-                crate::client::RpcProtocol::Http,
-                |x| {
-                    tx.send(x).ok();
-                    StateModification::empty()
-                },
-            )
+            .raw_method_call("FS", "setattr", params, vec![], self.rpc.protocol(), |x| {
+                tx.send(x).ok();
+                StateModification::empty()
+            })
             .await;
         rx.await
             .unwrap()
@@ -114,17 +117,10 @@ impl FsRpc {
     ) -> Result<MknodResult, crate::client::DecthingsRpcError<MknodError>> {
         let (tx, rx) = tokio::sync::oneshot::channel();
         self.rpc
-            .raw_method_call::<_, _, &[u8]>(
-                "FS",
-                "mknod",
-                params,
-                &[],
-                crate::client::RpcProtocol::Http,
-                |x| {
-                    tx.send(x).ok();
-                    StateModification::empty()
-                },
-            )
+            .raw_method_call("FS", "mknod", params, vec![], self.rpc.protocol(), |x| {
+                tx.send(x).ok();
+                StateModification::empty()
+            })
             .await;
         rx.await
             .unwrap()
@@ -144,17 +140,10 @@ impl FsRpc {
     ) -> Result<ReadResult, crate::client::DecthingsRpcError<ReadError>> {
         let (tx, rx) = tokio::sync::oneshot::channel();
         self.rpc
-            .raw_method_call::<_, _, &[u8]>(
-                "FS",
-                "read",
-                params,
-                &[],
-                crate::client::RpcProtocol::Http,
-                |x| {
-                    tx.send(x).ok();
-                    StateModification::empty()
-                },
-            )
+            .raw_method_call("FS", "read", params, vec![], self.rpc.protocol(), |x| {
+                tx.send(x).ok();
+                StateModification::empty()
+            })
             .await;
         rx.await
             .unwrap()
@@ -177,16 +166,30 @@ impl FsRpc {
 
     pub async fn write(
         &self,
-        params: WriteParams<'_, impl AsRef<[u8]>>,
+        params: WriteParams<'_, impl Into<bytes::Bytes>>,
     ) -> Result<WriteResult, crate::client::DecthingsRpcError<WriteError>> {
+        let WriteParams {
+            model_id,
+            inode,
+            offset,
+            truncate,
+            data,
+        } = params;
+        let params = WriteParams {
+            model_id,
+            inode,
+            offset,
+            truncate,
+            data: bytes::Bytes::new(),
+        };
         let (tx, rx) = tokio::sync::oneshot::channel();
         self.rpc
             .raw_method_call(
                 "FS",
                 "write",
                 &params,
-                &[params.data.as_ref()],
-                crate::client::RpcProtocol::Http,
+                vec![data.into()],
+                self.rpc.protocol(),
                 |x| {
                     tx.send(x).ok();
                     StateModification::empty()
@@ -211,17 +214,10 @@ impl FsRpc {
     ) -> Result<SymlinkResult, crate::client::DecthingsRpcError<SymlinkError>> {
         let (tx, rx) = tokio::sync::oneshot::channel();
         self.rpc
-            .raw_method_call::<_, _, &[u8]>(
-                "FS",
-                "symlink",
-                params,
-                &[],
-                crate::client::RpcProtocol::Http,
-                |x| {
-                    tx.send(x).ok();
-                    StateModification::empty()
-                },
-            )
+            .raw_method_call("FS", "symlink", params, vec![], self.rpc.protocol(), |x| {
+                tx.send(x).ok();
+                StateModification::empty()
+            })
             .await;
         rx.await
             .unwrap()
@@ -242,17 +238,10 @@ impl FsRpc {
     ) -> Result<ReadlinkResult, crate::client::DecthingsRpcError<ReadlinkError>> {
         let (tx, rx) = tokio::sync::oneshot::channel();
         self.rpc
-            .raw_method_call::<_, _, &[u8]>(
-                "FS",
-                "readlink",
-                params,
-                &[],
-                crate::client::RpcProtocol::Http,
-                |x| {
-                    tx.send(x).ok();
-                    StateModification::empty()
-                },
-            )
+            .raw_method_call("FS", "readlink", params, vec![], self.rpc.protocol(), |x| {
+                tx.send(x).ok();
+                StateModification::empty()
+            })
             .await;
         rx.await
             .unwrap()
@@ -280,17 +269,10 @@ impl FsRpc {
     ) -> Result<MkdirResult, crate::client::DecthingsRpcError<MkdirError>> {
         let (tx, rx) = tokio::sync::oneshot::channel();
         self.rpc
-            .raw_method_call::<_, _, &[u8]>(
-                "FS",
-                "mkdir",
-                params,
-                &[],
-                crate::client::RpcProtocol::Http,
-                |x| {
-                    tx.send(x).ok();
-                    StateModification::empty()
-                },
-            )
+            .raw_method_call("FS", "mkdir", params, vec![], self.rpc.protocol(), |x| {
+                tx.send(x).ok();
+                StateModification::empty()
+            })
             .await;
         rx.await
             .unwrap()
@@ -310,17 +292,10 @@ impl FsRpc {
     ) -> Result<UnlinkResult, crate::client::DecthingsRpcError<UnlinkError>> {
         let (tx, rx) = tokio::sync::oneshot::channel();
         self.rpc
-            .raw_method_call::<_, _, &[u8]>(
-                "FS",
-                "unlink",
-                params,
-                &[],
-                crate::client::RpcProtocol::Http,
-                |x| {
-                    tx.send(x).ok();
-                    StateModification::empty()
-                },
-            )
+            .raw_method_call("FS", "unlink", params, vec![], self.rpc.protocol(), |x| {
+                tx.send(x).ok();
+                StateModification::empty()
+            })
             .await;
         rx.await
             .unwrap()
@@ -340,17 +315,10 @@ impl FsRpc {
     ) -> Result<RmdirResult, crate::client::DecthingsRpcError<RmdirError>> {
         let (tx, rx) = tokio::sync::oneshot::channel();
         self.rpc
-            .raw_method_call::<_, _, &[u8]>(
-                "FS",
-                "rmdir",
-                params,
-                &[],
-                crate::client::RpcProtocol::Http,
-                |x| {
-                    tx.send(x).ok();
-                    StateModification::empty()
-                },
-            )
+            .raw_method_call("FS", "rmdir", params, vec![], self.rpc.protocol(), |x| {
+                tx.send(x).ok();
+                StateModification::empty()
+            })
             .await;
         rx.await
             .unwrap()
@@ -370,17 +338,10 @@ impl FsRpc {
     ) -> Result<RenameResult, crate::client::DecthingsRpcError<RenameError>> {
         let (tx, rx) = tokio::sync::oneshot::channel();
         self.rpc
-            .raw_method_call::<_, _, &[u8]>(
-                "FS",
-                "rename",
-                params,
-                &[],
-                crate::client::RpcProtocol::Http,
-                |x| {
-                    tx.send(x).ok();
-                    StateModification::empty()
-                },
-            )
+            .raw_method_call("FS", "rename", params, vec![], self.rpc.protocol(), |x| {
+                tx.send(x).ok();
+                StateModification::empty()
+            })
             .await;
         rx.await
             .unwrap()
@@ -400,17 +361,10 @@ impl FsRpc {
     ) -> Result<LinkResult, crate::client::DecthingsRpcError<LinkError>> {
         let (tx, rx) = tokio::sync::oneshot::channel();
         self.rpc
-            .raw_method_call::<_, _, &[u8]>(
-                "FS",
-                "link",
-                params,
-                &[],
-                crate::client::RpcProtocol::Http,
-                |x| {
-                    tx.send(x).ok();
-                    StateModification::empty()
-                },
-            )
+            .raw_method_call("FS", "link", params, vec![], self.rpc.protocol(), |x| {
+                tx.send(x).ok();
+                StateModification::empty()
+            })
             .await;
         rx.await
             .unwrap()
@@ -430,17 +384,10 @@ impl FsRpc {
     ) -> Result<ReaddirResult, crate::client::DecthingsRpcError<ReaddirError>> {
         let (tx, rx) = tokio::sync::oneshot::channel();
         self.rpc
-            .raw_method_call::<_, _, &[u8]>(
-                "FS",
-                "readdir",
-                params,
-                &[],
-                crate::client::RpcProtocol::Http,
-                |x| {
-                    tx.send(x).ok();
-                    StateModification::empty()
-                },
-            )
+            .raw_method_call("FS", "readdir", params, vec![], self.rpc.protocol(), |x| {
+                tx.send(x).ok();
+                StateModification::empty()
+            })
             .await;
         rx.await
             .unwrap()
@@ -461,17 +408,10 @@ impl FsRpc {
     ) -> Result<RmdirAllResult, crate::client::DecthingsRpcError<RmdirAllError>> {
         let (tx, rx) = tokio::sync::oneshot::channel();
         self.rpc
-            .raw_method_call::<_, _, &[u8]>(
-                "FS",
-                "rmdirAll",
-                params,
-                &[],
-                crate::client::RpcProtocol::Http,
-                |x| {
-                    tx.send(x).ok();
-                    StateModification::empty()
-                },
-            )
+            .raw_method_call("FS", "rmdirAll", params, vec![], self.rpc.protocol(), |x| {
+                tx.send(x).ok();
+                StateModification::empty()
+            })
             .await;
         rx.await
             .unwrap()
@@ -492,17 +432,10 @@ impl FsRpc {
     ) -> Result<CopyResult, crate::client::DecthingsRpcError<CopyError>> {
         let (tx, rx) = tokio::sync::oneshot::channel();
         self.rpc
-            .raw_method_call::<_, _, &[u8]>(
-                "FS",
-                "copy",
-                params,
-                &[],
-                crate::client::RpcProtocol::Http,
-                |x| {
-                    tx.send(x).ok();
-                    StateModification::empty()
-                },
-            )
+            .raw_method_call("FS", "copy", params, vec![], self.rpc.protocol(), |x| {
+                tx.send(x).ok();
+                StateModification::empty()
+            })
             .await;
         rx.await
             .unwrap()
@@ -97,11 +97,12 @@ pub struct ReadParams<'a> {
 
 #[derive(Debug, Clone, Serialize)]
 #[serde(rename_all = "camelCase")]
-pub struct WriteParams<'a, D: AsRef<[u8]>> {
+pub struct WriteParams<'a, D: Into<bytes::Bytes>> {
     /// The model's id.
     pub model_id: &'a str,
     /// Inode number of file.
     pub inode: u64,
+    /// Data to write. Pass `bytes::Bytes` to avoid copying it.
     #[serde(skip_serializing)]
     pub data: D,
     /// Where in the file to start writing.
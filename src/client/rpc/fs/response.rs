@@ -52,7 +52,7 @@ pub struct LookupResult {
 }
 
 #[derive(Debug, Clone, Deserialize)]
-#[serde(rename_all = "snake_case", tag = "code")]
+#[serde(rename_all = "snake_case", tag = "code", remote = "Self")]
 pub enum LookupError {
     ModelNotFound,
     ModelVersionNotFound,
@@ -71,8 +71,26 @@ pub enum LookupError {
         parameter_name: String,
         reason: String,
     },
+    /// An error code which is not known by this version of the client.
+    #[serde(skip)]
+    Unrecognized {
+        raw: serde_json::Value,
+    },
 }
 
+impl_decthings_error_code!(LookupError {
+    ModelNotFound => "model_not_found", "model not found";
+    ModelVersionNotFound => "model_version_not_found", "model version not found";
+    ESTALE => "ESTALE", "stale inode";
+    ENOENT => "ENOENT", "no such file or directory";
+    ENOTDIR => "ENOTDIR", "not a directory";
+    BadCredentials => "bad_credentials", "invalid API key";
+    TooManyRequests => "too_many_requests", "too many requests";
+    PaymentRequired => "payment_required", "payment required";
+    Unknown => "unknown", "unknown error";
+    InvalidParameter { parameter_name, reason } => "invalid_parameter", "parameter '{parameter_name}' invalid: {reason}";
+});
+
 #[derive(Debug, Clone, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct GetattrResult {
@@ -80,7 +98,7 @@ pub struct GetattrResult {
 }
 
 #[derive(Debug, Clone, Deserialize)]
-#[serde(rename_all = "snake_case", tag = "code")]
+#[serde(rename_all = "snake_case", tag = "code", remote = "Self")]
 pub enum GetattrError {
     ModelNotFound,
     ModelVersionNotFound,
@@ -95,8 +113,24 @@ pub enum GetattrError {
         parameter_name: String,
         reason: String,
     },
+    /// An error code which is not known by this version of the client.
+    #[serde(skip)]
+    Unrecognized {
+        raw: serde_json::Value,
+    },
 }
 
+impl_decthings_error_code!(GetattrError {
+    ModelNotFound => "model_not_found", "model not found";
+    ModelVersionNotFound => "model_version_not_found", "model version not found";
+    ESTALE => "ESTALE", "stale inode";
+    BadCredentials => "bad_credentials", "invalid API key";
+    TooManyRequests => "too_many_requests", "too many requests";
+    PaymentRequired => "payment_required", "payment required";
+    Unknown => "unknown", "unknown error";
+    InvalidParameter { parameter_name, reason } => "invalid_parameter", "parameter '{parameter_name}' invalid: {reason}";
+});
+
 #[derive(Debug, Clone, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct SetattrResult {
@@ -104,7 +138,7 @@ pub struct SetattrResult {
 }
 
 #[derive(Debug, Clone, Deserialize)]
-#[serde(rename_all = "snake_case", tag = "code")]
+#[serde(rename_all = "snake_case", tag = "code", remote = "Self")]
 pub enum SetattrError {
     ModelNotFound,
     AccessDenied,
@@ -123,8 +157,26 @@ pub enum SetattrError {
         parameter_name: String,
         reason: String,
     },
+    /// An error code which is not known by this version of the client.
+    #[serde(skip)]
+    Unrecognized {
+        raw: serde_json::Value,
+    },
 }
 
+impl_decthings_error_code!(SetattrError {
+    ModelNotFound => "model_not_found", "model not found";
+    AccessDenied => "access_denied", "access denied";
+    ESTALE => "ESTALE", "stale inode";
+    EFBIG => "EFBIG", "file too large";
+    EISDIR => "EISDIR", "is a directory";
+    BadCredentials => "bad_credentials", "invalid API key";
+    TooManyRequests => "too_many_requests", "too many requests";
+    PaymentRequired => "payment_required", "payment required";
+    Unknown => "unknown", "unknown error";
+    InvalidParameter { parameter_name, reason } => "invalid_parameter", "parameter '{parameter_name}' invalid: {reason}";
+});
+
 #[derive(Debug, Clone, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct MknodResult {
@@ -133,7 +185,7 @@ pub struct MknodResult {
 }
 
 #[derive(Debug, Clone, Deserialize)]
-#[serde(rename_all = "snake_case", tag = "code")]
+#[serde(rename_all = "snake_case", tag = "code", remote = "Self")]
 pub enum MknodError {
     ModelNotFound,
     AccessDenied,
@@ -154,8 +206,27 @@ pub enum MknodError {
         parameter_name: String,
         reason: String,
     },
+    /// An error code which is not known by this version of the client.
+    #[serde(skip)]
+    Unrecognized {
+        raw: serde_json::Value,
+    },
 }
 
+impl_decthings_error_code!(MknodError {
+    ModelNotFound => "model_not_found", "model not found";
+    AccessDenied => "access_denied", "access denied";
+    ESTALE => "ESTALE", "stale inode";
+    ENOTDIR => "ENOTDIR", "not a directory";
+    EEXIST => "EEXIST", "file already exists";
+    ENOSPC => "ENOSPC", "no space left on filesystem";
+    BadCredentials => "bad_credentials", "invalid API key";
+    TooManyRequests => "too_many_requests", "too many requests";
+    PaymentRequired => "payment_required", "payment required";
+    Unknown => "unknown", "unknown error";
+    InvalidParameter { parameter_name, reason } => "invalid_parameter", "parameter '{parameter_name}' invalid: {reason}";
+});
+
 #[derive(Debug, Clone, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct ReadResult {
@@ -164,7 +235,7 @@ pub struct ReadResult {
 }
 
 #[derive(Debug, Clone, Deserialize)]
-#[serde(rename_all = "snake_case", tag = "code")]
+#[serde(rename_all = "snake_case", tag = "code", remote = "Self")]
 pub enum ReadError {
     ModelNotFound,
     ModelVersionNotFound,
@@ -183,8 +254,26 @@ pub enum ReadError {
         parameter_name: String,
         reason: String,
     },
+    /// An error code which is not known by this version of the client.
+    #[serde(skip)]
+    Unrecognized {
+        raw: serde_json::Value,
+    },
 }
 
+impl_decthings_error_code!(ReadError {
+    ModelNotFound => "model_not_found", "model not found";
+    ModelVersionNotFound => "model_version_not_found", "model version not found";
+    ESTALE => "ESTALE", "stale inode";
+    EISDIR => "EISDIR", "is a directory";
+    EINVAL => "EINVAL", "invalid argument";
+    BadCredentials => "bad_credentials", "invalid API key";
+    TooManyRequests => "too_many_requests", "too many requests";
+    PaymentRequired => "payment_required", "payment required";
+    Unknown => "unknown", "unknown error";
+    InvalidParameter { parameter_name, reason } => "invalid_parameter", "parameter '{parameter_name}' invalid: {reason}";
+});
+
 #[derive(Debug, Clone, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct WriteResult {
@@ -192,7 +281,7 @@ pub struct WriteResult {
 }
 
 #[derive(Debug, Clone, Deserialize)]
-#[serde(rename_all = "snake_case", tag = "code")]
+#[serde(rename_all = "snake_case", tag = "code", remote = "Self")]
 pub enum WriteError {
     ModelNotFound,
     AccessDenied,
@@ -213,8 +302,27 @@ pub enum WriteError {
         parameter_name: String,
         reason: String,
     },
+    /// An error code which is not known by this version of the client.
+    #[serde(skip)]
+    Unrecognized {
+        raw: serde_json::Value,
+    },
 }
 
+impl_decthings_error_code!(WriteError {
+    ModelNotFound => "model_not_found", "model not found";
+    AccessDenied => "access_denied", "access denied";
+    ESTALE => "ESTALE", "stale inode";
+    EISDIR => "EISDIR", "is a directory";
+    ENOSPC => "ENOSPC", "no space left on filesystem";
+    EINVAL => "EINVAL", "invalid argument";
+    BadCredentials => "bad_credentials", "invalid API key";
+    TooManyRequests => "too_many_requests", "too many requests";
+    PaymentRequired => "payment_required", "payment required";
+    Unknown => "unknown", "unknown error";
+    InvalidParameter { parameter_name, reason } => "invalid_parameter", "parameter '{parameter_name}' invalid: {reason}";
+});
+
 #[derive(Debug, Clone, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct SymlinkResult {
@@ -223,7 +331,7 @@ pub struct SymlinkResult {
 }
 
 #[derive(Debug, Clone, Deserialize)]
-#[serde(rename_all = "snake_case", tag = "code")]
+#[serde(rename_all = "snake_case", tag = "code", remote = "Self")]
 pub enum SymlinkError {
     ModelNotFound,
     AccessDenied,
@@ -244,8 +352,27 @@ pub enum SymlinkError {
         parameter_name: String,
         reason: String,
     },
+    /// An error code which is not known by this version of the client.
+    #[serde(skip)]
+    Unrecognized {
+        raw: serde_json::Value,
+    },
 }
 
+impl_decthings_error_code!(SymlinkError {
+    ModelNotFound => "model_not_found", "model not found";
+    AccessDenied => "access_denied", "access denied";
+    ESTALE => "ESTALE", "stale inode";
+    ENOTDIR => "ENOTDIR", "not a directory";
+    EEXIST => "EEXIST", "file already exists";
+    ENOSPC => "ENOSPC", "no space left on filesystem";
+    BadCredentials => "bad_credentials", "invalid API key";
+    TooManyRequests => "too_many_requests", "too many requests";
+    PaymentRequired => "payment_required", "payment required";
+    Unknown => "unknown", "unknown error";
+    InvalidParameter { parameter_name, reason } => "invalid_parameter", "parameter '{parameter_name}' invalid: {reason}";
+});
+
 #[derive(Debug, Clone, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct ReadlinkResult {
@@ -254,7 +381,7 @@ pub struct ReadlinkResult {
 }
 
 #[derive(Debug, Clone, Deserialize)]
-#[serde(rename_all = "snake_case", tag = "code")]
+#[serde(rename_all = "snake_case", tag = "code", remote = "Self")]
 pub enum ReadlinkError {
     ModelNotFound,
     ModelVersionNotFound,
@@ -271,8 +398,25 @@ pub enum ReadlinkError {
         parameter_name: String,
         reason: String,
     },
+    /// An error code which is not known by this version of the client.
+    #[serde(skip)]
+    Unrecognized {
+        raw: serde_json::Value,
+    },
 }
 
+impl_decthings_error_code!(ReadlinkError {
+    ModelNotFound => "model_not_found", "model not found";
+    ModelVersionNotFound => "model_version_not_found", "model version not found";
+    ESTALE => "ESTALE", "stale inode";
+    EINVAL => "EINVAL", "invalid argument";
+    BadCredentials => "bad_credentials", "invalid API key";
+    TooManyRequests => "too_many_requests", "too many requests";
+    PaymentRequired => "payment_required", "payment required";
+    Unknown => "unknown", "unknown error";
+    InvalidParameter { parameter_name, reason } => "invalid_parameter", "parameter '{parameter_name}' invalid: {reason}";
+});
+
 #[derive(Debug, Clone, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct MkdirResult {
@@ -281,7 +425,7 @@ pub struct MkdirResult {
 }
 
 #[derive(Debug, Clone, Deserialize)]
-#[serde(rename_all = "snake_case", tag = "code")]
+#[serde(rename_all = "snake_case", tag = "code", remote = "Self")]
 pub enum MkdirError {
     ModelNotFound,
     AccessDenied,
@@ -302,14 +446,33 @@ pub enum MkdirError {
         parameter_name: String,
         reason: String,
     },
+    /// An error code which is not known by this version of the client.
+    #[serde(skip)]
+    Unrecognized {
+        raw: serde_json::Value,
+    },
 }
 
+impl_decthings_error_code!(MkdirError {
+    ModelNotFound => "model_not_found", "model not found";
+    AccessDenied => "access_denied", "access denied";
+    ESTALE => "ESTALE", "stale inode";
+    ENOTDIR => "ENOTDIR", "not a directory";
+    EEXIST => "EEXIST", "file already exists";
+    ENOSPC => "ENOSPC", "no space left on filesystem";
+    BadCredentials => "bad_credentials", "invalid API key";
+    TooManyRequests => "too_many_requests", "too many requests";
+    PaymentRequired => "payment_required", "payment required";
+    Unknown => "unknown", "unknown error";
+    InvalidParameter { parameter_name, reason } => "invalid_parameter", "parameter '{parameter_name}' invalid: {reason}";
+});
+
 #[derive(Debug, Clone, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct UnlinkResult {}
 
 #[derive(Debug, Clone, Deserialize)]
-#[serde(rename_all = "snake_case", tag = "code")]
+#[serde(rename_all = "snake_case", tag = "code", remote = "Self")]
 pub enum UnlinkError {
     ModelNotFound,
     AccessDenied,
@@ -330,14 +493,33 @@ pub enum UnlinkError {
         parameter_name: String,
         reason: String,
     },
+    /// An error code which is not known by this version of the client.
+    #[serde(skip)]
+    Unrecognized {
+        raw: serde_json::Value,
+    },
 }
 
+impl_decthings_error_code!(UnlinkError {
+    ModelNotFound => "model_not_found", "model not found";
+    AccessDenied => "access_denied", "access denied";
+    ESTALE => "ESTALE", "stale inode";
+    ENOTDIR => "ENOTDIR", "not a directory";
+    ENOENT => "ENOENT", "no such file or directory";
+    EISDIR => "EISDIR", "is a directory";
+    BadCredentials => "bad_credentials", "invalid API key";
+    TooManyRequests => "too_many_requests", "too many requests";
+    PaymentRequired => "payment_required", "payment required";
+    Unknown => "unknown", "unknown error";
+    InvalidParameter { parameter_name, reason } => "invalid_parameter", "parameter '{parameter_name}' invalid: {reason}";
+});
+
 #[derive(Debug, Clone, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct RmdirResult {}
 
 #[derive(Debug, Clone, Deserialize)]
-#[serde(rename_all = "snake_case", tag = "code")]
+#[serde(rename_all = "snake_case", tag = "code", remote = "Self")]
 pub enum RmdirError {
     ModelNotFound,
     AccessDenied,
@@ -358,14 +540,33 @@ pub enum RmdirError {
         parameter_name: String,
         reason: String,
     },
+    /// An error code which is not known by this version of the client.
+    #[serde(skip)]
+    Unrecognized {
+        raw: serde_json::Value,
+    },
 }
 
+impl_decthings_error_code!(RmdirError {
+    ModelNotFound => "model_not_found", "model not found";
+    AccessDenied => "access_denied", "access denied";
+    ESTALE => "ESTALE", "stale inode";
+    ENOTDIR => "ENOTDIR", "not a directory";
+    ENOENT => "ENOENT", "no such file or directory";
+    ENOTEMPTY => "ENOTEMPTY", "directory not empty";
+    BadCredentials => "bad_credentials", "invalid API key";
+    TooManyRequests => "too_many_requests", "too many requests";
+    PaymentRequired => "payment_required", "payment required";
+    Unknown => "unknown", "unknown error";
+    InvalidParameter { parameter_name, reason } => "invalid_parameter", "parameter '{parameter_name}' invalid: {reason}";
+});
+
 #[derive(Debug, Clone, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct RenameResult {}
 
 #[derive(Debug, Clone, Deserialize)]
-#[serde(rename_all = "snake_case", tag = "code")]
+#[serde(rename_all = "snake_case", tag = "code", remote = "Self")]
 pub enum RenameError {
     ModelNotFound,
     AccessDenied,
@@ -392,8 +593,30 @@ pub enum RenameError {
         parameter_name: String,
         reason: String,
     },
+    /// An error code which is not known by this version of the client.
+    #[serde(skip)]
+    Unrecognized {
+        raw: serde_json::Value,
+    },
 }
 
+impl_decthings_error_code!(RenameError {
+    ModelNotFound => "model_not_found", "model not found";
+    AccessDenied => "access_denied", "access denied";
+    ESTALE => "ESTALE", "stale inode";
+    ENOTDIR => "ENOTDIR", "not a directory";
+    ENOENT => "ENOENT", "no such file or directory";
+    ENOTEMPTY => "ENOTEMPTY", "directory not empty";
+    EEXIST => "EEXIST", "file already exists";
+    EISDIR => "EISDIR", "is a directory";
+    ENOSPC => "ENOSPC", "no space left on filesystem";
+    BadCredentials => "bad_credentials", "invalid API key";
+    TooManyRequests => "too_many_requests", "too many requests";
+    PaymentRequired => "payment_required", "payment required";
+    Unknown => "unknown", "unknown error";
+    InvalidParameter { parameter_name, reason } => "invalid_parameter", "parameter '{parameter_name}' invalid: {reason}";
+});
+
 #[derive(Debug, Clone, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct LinkResult {
@@ -401,7 +624,7 @@ pub struct LinkResult {
 }
 
 #[derive(Debug, Clone, Deserialize)]
-#[serde(rename_all = "snake_case", tag = "code")]
+#[serde(rename_all = "snake_case", tag = "code", remote = "Self")]
 pub enum LinkError {
     ModelNotFound,
     AccessDenied,
@@ -424,8 +647,28 @@ pub enum LinkError {
         parameter_name: String,
         reason: String,
     },
+    /// An error code which is not known by this version of the client.
+    #[serde(skip)]
+    Unrecognized {
+        raw: serde_json::Value,
+    },
 }
 
+impl_decthings_error_code!(LinkError {
+    ModelNotFound => "model_not_found", "model not found";
+    AccessDenied => "access_denied", "access denied";
+    ESTALE => "ESTALE", "stale inode";
+    ENOTDIR => "ENOTDIR", "not a directory";
+    EPERM => "EPERM", "operation not permitted";
+    EEXIST => "EEXIST", "file already exists";
+    ENOSPC => "ENOSPC", "no space left on filesystem";
+    BadCredentials => "bad_credentials", "invalid API key";
+    TooManyRequests => "too_many_requests", "too many requests";
+    PaymentRequired => "payment_required", "payment required";
+    Unknown => "unknown", "unknown error";
+    InvalidParameter { parameter_name, reason } => "invalid_parameter", "parameter '{parameter_name}' invalid: {reason}";
+});
+
 #[derive(Debug, Clone, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct ReaddirEntry {
@@ -445,7 +688,7 @@ pub struct ReaddirResult {
 }
 
 #[derive(Debug, Clone, Deserialize)]
-#[serde(rename_all = "snake_case", tag = "code")]
+#[serde(rename_all = "snake_case", tag = "code", remote = "Self")]
 pub enum ReaddirError {
     ModelNotFound,
     ModelVersionNotFound,
@@ -462,14 +705,31 @@ pub enum ReaddirError {
         parameter_name: String,
         reason: String,
     },
+    /// An error code which is not known by this version of the client.
+    #[serde(skip)]
+    Unrecognized {
+        raw: serde_json::Value,
+    },
 }
 
+impl_decthings_error_code!(ReaddirError {
+    ModelNotFound => "model_not_found", "model not found";
+    ModelVersionNotFound => "model_version_not_found", "model version not found";
+    ESTALE => "ESTALE", "stale inode";
+    ENOTDIR => "ENOTDIR", "not a directory";
+    BadCredentials => "bad_credentials", "invalid API key";
+    TooManyRequests => "too_many_requests", "too many requests";
+    PaymentRequired => "payment_required", "payment required";
+    Unknown => "unknown", "unknown error";
+    InvalidParameter { parameter_name, reason } => "invalid_parameter", "parameter '{parameter_name}' invalid: {reason}";
+});
+
 #[derive(Debug, Clone, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct RmdirAllResult {}
 
 #[derive(Debug, Clone, Deserialize)]
-#[serde(rename_all = "snake_case", tag = "code")]
+#[serde(rename_all = "snake_case", tag = "code", remote = "Self")]
 pub enum RmdirAllError {
     ModelNotFound,
     AccessDenied,
@@ -488,14 +748,32 @@ pub enum RmdirAllError {
         parameter_name: String,
         reason: String,
     },
+    /// An error code which is not known by this version of the client.
+    #[serde(skip)]
+    Unrecognized {
+        raw: serde_json::Value,
+    },
 }
 
+impl_decthings_error_code!(RmdirAllError {
+    ModelNotFound => "model_not_found", "model not found";
+    AccessDenied => "access_denied", "access denied";
+    ESTALE => "ESTALE", "stale inode";
+    ENOTDIR => "ENOTDIR", "not a directory";
+    ENOENT => "ENOENT", "no such file or directory";
+    BadCredentials => "bad_credentials", "invalid API key";
+    TooManyRequests => "too_many_requests", "too many requests";
+    PaymentRequired => "payment_required", "payment required";
+    Unknown => "unknown", "unknown error";
+    InvalidParameter { parameter_name, reason } => "invalid_parameter", "parameter '{parameter_name}' invalid: {reason}";
+});
+
 #[derive(Debug, Clone, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct CopyResult {}
 
 #[derive(Debug, Clone, Deserialize)]
-#[serde(rename_all = "snake_case", tag = "code")]
+#[serde(rename_all = "snake_case", tag = "code", remote = "Self")]
 pub enum CopyError {
     ModelNotFound,
     AccessDenied,
@@ -516,4 +794,23 @@ pub enum CopyError {
         parameter_name: String,
         reason: String,
     },
+    /// An error code which is not known by this version of the client.
+    #[serde(skip)]
+    Unrecognized {
+        raw: serde_json::Value,
+    },
 }
+
+impl_decthings_error_code!(CopyError {
+    ModelNotFound => "model_not_found", "model not found";
+    AccessDenied => "access_denied", "access denied";
+    ESTALE => "ESTALE", "stale inode";
+    ENOTDIR => "ENOTDIR", "not a directory";
+    EEXIST => "EEXIST", "file already exists";
+    ENOSPC => "ENOSPC", "no space left on filesystem";
+    BadCredentials => "bad_credentials", "invalid API key";
+    TooManyRequests => "too_many_requests", "too many requests";
+    PaymentRequired => "payment_required", "payment required";
+    Unknown => "unknown", "unknown error";
+    InvalidParameter { parameter_name, reason } => "invalid_parameter", "parameter '{parameter_name}' invalid: {reason}";
+});
@@ -15,6 +15,31 @@ impl ImageRpc {
         Self { rpc }
     }
 
+    /// Returns a copy of this API which uses the given timeout for each request, instead of
+    /// `DecthingsClientOptions::timeout`. Pass None to wait without a timeout.
+    pub fn with_timeout(&self, timeout: Option<std::time::Duration>) -> Self {
+        Self {
+            rpc: self.rpc.with_timeout(timeout),
+        }
+    }
+
+    /// Returns a copy of this API which retries requests according to the given policy, instead
+    /// of `DecthingsClientOptions::retry_policy`. Pass None to never retry.
+    pub fn with_retry_policy(&self, retry_policy: Option<crate::client::RetryPolicy>) -> Self {
+        Self {
+            rpc: self.rpc.with_retry_policy(retry_policy),
+        }
+    }
+
+    /// Returns a copy of this API which sends requests using the given protocol, instead of
+    /// `DecthingsClientOptions::default_protocol`. Methods which subscribe to events always use
+    /// WebSocket.
+    pub fn with_protocol(&self, protocol: crate::client::RpcProtocol) -> Self {
+        Self {
+            rpc: self.rpc.with_protocol(protocol),
+        }
+    }
+
     pub async fn create_repository(
         &self,
         params: CreateRepositoryParams<'_>,
@@ -22,12 +47,12 @@ impl ImageRpc {
     {
         let (tx, rx) = tokio::sync::oneshot::channel();
         self.rpc
-            .raw_method_call::<_, _, &[u8]>(
+            .raw_method_call(
                 "Image",
                 "createRepository",
                 params,
-                &[],
-                crate::client::RpcProtocol::Http,
+                vec![],
+                self.rpc.protocol(),
                 |x| {
                     tx.send(x).ok();
                     StateModification::empty()
@@ -54,12 +79,12 @@ impl ImageRpc {
     {
         let (tx, rx) = tokio::sync::oneshot::channel();
         self.rpc
-            .raw_method_call::<_, _, &[u8]>(
+            .raw_method_call(
                 "Image",
                 "updateRepository",
                 params,
-                &[],
-                crate::client::RpcProtocol::Http,
+                vec![],
+                self.rpc.protocol(),
                 |x| {
                     tx.send(x).ok();
                     StateModification::empty()
@@ -86,12 +111,12 @@ impl ImageRpc {
     {
         let (tx, rx) = tokio::sync::oneshot::channel();
         self.rpc
-            .raw_method_call::<_, _, &[u8]>(
+            .raw_method_call(
                 "Image",
                 "deleteRepository",
                 params,
-                &[],
-                crate::client::RpcProtocol::Http,
+                vec![],
+                self.rpc.protocol(),
                 |x| {
                     tx.send(x).ok();
                     StateModification::empty()
@@ -117,12 +142,12 @@ impl ImageRpc {
     ) -> Result<GetRepositoriesResult, crate::client::DecthingsRpcError<GetRepositoriesError>> {
         let (tx, rx) = tokio::sync::oneshot::channel();
         self.rpc
-            .raw_method_call::<_, _, &[u8]>(
+            .raw_method_call(
                 "Image",
                 "getRepositories",
                 params,
-                &[],
-                crate::client::RpcProtocol::Http,
+                vec![],
+                self.rpc.protocol(),
                 |x| {
                     tx.send(x).ok();
                     StateModification::empty()
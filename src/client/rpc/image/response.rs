@@ -5,7 +5,7 @@ use serde::Deserialize;
 pub struct CreateRepositoryResult {}
 
 #[derive(Debug, Clone, Deserialize)]
-#[serde(rename_all = "snake_case", tag = "code")]
+#[serde(rename_all = "snake_case", tag = "code", remote = "Self")]
 pub enum CreateRepositoryError {
     NameAlreadyUsed,
     OrganizationNotFound,
@@ -20,14 +20,31 @@ pub enum CreateRepositoryError {
         parameter_name: String,
         reason: String,
     },
+    /// An error code which is not known by this version of the client.
+    #[serde(skip)]
+    Unrecognized {
+        raw: serde_json::Value,
+    },
 }
 
+impl_decthings_error_code!(CreateRepositoryError {
+    NameAlreadyUsed => "name_already_used", "name already used";
+    OrganizationNotFound => "organization_not_found", "organization not found";
+    AccessDenied => "access_denied", "access denied";
+    QuotaExceeded => "quota_exceeded", "quota exceeded";
+    BadCredentials => "bad_credentials", "invalid API key";
+    TooManyRequests => "too_many_requests", "too many requests";
+    PaymentRequired => "payment_required", "payment required";
+    Unknown => "unknown", "unknown error";
+    InvalidParameter { parameter_name, reason } => "invalid_parameter", "parameter '{parameter_name}' invalid: {reason}";
+});
+
 #[derive(Debug, Clone, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct UpdateRepositoryResult {}
 
 #[derive(Debug, Clone, Deserialize)]
-#[serde(rename_all = "snake_case", tag = "code")]
+#[serde(rename_all = "snake_case", tag = "code", remote = "Self")]
 pub enum UpdateRepositoryError {
     RepositoryNotFound,
     AccessDenied,
@@ -40,14 +57,29 @@ pub enum UpdateRepositoryError {
         parameter_name: String,
         reason: String,
     },
+    /// An error code which is not known by this version of the client.
+    #[serde(skip)]
+    Unrecognized {
+        raw: serde_json::Value,
+    },
 }
 
+impl_decthings_error_code!(UpdateRepositoryError {
+    RepositoryNotFound => "repository_not_found", "repository not found";
+    AccessDenied => "access_denied", "access denied";
+    BadCredentials => "bad_credentials", "invalid API key";
+    TooManyRequests => "too_many_requests", "too many requests";
+    PaymentRequired => "payment_required", "payment required";
+    Unknown => "unknown", "unknown error";
+    InvalidParameter { parameter_name, reason } => "invalid_parameter", "parameter '{parameter_name}' invalid: {reason}";
+});
+
 #[derive(Debug, Clone, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct DeleteRepositoryResult {}
 
 #[derive(Debug, Clone, Deserialize)]
-#[serde(rename_all = "snake_case", tag = "code")]
+#[serde(rename_all = "snake_case", tag = "code", remote = "Self")]
 pub enum DeleteRepositoryError {
     RepositoryNotFound,
     AccessDenied,
@@ -60,10 +92,25 @@ pub enum DeleteRepositoryError {
         parameter_name: String,
         reason: String,
     },
+    /// An error code which is not known by this version of the client.
+    #[serde(skip)]
+    Unrecognized {
+        raw: serde_json::Value,
+    },
 }
 
+impl_decthings_error_code!(DeleteRepositoryError {
+    RepositoryNotFound => "repository_not_found", "repository not found";
+    AccessDenied => "access_denied", "access denied";
+    BadCredentials => "bad_credentials", "invalid API key";
+    TooManyRequests => "too_many_requests", "too many requests";
+    PaymentRequired => "payment_required", "payment required";
+    Unknown => "unknown", "unknown error";
+    InvalidParameter { parameter_name, reason } => "invalid_parameter", "parameter '{parameter_name}' invalid: {reason}";
+});
+
 #[derive(Debug, Clone, Deserialize)]
-#[serde(rename_all = "camelCase", tag = "type")]
+#[serde(rename_all = "camelCase", tag = "type", remote = "Self")]
 pub enum RepositoryOwner {
     #[serde(rename_all = "camelCase")]
     User { user_id: String, username: String },
@@ -72,15 +119,27 @@ pub enum RepositoryOwner {
         organization_id: String,
         organization_name: String,
     },
+    /// An owner which is not known by this version of the client.
+    #[serde(skip)]
+    Unrecognized { raw: serde_json::Value },
 }
 
+impl_deserialize_unrecognized!(RepositoryOwner, "type", ["user", "organization"]);
+
 #[derive(Debug, Clone, Deserialize)]
-#[serde(rename_all = "camelCase")]
+#[serde(rename_all = "camelCase", remote = "Self")]
 pub enum RepositoryAccess {
     Read,
     Readwrite,
+    /// An access level which is not known by this version of the client.
+    #[serde(skip)]
+    Unrecognized {
+        raw: serde_json::Value,
+    },
 }
 
+impl_deserialize_unrecognized!(RepositoryAccess, ["read", "readwrite"]);
+
 #[derive(Debug, Clone, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct Repository {
@@ -103,7 +162,7 @@ pub struct GetRepositoriesResult {
 }
 
 #[derive(Debug, Clone, Deserialize)]
-#[serde(rename_all = "snake_case", tag = "code")]
+#[serde(rename_all = "snake_case", tag = "code", remote = "Self")]
 pub enum GetRepositoriesError {
     BadCredentials,
     TooManyRequests,
@@ -114,4 +173,17 @@ pub enum GetRepositoriesError {
         parameter_name: String,
         reason: String,
     },
+    /// An error code which is not known by this version of the client.
+    #[serde(skip)]
+    Unrecognized {
+        raw: serde_json::Value,
+    },
 }
+
+impl_decthings_error_code!(GetRepositoriesError {
+    BadCredentials => "bad_credentials", "invalid API key";
+    TooManyRequests => "too_many_requests", "too many requests";
+    PaymentRequired => "payment_required", "payment required";
+    Unknown => "unknown", "unknown error";
+    InvalidParameter { parameter_name, reason } => "invalid_parameter", "parameter '{parameter_name}' invalid: {reason}";
+});
@@ -15,6 +15,22 @@ impl LanguageRpc {
         Self { rpc }
     }
 
+    /// Returns a copy of this API which uses the given timeout for each request, instead of
+    /// `DecthingsClientOptions::timeout`. Pass None to wait without a timeout.
+    pub fn with_timeout(&self, timeout: Option<std::time::Duration>) -> Self {
+        Self {
+            rpc: self.rpc.with_timeout(timeout),
+        }
+    }
+
+    /// Returns a copy of this API which retries requests according to the given policy, instead
+    /// of `DecthingsClientOptions::retry_policy`. Pass None to never retry.
+    pub fn with_retry_policy(&self, retry_policy: Option<crate::client::RetryPolicy>) -> Self {
+        Self {
+            rpc: self.rpc.with_retry_policy(retry_policy),
+        }
+    }
+
     pub async fn start_language_server(
         &self,
         params: StartLanguageServerParams<'_>,
@@ -22,11 +38,11 @@ impl LanguageRpc {
     {
         let (tx, rx) = tokio::sync::oneshot::channel();
         self.rpc
-            .raw_method_call::<_, _, &[u8]>(
+            .raw_method_call(
                 "Language",
                 "startLanguageServer",
                 params,
-                &[],
+                vec![],
                 crate::client::RpcProtocol::Ws,
                 move |x| {
                     match x {
@@ -43,7 +59,7 @@ impl LanguageRpc {
                                     let language_server_id = val.language_server_id.clone();
                                     tx.send(Ok(val)).ok();
                                     return StateModification {
-                                        add_events: vec![language_server_id],
+                                        add_events: vec![("Language", language_server_id)],
                                         remove_events: vec![],
                                     };
                                 }
@@ -69,11 +85,19 @@ impl LanguageRpc {
 
     pub async fn write_to_language_server(
         &self,
-        params: WriteToLanguageServerParams<'_, impl AsRef<[u8]>>,
+        params: WriteToLanguageServerParams<'_, impl Into<bytes::Bytes>>,
     ) -> Result<
         WriteToLanguageServerResult,
         crate::client::DecthingsRpcError<WriteToLanguageServerError>,
     > {
+        let WriteToLanguageServerParams {
+            language_server_id,
+            data,
+        } = params;
+        let params = WriteToLanguageServerParams {
+            language_server_id,
+            data: bytes::Bytes::new(),
+        };
         let (tx, rx) = tokio::sync::oneshot::channel();
         let did_call = self
             .rpc
@@ -81,7 +105,7 @@ impl LanguageRpc {
                 "Language",
                 "writeToLanguageServer",
                 &params,
-                [params.data.as_ref()],
+                vec![data.into()],
                 crate::client::RpcProtocol::WsIfAvailableOtherwiseNone,
                 |x| {
                     tx.send(x).ok();
@@ -107,6 +131,17 @@ impl LanguageRpc {
             })
     }
 
+    /// Returns a stream of the events of the language server `language_server_id`, which ends after the Exit
+    /// event. Only events received after this call are included. See
+    /// [`EventStream`](crate::client::event::EventStream).
+    #[cfg(feature = "events")]
+    pub fn events(
+        &self,
+        language_server_id: impl Into<String>,
+    ) -> crate::client::event::EventStream<LanguageEvent> {
+        self.rpc.event_stream(language_server_id.into())
+    }
+
     pub async fn unsubscribe_from_events(
         &self,
         params: LanguageUnsubscribeFromEventsParams<'_>,
@@ -118,11 +153,11 @@ impl LanguageRpc {
         let language_server_id_owned = params.language_server_id.to_owned();
         let did_call = self
             .rpc
-            .raw_method_call::<_, _, &[u8]>(
+            .raw_method_call(
                 "Language",
                 "unsubscribeFromEvents",
                 params,
-                &[],
+                vec![],
                 crate::client::RpcProtocol::WsIfAvailableOtherwiseNone,
                 move |x| {
                     match x {
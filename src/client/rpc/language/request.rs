@@ -19,10 +19,10 @@ pub struct StartLanguageServerParams<'a> {
 
 #[derive(Debug, Clone, Serialize)]
 #[serde(rename_all = "camelCase")]
-pub struct WriteToLanguageServerParams<'a, D: AsRef<[u8]>> {
+pub struct WriteToLanguageServerParams<'a, D: Into<bytes::Bytes>> {
     /// The language server's id.
     pub language_server_id: &'a str,
-    /// Data to write.
+    /// Data to write. Pass `bytes::Bytes` to avoid copying it.
     #[serde(skip_serializing)]
     pub data: D,
 }
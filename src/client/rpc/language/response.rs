@@ -2,16 +2,32 @@ use serde::Deserialize;
 
 #[cfg(feature = "events")]
 #[derive(Debug, Clone, Deserialize)]
-#[serde(rename_all = "snake_case")]
+#[serde(rename_all = "snake_case", remote = "Self")]
 pub enum LanguageServerTerminatedReason {
     Timedout,
     Oom,
     Unknown,
+    /// A reason which is not known by this version of the client.
+    #[serde(skip)]
+    Unrecognized {
+        raw: serde_json::Value,
+    },
 }
 
+#[cfg(feature = "events")]
+impl_deserialize_unrecognized!(
+    LanguageServerTerminatedReason,
+    ["timedout", "oom", "unknown"]
+);
+
 #[cfg(feature = "events")]
 #[derive(Debug, Clone, Deserialize)]
-#[serde(rename_all = "camelCase", tag = "event", content = "params")]
+#[serde(
+    rename_all = "camelCase",
+    tag = "event",
+    content = "params",
+    remote = "Self"
+)]
 pub enum LanguageEvent {
     #[serde(rename_all = "camelCase")]
     Exit {
@@ -24,8 +40,14 @@ pub enum LanguageEvent {
         #[serde(skip_deserializing)]
         data: bytes::Bytes,
     },
+    /// An event which is not known by this version of the client.
+    #[serde(skip)]
+    Unrecognized { raw: serde_json::Value },
 }
 
+#[cfg(feature = "events")]
+impl_deserialize_unrecognized!(LanguageEvent, "event", ["exit", "data"]);
+
 #[derive(Debug, Clone, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct StartLanguageServerResult {
@@ -34,7 +56,7 @@ pub struct StartLanguageServerResult {
 }
 
 #[derive(Debug, Clone, Deserialize)]
-#[serde(rename_all = "snake_case", tag = "code")]
+#[serde(rename_all = "snake_case", tag = "code", remote = "Self")]
 pub enum StartLanguageServerError {
     ModelNotFound,
     ServerOverloaded,
@@ -47,14 +69,29 @@ pub enum StartLanguageServerError {
         parameter_name: String,
         reason: String,
     },
+    /// An error code which is not known by this version of the client.
+    #[serde(skip)]
+    Unrecognized {
+        raw: serde_json::Value,
+    },
 }
 
+impl_decthings_error_code!(StartLanguageServerError {
+    ModelNotFound => "model_not_found", "model not found";
+    ServerOverloaded => "server_overloaded", "server overloaded";
+    BadCredentials => "bad_credentials", "invalid API key";
+    TooManyRequests => "too_many_requests", "too many requests";
+    PaymentRequired => "payment_required", "payment required";
+    Unknown => "unknown", "unknown error";
+    InvalidParameter { parameter_name, reason } => "invalid_parameter", "parameter '{parameter_name}' invalid: {reason}";
+});
+
 #[derive(Debug, Clone, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct WriteToLanguageServerResult {}
 
 #[derive(Debug, Clone, Deserialize)]
-#[serde(rename_all = "snake_case", tag = "code")]
+#[serde(rename_all = "snake_case", tag = "code", remote = "Self")]
 pub enum WriteToLanguageServerError {
     LanguageServerNotFound,
     BadCredentials,
@@ -66,8 +103,22 @@ pub enum WriteToLanguageServerError {
         parameter_name: String,
         reason: String,
     },
+    /// An error code which is not known by this version of the client.
+    #[serde(skip)]
+    Unrecognized {
+        raw: serde_json::Value,
+    },
 }
 
+impl_decthings_error_code!(WriteToLanguageServerError {
+    LanguageServerNotFound => "language_server_not_found", "language server not found";
+    BadCredentials => "bad_credentials", "invalid API key";
+    TooManyRequests => "too_many_requests", "too many requests";
+    PaymentRequired => "payment_required", "payment required";
+    Unknown => "unknown", "unknown error";
+    InvalidParameter { parameter_name, reason } => "invalid_parameter", "parameter '{parameter_name}' invalid: {reason}";
+});
+
 #[cfg(feature = "events")]
 #[derive(Debug, Clone, Deserialize)]
 #[serde(rename_all = "camelCase")]
@@ -75,7 +126,7 @@ pub struct LanguageUnsubscribeFromEventsResult {}
 
 #[cfg(feature = "events")]
 #[derive(Debug, Clone, Deserialize)]
-#[serde(rename_all = "snake_case", tag = "code")]
+#[serde(rename_all = "snake_case", tag = "code", remote = "Self")]
 pub enum LanguageUnsubscribeFromEventsError {
     NotSubscribed,
     TooManyRequests,
@@ -85,4 +136,17 @@ pub enum LanguageUnsubscribeFromEventsError {
         parameter_name: String,
         reason: String,
     },
+    /// An error code which is not known by this version of the client.
+    #[serde(skip)]
+    Unrecognized {
+        raw: serde_json::Value,
+    },
 }
+
+#[cfg(feature = "events")]
+impl_decthings_error_code!(LanguageUnsubscribeFromEventsError {
+    NotSubscribed => "not_subscribed", "not subscribed";
+    TooManyRequests => "too_many_requests", "too many requests";
+    Unknown => "unknown", "unknown error";
+    InvalidParameter { parameter_name, reason } => "invalid_parameter", "parameter '{parameter_name}' invalid: {reason}";
+});
@@ -1,18 +1,114 @@
-use serde::{ser::SerializeSeq, Deserialize, Serialize, Serializer};
+// The shared types and helpers are only used by some of the APIs.
+#![cfg_attr(
+    not(all(
+        feature = "dataset",
+        feature = "debug",
+        feature = "fs",
+        feature = "image",
+        feature = "language",
+        feature = "model",
+        feature = "persistent-launcher",
+        feature = "spawned",
+        feature = "terminal"
+    )),
+    allow(dead_code, unused_imports, unused_macros)
+)]
+
+use serde::{Deserialize, Serialize, Serializer, ser::SerializeSeq};
 
 use crate::tensor::DecthingsParameterDefinition;
 
+/// Implements Deserialize for an enum which has an `Unrecognized { raw: serde_json::Value }`
+/// variant, given the tag field and the names of all other variants. The enum must derive
+/// Deserialize with `#[serde(remote = "Self")]`, which is used for the known variants. Values
+/// with any other tag become Unrecognized, so that new variants added to Decthings do not break
+/// older clients. Without a tag field, the enum is deserialized from a string.
+macro_rules! impl_deserialize_unrecognized {
+    ($name:ident, [$($known:literal),* $(,)?]) => {
+        impl<'de> serde::Deserialize<'de> for $name {
+            fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+                let raw = <serde_json::Value as serde::Deserialize>::deserialize(deserializer)?;
+                match raw.as_str() {
+                    Some($($known)|*) => $name::deserialize(raw).map_err(serde::de::Error::custom),
+                    _ => Ok(Self::Unrecognized { raw }),
+                }
+            }
+        }
+    };
+    ($name:ident, $tag:literal, [$($known:literal),* $(,)?]) => {
+        impl<'de> serde::Deserialize<'de> for $name {
+            fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+                let raw = <serde_json::Value as serde::Deserialize>::deserialize(deserializer)?;
+                match raw.get($tag).and_then(|x| x.as_str()) {
+                    Some($($known)|*) => $name::deserialize(raw).map_err(serde::de::Error::custom),
+                    _ => Ok(Self::Unrecognized { raw }),
+                }
+            }
+        }
+    };
+}
+
+/// Implements DecthingsErrorCode, Display and Deserialize for an error enum, given the code and
+/// message of each variant. Fields used in the message are listed after the variant. All error
+/// enums have an InvalidParameter and an Unrecognized variant.
+macro_rules! impl_decthings_error_code {
+    ($name:ident {
+        $($variant:ident $({ $($field:ident),* })? => $code:literal, $fmt:literal $(, $arg:expr)*;)*
+    }) => {
+        impl_deserialize_unrecognized!($name, "code", [$($code),*]);
+
+        impl crate::client::DecthingsErrorCode for $name {
+            fn code(&self) -> &str {
+                match self {
+                    $(Self::$variant { .. } => $code,)*
+                    Self::Unrecognized { raw } => {
+                        raw.get("code").and_then(|x| x.as_str()).unwrap_or("unknown")
+                    }
+                }
+            }
+
+            fn invalid_parameter(&self) -> Option<(&str, &str)> {
+                match self {
+                    Self::InvalidParameter {
+                        parameter_name,
+                        reason,
+                    } => Some((parameter_name, reason)),
+                    _ => None,
+                }
+            }
+        }
+
+        impl std::fmt::Display for $name {
+            fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+                match self {
+                    $(Self::$variant { $($($field,)*)? .. } => write!(f, $fmt $(, $arg)*),)*
+                    Self::Unrecognized { .. } => {
+                        let code = crate::client::DecthingsErrorCode::code(self);
+                        write!(f, "unrecognized error '{code}'")
+                    }
+                }
+            }
+        }
+    };
+}
+
+#[cfg(feature = "dataset")]
 pub mod dataset;
+#[cfg(feature = "debug")]
 pub mod debug;
+#[cfg(feature = "fs")]
 pub mod fs;
+#[cfg(feature = "image")]
 pub mod image;
-
-#[cfg(feature = "events")]
+#[cfg(feature = "language")]
 pub mod language;
-
+#[cfg(feature = "model")]
 pub mod model;
+#[cfg(feature = "persistent-launcher")]
 pub mod persistent_launcher;
+#[cfg(feature = "spawned")]
 pub mod spawned;
+#[cfg(feature = "terminal")]
 pub mod terminal;
 
 #[derive(Debug, Clone, PartialEq, Serialize)]
@@ -113,8 +209,9 @@ pub struct ParameterDefinitions {
 }
 
 #[derive(Debug, Clone)]
-pub struct WeightKeyDataProvider<'a, D: AsRef<[u8]>> {
+pub struct WeightKeyDataProvider<'a, D: Clone + Into<bytes::Bytes>> {
     pub key: &'a str,
+    /// The data is cloned before it is sent, so pass `bytes::Bytes` to avoid copying it.
     pub data: D,
 }
 
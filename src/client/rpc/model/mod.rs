@@ -15,18 +15,43 @@ impl ModelRpc {
         Self { rpc }
     }
 
+    /// Returns a copy of this API which uses the given timeout for each request, instead of
+    /// `DecthingsClientOptions::timeout`. Pass None to wait without a timeout.
+    pub fn with_timeout(&self, timeout: Option<std::time::Duration>) -> Self {
+        Self {
+            rpc: self.rpc.with_timeout(timeout),
+        }
+    }
+
+    /// Returns a copy of this API which retries requests according to the given policy, instead
+    /// of `DecthingsClientOptions::retry_policy`. Pass None to never retry.
+    pub fn with_retry_policy(&self, retry_policy: Option<crate::client::RetryPolicy>) -> Self {
+        Self {
+            rpc: self.rpc.with_retry_policy(retry_policy),
+        }
+    }
+
+    /// Returns a copy of this API which sends requests using the given protocol, instead of
+    /// `DecthingsClientOptions::default_protocol`. Methods which subscribe to events always use
+    /// WebSocket.
+    pub fn with_protocol(&self, protocol: crate::client::RpcProtocol) -> Self {
+        Self {
+            rpc: self.rpc.with_protocol(protocol),
+        }
+    }
+
     pub async fn create_model(
         &self,
         params: CreateModelParams<'_>,
     ) -> Result<CreateModelResult, crate::client::DecthingsRpcError<CreateModelError>> {
         let (tx, rx) = tokio::sync::oneshot::channel();
         self.rpc
-            .raw_method_call::<_, _, &[u8]>(
+            .raw_method_call(
                 "Model",
                 "createModel",
                 params,
-                &[],
-                crate::client::RpcProtocol::Http,
+                vec![],
+                self.rpc.protocol(),
                 |x| {
                     tx.send(x).ok();
                     StateModification::empty()
@@ -52,12 +77,12 @@ impl ModelRpc {
     ) -> Result<DeleteModelResult, crate::client::DecthingsRpcError<DeleteModelError>> {
         let (tx, rx) = tokio::sync::oneshot::channel();
         self.rpc
-            .raw_method_call::<_, _, &[u8]>(
+            .raw_method_call(
                 "Model",
                 "deleteModel",
                 params,
-                &[],
-                crate::client::RpcProtocol::Http,
+                vec![],
+                self.rpc.protocol(),
                 |x| {
                     tx.send(x).ok();
                     StateModification::empty()
@@ -83,12 +108,12 @@ impl ModelRpc {
     ) -> Result<UpdateModelResult, crate::client::DecthingsRpcError<UpdateModelError>> {
         let (tx, rx) = tokio::sync::oneshot::channel();
         self.rpc
-            .raw_method_call::<_, _, &[u8]>(
+            .raw_method_call(
                 "Model",
                 "updateModel",
                 params,
-                &[],
-                crate::client::RpcProtocol::Http,
+                vec![],
+                self.rpc.protocol(),
                 |x| {
                     tx.send(x).ok();
                     StateModification::empty()
@@ -114,12 +139,12 @@ impl ModelRpc {
     ) -> Result<GetModelsResult, crate::client::DecthingsRpcError<GetModelsError>> {
         let (tx, rx) = tokio::sync::oneshot::channel();
         self.rpc
-            .raw_method_call::<_, _, &[u8]>(
+            .raw_method_call(
                 "Model",
                 "getModels",
                 params,
-                &[],
-                crate::client::RpcProtocol::Http,
+                vec![],
+                self.rpc.protocol(),
                 |x| {
                     tx.send(x).ok();
                     StateModification::empty()
@@ -146,12 +171,12 @@ impl ModelRpc {
     {
         let (tx, rx) = tokio::sync::oneshot::channel();
         self.rpc
-            .raw_method_call::<_, _, &[u8]>(
+            .raw_method_call(
                 "Model",
                 "setFilesystemSize",
                 params,
-                &[],
-                crate::client::RpcProtocol::Http,
+                vec![],
+                self.rpc.protocol(),
                 |x| {
                     tx.send(x).ok();
                     StateModification::empty()
@@ -184,7 +209,7 @@ impl ModelRpc {
                 "createModelVersion",
                 params,
                 serialized,
-                crate::client::RpcProtocol::Http,
+                self.rpc.protocol(),
                 |x| {
                     tx.send(x).ok();
                     StateModification::empty()
@@ -206,20 +231,20 @@ impl ModelRpc {
 
     pub async fn create_model_version_upload_weights(
         &self,
-        params: CreateModelVersionUploadWeightsParams<'_, impl AsRef<[u8]>>,
+        params: CreateModelVersionUploadWeightsParams<'_, impl Clone + Into<bytes::Bytes>>,
     ) -> Result<
         CreateModelVersionUploadWeightsResult,
         crate::client::DecthingsRpcError<CreateModelVersionUploadWeightsError>,
     > {
         let (tx, rx) = tokio::sync::oneshot::channel();
-        let data = params.data.iter().map(|x| &x.data).collect::<Vec<_>>();
+        let data = params.data.iter().map(|x| x.data.clone().into()).collect();
         self.rpc
             .raw_method_call(
                 "Model",
                 "createModelVersionUploadWeights",
                 params,
                 data,
-                crate::client::RpcProtocol::Http,
+                self.rpc.protocol(),
                 |x| {
                     tx.send(x).ok();
                     StateModification::empty()
@@ -248,12 +273,12 @@ impl ModelRpc {
     {
         let (tx, rx) = tokio::sync::oneshot::channel();
         self.rpc
-            .raw_method_call::<_, _, &[u8]>(
+            .raw_method_call(
                 "Model",
                 "updateModelVersion",
                 params,
-                &[],
-                crate::client::RpcProtocol::Http,
+                vec![],
+                self.rpc.protocol(),
                 |x| {
                     tx.send(x).ok();
                     StateModification::empty()
@@ -279,12 +304,12 @@ impl ModelRpc {
     ) -> Result<GetWeightsResult, crate::client::DecthingsRpcError<GetWeightsError>> {
         let (tx, rx) = tokio::sync::oneshot::channel();
         self.rpc
-            .raw_method_call::<_, _, &[u8]>(
+            .raw_method_call(
                 "Model",
                 "getWeights",
                 params,
-                &[],
-                crate::client::RpcProtocol::Http,
+                vec![],
+                self.rpc.protocol(),
                 |x| {
                     tx.send(x).ok();
                     StateModification::empty()
@@ -311,6 +336,27 @@ impl ModelRpc {
             })
     }
 
+    /// Like `get_weights`, but the weight data is read while it is downloaded instead of being
+    /// kept in memory.
+    pub async fn get_weights_streamed(
+        &self,
+        params: GetWeightsParams<'_, impl AsRef<str>>,
+    ) -> Result<GetWeightsStreamedResult, crate::client::DecthingsRpcError<GetWeightsError>> {
+        let (response, blobs) = self
+            .rpc
+            .raw_method_call_streamed("Model", "getWeights", params, vec![])
+            .await?;
+        let res: super::Response<GetWeightsResult, GetWeightsError> =
+            serde_json::from_slice(&response)?;
+        match res {
+            super::Response::Result(val) => Ok(GetWeightsStreamedResult {
+                keys: val.data.into_iter().map(|x| x.key).collect(),
+                data: blobs,
+            }),
+            super::Response::Error(val) => Err(crate::client::DecthingsRpcError::Rpc(val)),
+        }
+    }
+
     pub async fn delete_model_version(
         &self,
         params: DeleteModelVersionParams<'_>,
@@ -318,12 +364,12 @@ impl ModelRpc {
     {
         let (tx, rx) = tokio::sync::oneshot::channel();
         self.rpc
-            .raw_method_call::<_, _, &[u8]>(
+            .raw_method_call(
                 "Model",
                 "deleteModelVersion",
                 params,
-                &[],
-                crate::client::RpcProtocol::Http,
+                vec![],
+                self.rpc.protocol(),
                 |x| {
                     tx.send(x).ok();
                     StateModification::empty()
@@ -355,7 +401,7 @@ impl ModelRpc {
                 "train",
                 params,
                 serialized,
-                crate::client::RpcProtocol::Http,
+                self.rpc.protocol(),
                 |x| {
                     tx.send(x).ok();
                     StateModification::empty()
@@ -381,12 +427,12 @@ impl ModelRpc {
     {
         let (tx, rx) = tokio::sync::oneshot::channel();
         self.rpc
-            .raw_method_call::<_, _, &[u8]>(
+            .raw_method_call(
                 "Model",
                 "getTrainingStatus",
                 params,
-                &[],
-                crate::client::RpcProtocol::Http,
+                vec![],
+                self.rpc.protocol(),
                 |x| {
                     tx.send(x).ok();
                     StateModification::empty()
@@ -413,12 +459,12 @@ impl ModelRpc {
     {
         let (tx, rx) = tokio::sync::oneshot::channel();
         self.rpc
-            .raw_method_call::<_, _, &[u8]>(
+            .raw_method_call(
                 "Model",
                 "getTrainingMetrics",
                 params,
-                &[],
-                crate::client::RpcProtocol::Http,
+                vec![],
+                self.rpc.protocol(),
                 |x| {
                     tx.send(x).ok();
                     StateModification::empty()
@@ -461,12 +507,12 @@ impl ModelRpc {
     > {
         let (tx, rx) = tokio::sync::oneshot::channel();
         self.rpc
-            .raw_method_call::<_, _, &[u8]>(
+            .raw_method_call(
                 "Model",
                 "cancelTrainingSession",
                 params,
-                &[],
-                crate::client::RpcProtocol::Http,
+                vec![],
+                self.rpc.protocol(),
                 |x| {
                     tx.send(x).ok();
                     StateModification::empty()
@@ -495,12 +541,12 @@ impl ModelRpc {
     > {
         let (tx, rx) = tokio::sync::oneshot::channel();
         self.rpc
-            .raw_method_call::<_, _, &[u8]>(
+            .raw_method_call(
                 "Model",
                 "clearPreviousTrainingSession",
                 params,
-                &[],
-                crate::client::RpcProtocol::Http,
+                vec![],
+                self.rpc.protocol(),
                 |x| {
                     tx.send(x).ok();
                     StateModification::empty()
@@ -534,7 +580,7 @@ impl ModelRpc {
                 "evaluate",
                 params,
                 serialized,
-                crate::client::RpcProtocol::Http,
+                self.rpc.protocol(),
                 |x| {
                     tx.send(x).ok();
                     StateModification::empty()
@@ -577,12 +623,12 @@ impl ModelRpc {
     ) -> Result<GetEvaluationsResult, crate::client::DecthingsRpcError<GetEvaluationsError>> {
         let (tx, rx) = tokio::sync::oneshot::channel();
         self.rpc
-            .raw_method_call::<_, _, &[u8]>(
+            .raw_method_call(
                 "Model",
                 "getEvaluations",
                 params,
-                &[],
-                crate::client::RpcProtocol::Http,
+                vec![],
+                self.rpc.protocol(),
                 |x| {
                     tx.send(x).ok();
                     StateModification::empty()
@@ -611,12 +657,12 @@ impl ModelRpc {
     > {
         let (tx, rx) = tokio::sync::oneshot::channel();
         self.rpc
-            .raw_method_call::<_, _, &[u8]>(
+            .raw_method_call(
                 "Model",
                 "getFinishedEvaluationResult",
                 params,
-                &[],
-                crate::client::RpcProtocol::Http,
+                vec![],
+                self.rpc.protocol(),
                 |x| {
                     tx.send(x).ok();
                     StateModification::empty()
@@ -665,12 +711,12 @@ impl ModelRpc {
     {
         let (tx, rx) = tokio::sync::oneshot::channel();
         self.rpc
-            .raw_method_call::<_, _, &[u8]>(
+            .raw_method_call(
                 "Model",
                 "cancelEvaluation",
                 params,
-                &[],
-                crate::client::RpcProtocol::Http,
+                vec![],
+                self.rpc.protocol(),
                 |x| {
                     tx.send(x).ok();
                     StateModification::empty()
@@ -699,12 +745,12 @@ impl ModelRpc {
     > {
         let (tx, rx) = tokio::sync::oneshot::channel();
         self.rpc
-            .raw_method_call::<_, _, &[u8]>(
+            .raw_method_call(
                 "Model",
                 "setUsedPersistentLaunchersForEvaluate",
                 params,
-                &[],
-                crate::client::RpcProtocol::Http,
+                vec![],
+                self.rpc.protocol(),
                 |x| {
                     tx.send(x).ok();
                     StateModification::empty()
@@ -735,12 +781,12 @@ impl ModelRpc {
     > {
         let (tx, rx) = tokio::sync::oneshot::channel();
         self.rpc
-            .raw_method_call::<_, _, &[u8]>(
+            .raw_method_call(
                 "Model",
                 "getUsedPersistentLaunchersForEvaluate",
                 params,
-                &[],
-                crate::client::RpcProtocol::Http,
+                vec![],
+                self.rpc.protocol(),
                 |x| {
                     tx.send(x).ok();
                     StateModification::empty()
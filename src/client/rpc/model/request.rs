@@ -1,6 +1,6 @@
 use crate::client::{
-    rpc::{ExecutionLocationProvider, ParameterDefinitions, TagProvider, WeightKeyDataProvider},
     DecthingsParameterProvider,
+    rpc::{ExecutionLocationProvider, ParameterDefinitions, TagProvider, WeightKeyDataProvider},
 };
 use serde::Serialize;
 
@@ -160,7 +160,7 @@ pub struct CreateModelVersionParams<'a> {
 
 #[derive(Debug, Clone, Serialize)]
 #[serde(rename_all = "camelCase")]
-pub struct CreateModelVersionUploadWeightsParams<'a, D: AsRef<[u8]>> {
+pub struct CreateModelVersionUploadWeightsParams<'a, D: Clone + Into<bytes::Bytes>> {
     /// The model's id.
     pub model_id: &'a str,
     /// The name of the version.
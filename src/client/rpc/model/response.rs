@@ -1,6 +1,6 @@
 use crate::{
-    client::rpc::{ExecutionLocation, LauncherSpec, ParameterDefinitions, Tag, WeightKeyData},
     client::DecthingsParameter,
+    client::rpc::{ExecutionLocation, LauncherSpec, ParameterDefinitions, Tag, WeightKeyData},
     tensor::OwnedDecthingsTensor,
 };
 use serde::{Deserialize, Serialize};
@@ -13,7 +13,7 @@ pub struct CreateModelResult {
 }
 
 #[derive(Debug, Clone, Deserialize)]
-#[serde(rename_all = "snake_case", tag = "code")]
+#[serde(rename_all = "snake_case", tag = "code", remote = "Self")]
 pub enum CreateModelError {
     NameAlreadyUsed,
     OrganizationNotFound,
@@ -31,14 +31,34 @@ pub enum CreateModelError {
         parameter_name: String,
         reason: String,
     },
+    /// An error code which is not known by this version of the client.
+    #[serde(skip)]
+    Unrecognized {
+        raw: serde_json::Value,
+    },
 }
 
+impl_decthings_error_code!(CreateModelError {
+    NameAlreadyUsed => "name_already_used", "name already used";
+    OrganizationNotFound => "organization_not_found", "organization not found";
+    AccessDenied => "access_denied", "access denied";
+    ModelNotFound => "model_not_found", "model not found";
+    ModelVersionNotFound => "model_version_not_found", "model version not found";
+    QuotaExceeded => "quota_exceeded", "quota exceeded";
+    ServerOverloaded => "server_overloaded", "server overloaded";
+    BadCredentials => "bad_credentials", "invalid API key";
+    TooManyRequests => "too_many_requests", "too many requests";
+    PaymentRequired => "payment_required", "payment required";
+    Unknown => "unknown", "unknown error";
+    InvalidParameter { parameter_name, reason } => "invalid_parameter", "parameter '{parameter_name}' invalid: {reason}";
+});
+
 #[derive(Debug, Clone, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct DeleteModelResult {}
 
 #[derive(Debug, Clone, Deserialize)]
-#[serde(rename_all = "snake_case", tag = "code")]
+#[serde(rename_all = "snake_case", tag = "code", remote = "Self")]
 pub enum DeleteModelError {
     ModelNotFound,
     AccessDenied,
@@ -51,14 +71,29 @@ pub enum DeleteModelError {
         parameter_name: String,
         reason: String,
     },
+    /// An error code which is not known by this version of the client.
+    #[serde(skip)]
+    Unrecognized {
+        raw: serde_json::Value,
+    },
 }
 
+impl_decthings_error_code!(DeleteModelError {
+    ModelNotFound => "model_not_found", "model not found";
+    AccessDenied => "access_denied", "access denied";
+    BadCredentials => "bad_credentials", "invalid API key";
+    TooManyRequests => "too_many_requests", "too many requests";
+    PaymentRequired => "payment_required", "payment required";
+    Unknown => "unknown", "unknown error";
+    InvalidParameter { parameter_name, reason } => "invalid_parameter", "parameter '{parameter_name}' invalid: {reason}";
+});
+
 #[derive(Debug, Clone, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct UpdateModelResult {}
 
 #[derive(Debug, Clone, Deserialize)]
-#[serde(rename_all = "snake_case", tag = "code")]
+#[serde(rename_all = "snake_case", tag = "code", remote = "Self")]
 pub enum UpdateModelError {
     ModelNotFound,
     AccessDenied,
@@ -72,10 +107,26 @@ pub enum UpdateModelError {
         parameter_name: String,
         reason: String,
     },
+    /// An error code which is not known by this version of the client.
+    #[serde(skip)]
+    Unrecognized {
+        raw: serde_json::Value,
+    },
 }
 
+impl_decthings_error_code!(UpdateModelError {
+    ModelNotFound => "model_not_found", "model not found";
+    AccessDenied => "access_denied", "access denied";
+    NameAlreadyUsed => "name_already_used", "name already used";
+    BadCredentials => "bad_credentials", "invalid API key";
+    TooManyRequests => "too_many_requests", "too many requests";
+    PaymentRequired => "payment_required", "payment required";
+    Unknown => "unknown", "unknown error";
+    InvalidParameter { parameter_name, reason } => "invalid_parameter", "parameter '{parameter_name}' invalid: {reason}";
+});
+
 #[derive(Debug, Clone, Deserialize)]
-#[serde(rename_all = "camelCase", tag = "type")]
+#[serde(rename_all = "camelCase", tag = "type", remote = "Self")]
 pub enum ModelOwner {
     #[serde(rename_all = "camelCase")]
     User { user_id: String, username: String },
@@ -84,17 +135,29 @@ pub enum ModelOwner {
         organization_id: String,
         organization_name: String,
     },
+    /// An owner which is not known by this version of the client.
+    #[serde(skip)]
+    Unrecognized { raw: serde_json::Value },
 }
 
+impl_deserialize_unrecognized!(ModelOwner, "type", ["user", "organization"]);
+
 #[derive(Debug, Clone, Deserialize)]
-#[serde(rename_all = "camelCase")]
+#[serde(rename_all = "camelCase", remote = "Self")]
 pub enum ModelAccess {
     Read,
     Readwrite,
+    /// An access level which is not known by this version of the client.
+    #[serde(skip)]
+    Unrecognized {
+        raw: serde_json::Value,
+    },
 }
 
+impl_deserialize_unrecognized!(ModelAccess, ["read", "readwrite"]);
+
 #[derive(Debug, Clone, Deserialize)]
-#[serde(rename_all = "camelCase", tag = "type")]
+#[serde(rename_all = "camelCase", tag = "type", remote = "Self")]
 pub enum ModelSource {
     #[serde(rename_all = "camelCase")]
     Code {
@@ -110,8 +173,13 @@ pub enum ModelSource {
         model_id: String,
         version_id: String,
     },
+    /// A source which is not known by this version of the client.
+    #[serde(skip)]
+    Unrecognized { raw: serde_json::Value },
 }
 
+impl_deserialize_unrecognized!(ModelSource, "type", ["code", "model"]);
+
 #[derive(Debug, Clone, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct DefaultLauncherSpecs {
@@ -162,7 +230,7 @@ pub struct WeightKey {
 }
 
 #[derive(Debug, Clone, Deserialize)]
-#[serde(rename_all = "camelCase", tag = "state")]
+#[serde(rename_all = "camelCase", tag = "state", remote = "Self")]
 pub enum VersionStatus {
     InitializingWeights,
     #[serde(rename_all = "camelCase")]
@@ -173,8 +241,19 @@ pub enum VersionStatus {
     Created {
         weights: Vec<WeightKey>,
     },
+    /// A state which is not known by this version of the client.
+    #[serde(skip)]
+    Unrecognized {
+        raw: serde_json::Value,
+    },
 }
 
+impl_deserialize_unrecognized!(
+    VersionStatus,
+    "state",
+    ["initializingWeights", "training", "created"]
+);
+
 #[derive(Debug, Clone, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct ModelVersion {
@@ -215,7 +294,7 @@ pub struct GetModelsResult {
 }
 
 #[derive(Debug, Clone, Deserialize)]
-#[serde(rename_all = "snake_case", tag = "code")]
+#[serde(rename_all = "snake_case", tag = "code", remote = "Self")]
 pub enum GetModelsError {
     BadCredentials,
     TooManyRequests,
@@ -226,14 +305,27 @@ pub enum GetModelsError {
         parameter_name: String,
         reason: String,
     },
+    /// An error code which is not known by this version of the client.
+    #[serde(skip)]
+    Unrecognized {
+        raw: serde_json::Value,
+    },
 }
 
+impl_decthings_error_code!(GetModelsError {
+    BadCredentials => "bad_credentials", "invalid API key";
+    TooManyRequests => "too_many_requests", "too many requests";
+    PaymentRequired => "payment_required", "payment required";
+    Unknown => "unknown", "unknown error";
+    InvalidParameter { parameter_name, reason } => "invalid_parameter", "parameter '{parameter_name}' invalid: {reason}";
+});
+
 #[derive(Debug, Clone, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct SetFilesystemSizeResult {}
 
 #[derive(Debug, Clone, Deserialize)]
-#[serde(rename_all = "snake_case", tag = "code")]
+#[serde(rename_all = "snake_case", tag = "code", remote = "Self")]
 pub enum SetFilesystemSizeError {
     ModelNotFound,
     InvalidModelSourceType,
@@ -250,8 +342,27 @@ pub enum SetFilesystemSizeError {
         parameter_name: String,
         reason: String,
     },
+    /// An error code which is not known by this version of the client.
+    #[serde(skip)]
+    Unrecognized {
+        raw: serde_json::Value,
+    },
 }
 
+impl_decthings_error_code!(SetFilesystemSizeError {
+    ModelNotFound => "model_not_found", "model not found";
+    InvalidModelSourceType => "invalid_model_source_type", "invalid model source type";
+    NotEnoughSpace => "not_enough_space", "not enough space";
+    AccessDenied => "access_denied", "access denied";
+    QuotaExceeded => "quota_exceeded", "quota exceeded";
+    ServerOverloaded => "server_overloaded", "server overloaded";
+    BadCredentials => "bad_credentials", "invalid API key";
+    TooManyRequests => "too_many_requests", "too many requests";
+    PaymentRequired => "payment_required", "payment required";
+    Unknown => "unknown", "unknown error";
+    InvalidParameter { parameter_name, reason } => "invalid_parameter", "parameter '{parameter_name}' invalid: {reason}";
+});
+
 #[derive(Debug, Clone, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct InitializeWeightsDurations {
@@ -286,7 +397,7 @@ pub enum ModelFunction {
 }
 
 #[derive(Debug, Clone, Deserialize)]
-#[serde(rename_all = "snake_case", tag = "code")]
+#[serde(rename_all = "snake_case", tag = "code", remote = "Self")]
 pub enum InitializeWeightsFailedReason {
     Cancelled,
     InvalidExecutableFile,
@@ -309,8 +420,29 @@ pub enum InitializeWeightsFailedReason {
     },
     ServerOverloaded,
     Unknown,
+    /// A code which is not known by this version of the client.
+    #[serde(skip)]
+    Unrecognized {
+        raw: serde_json::Value,
+    },
 }
 
+impl_deserialize_unrecognized!(
+    InitializeWeightsFailedReason,
+    "code",
+    [
+        "cancelled",
+        "invalid_executable_file",
+        "read_executable_file_failed",
+        "launcher_terminated",
+        "max_duration_exceeded",
+        "code_terminated",
+        "exception",
+        "server_overloaded",
+        "unknown"
+    ]
+);
+
 #[derive(Debug, Clone, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct CreateModelVersionResult {
@@ -320,7 +452,7 @@ pub struct CreateModelVersionResult {
 }
 
 #[derive(Debug, Clone, Deserialize)]
-#[serde(rename_all = "snake_case", tag = "code")]
+#[serde(rename_all = "snake_case", tag = "code", remote = "Self")]
 pub enum CreateModelVersionError {
     ModelNotFound,
     AccessDenied,
@@ -351,8 +483,30 @@ pub enum CreateModelVersionError {
         parameter_name: String,
         reason: String,
     },
+    /// An error code which is not known by this version of the client.
+    #[serde(skip)]
+    Unrecognized {
+        raw: serde_json::Value,
+    },
 }
 
+impl_decthings_error_code!(CreateModelVersionError {
+    ModelNotFound => "model_not_found", "model not found";
+    AccessDenied => "access_denied", "access denied";
+    QuotaExceeded => "quota_exceeded", "quota exceeded";
+    ModelToMountNotFound => "model_to_mount_not_found", "model to mount not found";
+    VersionForModelToMountNotFound => "version_for_model_to_mount_not_found", "version of model to mount not found";
+    DatasetNotFound { dataset_id } => "dataset_not_found", "dataset '{dataset_id}' not found";
+    DatasetKeyNotFound { dataset_id, dataset_key } => "dataset_key_not_found", "key '{dataset_key}' not found in dataset '{dataset_id}'";
+    InitializeWeightsFailed => "initialize_weights_failed", "initializing weights failed";
+    ServerOverloaded => "server_overloaded", "server overloaded";
+    BadCredentials => "bad_credentials", "invalid API key";
+    TooManyRequests => "too_many_requests", "too many requests";
+    PaymentRequired => "payment_required", "payment required";
+    Unknown => "unknown", "unknown error";
+    InvalidParameter { parameter_name, reason } => "invalid_parameter", "parameter '{parameter_name}' invalid: {reason}";
+});
+
 #[derive(Debug, Clone, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct CreateModelVersionUploadWeightsResult {
@@ -361,7 +515,7 @@ pub struct CreateModelVersionUploadWeightsResult {
 }
 
 #[derive(Debug, Clone, Deserialize)]
-#[serde(rename_all = "snake_case", tag = "code")]
+#[serde(rename_all = "snake_case", tag = "code", remote = "Self")]
 pub enum CreateModelVersionUploadWeightsError {
     ModelNotFound,
     AccessDenied,
@@ -378,14 +532,33 @@ pub enum CreateModelVersionUploadWeightsError {
         parameter_name: String,
         reason: String,
     },
+    /// An error code which is not known by this version of the client.
+    #[serde(skip)]
+    Unrecognized {
+        raw: serde_json::Value,
+    },
 }
 
+impl_decthings_error_code!(CreateModelVersionUploadWeightsError {
+    ModelNotFound => "model_not_found", "model not found";
+    AccessDenied => "access_denied", "access denied";
+    QuotaExceeded => "quota_exceeded", "quota exceeded";
+    ModelToMountNotFound => "model_to_mount_not_found", "model to mount not found";
+    VersionForModelToMountNotFound => "version_for_model_to_mount_not_found", "version of model to mount not found";
+    ServerOverloaded => "server_overloaded", "server overloaded";
+    BadCredentials => "bad_credentials", "invalid API key";
+    TooManyRequests => "too_many_requests", "too many requests";
+    PaymentRequired => "payment_required", "payment required";
+    Unknown => "unknown", "unknown error";
+    InvalidParameter { parameter_name, reason } => "invalid_parameter", "parameter '{parameter_name}' invalid: {reason}";
+});
+
 #[derive(Debug, Clone, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct UpdateModelVersionResult {}
 
 #[derive(Debug, Clone, Deserialize)]
-#[serde(rename_all = "snake_case", tag = "code")]
+#[serde(rename_all = "snake_case", tag = "code", remote = "Self")]
 pub enum UpdateModelVersionError {
     ModelNotFound,
     ModelVersionNotFound,
@@ -399,8 +572,24 @@ pub enum UpdateModelVersionError {
         parameter_name: String,
         reason: String,
     },
+    /// An error code which is not known by this version of the client.
+    #[serde(skip)]
+    Unrecognized {
+        raw: serde_json::Value,
+    },
 }
 
+impl_decthings_error_code!(UpdateModelVersionError {
+    ModelNotFound => "model_not_found", "model not found";
+    ModelVersionNotFound => "model_version_not_found", "model version not found";
+    AccessDenied => "access_denied", "access denied";
+    BadCredentials => "bad_credentials", "invalid API key";
+    TooManyRequests => "too_many_requests", "too many requests";
+    PaymentRequired => "payment_required", "payment required";
+    Unknown => "unknown", "unknown error";
+    InvalidParameter { parameter_name, reason } => "invalid_parameter", "parameter '{parameter_name}' invalid: {reason}";
+});
+
 #[derive(Debug, Clone, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct GetWeightsResult {
@@ -408,8 +597,15 @@ pub struct GetWeightsResult {
     pub data: Vec<WeightKeyData>,
 }
 
+#[derive(Debug)]
+pub struct GetWeightsStreamedResult {
+    /// The weight keys. `data` contains one blob for each key, in the same order.
+    pub keys: Vec<String>,
+    pub data: crate::client::ResponseBlobs,
+}
+
 #[derive(Debug, Clone, Deserialize)]
-#[serde(rename_all = "snake_case", tag = "code")]
+#[serde(rename_all = "snake_case", tag = "code", remote = "Self")]
 pub enum GetWeightsError {
     ModelNotFound,
     ModelVersionNotFound,
@@ -423,14 +619,30 @@ pub enum GetWeightsError {
         parameter_name: String,
         reason: String,
     },
+    /// An error code which is not known by this version of the client.
+    #[serde(skip)]
+    Unrecognized {
+        raw: serde_json::Value,
+    },
 }
 
+impl_decthings_error_code!(GetWeightsError {
+    ModelNotFound => "model_not_found", "model not found";
+    ModelVersionNotFound => "model_version_not_found", "model version not found";
+    WeightKeyNotFound => "weight_key_not_found", "weight key not found";
+    BadCredentials => "bad_credentials", "invalid API key";
+    TooManyRequests => "too_many_requests", "too many requests";
+    PaymentRequired => "payment_required", "payment required";
+    Unknown => "unknown", "unknown error";
+    InvalidParameter { parameter_name, reason } => "invalid_parameter", "parameter '{parameter_name}' invalid: {reason}";
+});
+
 #[derive(Debug, Clone, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct DeleteModelVersionResult {}
 
 #[derive(Debug, Clone, Deserialize)]
-#[serde(rename_all = "snake_case", tag = "code")]
+#[serde(rename_all = "snake_case", tag = "code", remote = "Self")]
 pub enum DeleteModelVersionError {
     ModelNotFound,
     ModelVersionNotFound,
@@ -444,8 +656,24 @@ pub enum DeleteModelVersionError {
         parameter_name: String,
         reason: String,
     },
+    /// An error code which is not known by this version of the client.
+    #[serde(skip)]
+    Unrecognized {
+        raw: serde_json::Value,
+    },
 }
 
+impl_decthings_error_code!(DeleteModelVersionError {
+    ModelNotFound => "model_not_found", "model not found";
+    ModelVersionNotFound => "model_version_not_found", "model version not found";
+    AccessDenied => "access_denied", "access denied";
+    BadCredentials => "bad_credentials", "invalid API key";
+    TooManyRequests => "too_many_requests", "too many requests";
+    PaymentRequired => "payment_required", "payment required";
+    Unknown => "unknown", "unknown error";
+    InvalidParameter { parameter_name, reason } => "invalid_parameter", "parameter '{parameter_name}' invalid: {reason}";
+});
+
 #[derive(Debug, Clone, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct TrainResult {
@@ -454,7 +682,7 @@ pub struct TrainResult {
 }
 
 #[derive(Debug, Clone, Deserialize)]
-#[serde(rename_all = "snake_case", tag = "code")]
+#[serde(rename_all = "snake_case", tag = "code", remote = "Self")]
 pub enum TrainError {
     ModelNotFound,
     ModelVersionNotFound,
@@ -480,8 +708,29 @@ pub enum TrainError {
         parameter_name: String,
         reason: String,
     },
+    /// An error code which is not known by this version of the client.
+    #[serde(skip)]
+    Unrecognized {
+        raw: serde_json::Value,
+    },
 }
 
+impl_decthings_error_code!(TrainError {
+    ModelNotFound => "model_not_found", "model not found";
+    ModelVersionNotFound => "model_version_not_found", "model version not found";
+    PersistentLauncherNotFound => "persistent_launcher_not_found", "persistent launcher not found";
+    AccessDenied => "access_denied", "access denied";
+    QuotaExceeded => "quota_exceeded", "quota exceeded";
+    DatasetNotFound { dataset_id } => "dataset_not_found", "dataset '{dataset_id}' not found";
+    DatasetKeyNotFound { dataset_id, dataset_key } => "dataset_key_not_found", "key '{dataset_key}' not found in dataset '{dataset_id}'";
+    ModelToMountNoLongerExists => "model_to_mount_no_longer_exists", "model to mount no longer exists";
+    BadCredentials => "bad_credentials", "invalid API key";
+    TooManyRequests => "too_many_requests", "too many requests";
+    PaymentRequired => "payment_required", "payment required";
+    Unknown => "unknown", "unknown error";
+    InvalidParameter { parameter_name, reason } => "invalid_parameter", "parameter '{parameter_name}' invalid: {reason}";
+});
+
 #[derive(Debug, Clone, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct TrainMetric {
@@ -498,7 +747,7 @@ pub struct TrainingStartDurations {
 }
 
 #[derive(Debug, Clone, Deserialize)]
-#[serde(rename_all = "snake_case", tag = "code")]
+#[serde(rename_all = "snake_case", tag = "code", remote = "Self")]
 pub enum TrainingSessionFailReason {
     Cancelled,
     InvalidExecutableFile,
@@ -521,10 +770,31 @@ pub enum TrainingSessionFailReason {
     },
     ServerOverloaded,
     Unknown,
+    /// A code which is not known by this version of the client.
+    #[serde(skip)]
+    Unrecognized {
+        raw: serde_json::Value,
+    },
 }
 
-#[derive(Debug, Clone, Deserialize)]
-#[serde(rename_all = "camelCase", tag = "state")]
+impl_deserialize_unrecognized!(
+    TrainingSessionFailReason,
+    "code",
+    [
+        "cancelled",
+        "invalid_executable_file",
+        "read_executable_file_failed",
+        "launcher_terminated",
+        "max_duration_exceeded",
+        "code_terminated",
+        "exception",
+        "server_overloaded",
+        "unknown"
+    ]
+);
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase", tag = "state", remote = "Self")]
 pub enum TrainingStatus {
     Starting,
     #[serde(rename_all = "camelCase")]
@@ -553,8 +823,25 @@ pub enum TrainingStatus {
         finished_at: i64,
         fail_reason: TrainingSessionFailReason,
     },
+    /// A state which is not known by this version of the client.
+    #[serde(skip)]
+    Unrecognized {
+        raw: serde_json::Value,
+    },
 }
 
+impl_deserialize_unrecognized!(
+    TrainingStatus,
+    "state",
+    [
+        "starting",
+        "running",
+        "gettingWeights",
+        "completed",
+        "failed"
+    ]
+);
+
 #[derive(Debug, Clone, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct GetTrainingStatusResult {
@@ -569,7 +856,7 @@ pub struct GetTrainingStatusResult {
 }
 
 #[derive(Debug, Clone, Deserialize)]
-#[serde(rename_all = "snake_case", tag = "code")]
+#[serde(rename_all = "snake_case", tag = "code", remote = "Self")]
 pub enum GetTrainingStatusError {
     ModelNotFound,
     TrainingSessionNotFound,
@@ -582,8 +869,23 @@ pub enum GetTrainingStatusError {
         parameter_name: String,
         reason: String,
     },
+    /// An error code which is not known by this version of the client.
+    #[serde(skip)]
+    Unrecognized {
+        raw: serde_json::Value,
+    },
 }
 
+impl_decthings_error_code!(GetTrainingStatusError {
+    ModelNotFound => "model_not_found", "model not found";
+    TrainingSessionNotFound => "training_session_not_found", "training session not found";
+    BadCredentials => "bad_credentials", "invalid API key";
+    TooManyRequests => "too_many_requests", "too many requests";
+    PaymentRequired => "payment_required", "payment required";
+    Unknown => "unknown", "unknown error";
+    InvalidParameter { parameter_name, reason } => "invalid_parameter", "parameter '{parameter_name}' invalid: {reason}";
+});
+
 #[derive(Debug, Clone, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct FetchedTrainingMetricEntry {
@@ -607,7 +909,7 @@ pub struct GetTrainingMetricsResult {
 }
 
 #[derive(Debug, Clone, Deserialize)]
-#[serde(rename_all = "snake_case", tag = "code")]
+#[serde(rename_all = "snake_case", tag = "code", remote = "Self")]
 pub enum GetTrainingMetricsError {
     ModelNotFound,
     TrainingSessionNotFound,
@@ -620,8 +922,23 @@ pub enum GetTrainingMetricsError {
         parameter_name: String,
         reason: String,
     },
+    /// An error code which is not known by this version of the client.
+    #[serde(skip)]
+    Unrecognized {
+        raw: serde_json::Value,
+    },
 }
 
+impl_decthings_error_code!(GetTrainingMetricsError {
+    ModelNotFound => "model_not_found", "model not found";
+    TrainingSessionNotFound => "training_session_not_found", "training session not found";
+    BadCredentials => "bad_credentials", "invalid API key";
+    TooManyRequests => "too_many_requests", "too many requests";
+    PaymentRequired => "payment_required", "payment required";
+    Unknown => "unknown", "unknown error";
+    InvalidParameter { parameter_name, reason } => "invalid_parameter", "parameter '{parameter_name}' invalid: {reason}";
+});
+
 #[derive(Debug, Clone, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct SysinfoDataPoint {
@@ -638,7 +955,7 @@ pub struct GetTrainingSysinfoResult {
 }
 
 #[derive(Debug, Clone, Deserialize)]
-#[serde(rename_all = "snake_case", tag = "code")]
+#[serde(rename_all = "snake_case", tag = "code", remote = "Self")]
 pub enum GetTrainingSysinfoError {
     ModelNotFound,
     TrainingSessionNotFound,
@@ -651,14 +968,29 @@ pub enum GetTrainingSysinfoError {
         parameter_name: String,
         reason: String,
     },
+    /// An error code which is not known by this version of the client.
+    #[serde(skip)]
+    Unrecognized {
+        raw: serde_json::Value,
+    },
 }
 
+impl_decthings_error_code!(GetTrainingSysinfoError {
+    ModelNotFound => "model_not_found", "model not found";
+    TrainingSessionNotFound => "training_session_not_found", "training session not found";
+    BadCredentials => "bad_credentials", "invalid API key";
+    TooManyRequests => "too_many_requests", "too many requests";
+    PaymentRequired => "payment_required", "payment required";
+    Unknown => "unknown", "unknown error";
+    InvalidParameter { parameter_name, reason } => "invalid_parameter", "parameter '{parameter_name}' invalid: {reason}";
+});
+
 #[derive(Debug, Clone, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct CancelTrainingSessionResult {}
 
 #[derive(Debug, Clone, Deserialize)]
-#[serde(rename_all = "snake_case", tag = "code")]
+#[serde(rename_all = "snake_case", tag = "code", remote = "Self")]
 pub enum CancelTrainingSessionError {
     ModelNotFound,
     TrainingSessionNotFound,
@@ -673,14 +1005,31 @@ pub enum CancelTrainingSessionError {
         parameter_name: String,
         reason: String,
     },
+    /// An error code which is not known by this version of the client.
+    #[serde(skip)]
+    Unrecognized {
+        raw: serde_json::Value,
+    },
 }
 
+impl_decthings_error_code!(CancelTrainingSessionError {
+    ModelNotFound => "model_not_found", "model not found";
+    TrainingSessionNotFound => "training_session_not_found", "training session not found";
+    TrainingSessionNotRunning => "training_session_not_running", "training session is not running";
+    AccessDenied => "access_denied", "access denied";
+    BadCredentials => "bad_credentials", "invalid API key";
+    TooManyRequests => "too_many_requests", "too many requests";
+    PaymentRequired => "payment_required", "payment required";
+    Unknown => "unknown", "unknown error";
+    InvalidParameter { parameter_name, reason } => "invalid_parameter", "parameter '{parameter_name}' invalid: {reason}";
+});
+
 #[derive(Debug, Clone, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct ClearPreviousTrainingSessionResult {}
 
 #[derive(Debug, Clone, Deserialize)]
-#[serde(rename_all = "snake_case", tag = "code")]
+#[serde(rename_all = "snake_case", tag = "code", remote = "Self")]
 pub enum ClearPreviousTrainingSessionError {
     ModelNotFound,
     TrainingSessionNotFound,
@@ -695,8 +1044,25 @@ pub enum ClearPreviousTrainingSessionError {
         parameter_name: String,
         reason: String,
     },
+    /// An error code which is not known by this version of the client.
+    #[serde(skip)]
+    Unrecognized {
+        raw: serde_json::Value,
+    },
 }
 
+impl_decthings_error_code!(ClearPreviousTrainingSessionError {
+    ModelNotFound => "model_not_found", "model not found";
+    TrainingSessionNotFound => "training_session_not_found", "training session not found";
+    TrainingSessionRunning => "training_session_running", "training session is still running";
+    AccessDenied => "access_denied", "access denied";
+    BadCredentials => "bad_credentials", "invalid API key";
+    TooManyRequests => "too_many_requests", "too many requests";
+    PaymentRequired => "payment_required", "payment required";
+    Unknown => "unknown", "unknown error";
+    InvalidParameter { parameter_name, reason } => "invalid_parameter", "parameter '{parameter_name}' invalid: {reason}";
+});
+
 #[derive(Debug, Clone, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct EvaluateDurations {
@@ -725,7 +1091,7 @@ pub enum InvalidOutputType {
 }
 
 #[derive(Debug, Clone, Deserialize)]
-#[serde(rename_all = "snake_case", tag = "code")]
+#[serde(rename_all = "snake_case", tag = "code", remote = "Self")]
 pub enum EvaluateFailedReason {
     Cancelled,
     InvalidExecutableFile,
@@ -753,8 +1119,30 @@ pub enum EvaluateFailedReason {
         reason: InvalidOutputType,
         details: String,
     },
+    /// A code which is not known by this version of the client.
+    #[serde(skip)]
+    Unrecognized {
+        raw: serde_json::Value,
+    },
 }
 
+impl_deserialize_unrecognized!(
+    EvaluateFailedReason,
+    "code",
+    [
+        "cancelled",
+        "invalid_executable_file",
+        "read_executable_file_failed",
+        "launcher_terminated",
+        "max_duration_exceeded",
+        "code_terminated",
+        "exception",
+        "server_overloaded",
+        "unknown",
+        "invalid_output"
+    ]
+);
+
 #[derive(Debug, Clone, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct EvaluateResult {
@@ -764,7 +1152,7 @@ pub struct EvaluateResult {
 }
 
 #[derive(Debug, Clone, Deserialize)]
-#[serde(rename_all = "snake_case", tag = "code")]
+#[serde(rename_all = "snake_case", tag = "code", remote = "Self")]
 pub enum EvaluateError {
     ModelNotFound,
     ModelVersionNotFound,
@@ -782,7 +1170,7 @@ pub enum EvaluateError {
     #[serde(rename_all = "camelCase")]
     EvaluateFailed {
         durations: EvaluateFailedDurations,
-        executed_on_launcher: ExecutionLocation,
+        executed_on_launcher: Box<ExecutionLocation>,
         reason: EvaluateFailedReason,
     },
     BadCredentials,
@@ -794,8 +1182,28 @@ pub enum EvaluateError {
         parameter_name: String,
         reason: String,
     },
+    /// An error code which is not known by this version of the client.
+    #[serde(skip)]
+    Unrecognized {
+        raw: serde_json::Value,
+    },
 }
 
+impl_decthings_error_code!(EvaluateError {
+    ModelNotFound => "model_not_found", "model not found";
+    ModelVersionNotFound => "model_version_not_found", "model version not found";
+    QuotaExceeded => "quota_exceeded", "quota exceeded";
+    DatasetNotFound { dataset_id } => "dataset_not_found", "dataset '{dataset_id}' not found";
+    DatasetKeyNotFound { dataset_id, dataset_key } => "dataset_key_not_found", "key '{dataset_key}' not found in dataset '{dataset_id}'";
+    ModelToMountNoLongerExists => "model_to_mount_no_longer_exists", "model to mount no longer exists";
+    EvaluateFailed => "evaluate_failed", "evaluation failed";
+    BadCredentials => "bad_credentials", "invalid API key";
+    TooManyRequests => "too_many_requests", "too many requests";
+    PaymentRequired => "payment_required", "payment required";
+    Unknown => "unknown", "unknown error";
+    InvalidParameter { parameter_name, reason } => "invalid_parameter", "parameter '{parameter_name}' invalid: {reason}";
+});
+
 #[derive(Debug, Clone, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct RunningEvaluation {
@@ -822,7 +1230,7 @@ pub struct GetEvaluationsResult {
 }
 
 #[derive(Debug, Clone, Deserialize)]
-#[serde(rename_all = "snake_case", tag = "code")]
+#[serde(rename_all = "snake_case", tag = "code", remote = "Self")]
 pub enum GetEvaluationsError {
     ModelNotFound,
     BadCredentials,
@@ -834,8 +1242,22 @@ pub enum GetEvaluationsError {
         parameter_name: String,
         reason: String,
     },
+    /// An error code which is not known by this version of the client.
+    #[serde(skip)]
+    Unrecognized {
+        raw: serde_json::Value,
+    },
 }
 
+impl_decthings_error_code!(GetEvaluationsError {
+    ModelNotFound => "model_not_found", "model not found";
+    BadCredentials => "bad_credentials", "invalid API key";
+    TooManyRequests => "too_many_requests", "too many requests";
+    PaymentRequired => "payment_required", "payment required";
+    Unknown => "unknown", "unknown error";
+    InvalidParameter { parameter_name, reason } => "invalid_parameter", "parameter '{parameter_name}' invalid: {reason}";
+});
+
 #[derive(Debug, Clone, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct GetFinishedEvaluationResultResult {
@@ -845,14 +1267,14 @@ pub struct GetFinishedEvaluationResultResult {
 }
 
 #[derive(Debug, Clone, Deserialize)]
-#[serde(rename_all = "snake_case", tag = "code")]
+#[serde(rename_all = "snake_case", tag = "code", remote = "Self")]
 pub enum GetFinishedEvaluationResultError {
     ModelNotFound,
     EvaluationNotFound,
     #[serde(rename_all = "camelCase")]
     EvaluateFailed {
         durations: EvaluateFailedDurations,
-        executed_on_launcher: ExecutionLocation,
+        executed_on_launcher: Box<ExecutionLocation>,
         reason: EvaluateFailedReason,
     },
     BadCredentials,
@@ -864,14 +1286,30 @@ pub enum GetFinishedEvaluationResultError {
         parameter_name: String,
         reason: String,
     },
+    /// An error code which is not known by this version of the client.
+    #[serde(skip)]
+    Unrecognized {
+        raw: serde_json::Value,
+    },
 }
 
+impl_decthings_error_code!(GetFinishedEvaluationResultError {
+    ModelNotFound => "model_not_found", "model not found";
+    EvaluationNotFound => "evaluation_not_found", "evaluation not found";
+    EvaluateFailed => "evaluate_failed", "evaluation failed";
+    BadCredentials => "bad_credentials", "invalid API key";
+    TooManyRequests => "too_many_requests", "too many requests";
+    PaymentRequired => "payment_required", "payment required";
+    Unknown => "unknown", "unknown error";
+    InvalidParameter { parameter_name, reason } => "invalid_parameter", "parameter '{parameter_name}' invalid: {reason}";
+});
+
 #[derive(Debug, Clone, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct CancelEvaluationResult {}
 
 #[derive(Debug, Clone, Deserialize)]
-#[serde(rename_all = "snake_case", tag = "code")]
+#[serde(rename_all = "snake_case", tag = "code", remote = "Self")]
 pub enum CancelEvaluationError {
     ModelNotFound,
     EvaluationNotFound,
@@ -884,14 +1322,29 @@ pub enum CancelEvaluationError {
         parameter_name: String,
         reason: String,
     },
+    /// An error code which is not known by this version of the client.
+    #[serde(skip)]
+    Unrecognized {
+        raw: serde_json::Value,
+    },
 }
 
+impl_decthings_error_code!(CancelEvaluationError {
+    ModelNotFound => "model_not_found", "model not found";
+    EvaluationNotFound => "evaluation_not_found", "evaluation not found";
+    BadCredentials => "bad_credentials", "invalid API key";
+    TooManyRequests => "too_many_requests", "too many requests";
+    PaymentRequired => "payment_required", "payment required";
+    Unknown => "unknown", "unknown error";
+    InvalidParameter { parameter_name, reason } => "invalid_parameter", "parameter '{parameter_name}' invalid: {reason}";
+});
+
 #[derive(Debug, Clone, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct SetUsedPersistentLaunchersForEvaluateResult {}
 
 #[derive(Debug, Clone, Deserialize)]
-#[serde(rename_all = "snake_case", tag = "code")]
+#[serde(rename_all = "snake_case", tag = "code", remote = "Self")]
 pub enum SetUsedPersistentLaunchersForEvaluateError {
     PersistentLauncherNotFound,
     ModelNotFound,
@@ -906,14 +1359,50 @@ pub enum SetUsedPersistentLaunchersForEvaluateError {
         parameter_name: String,
         reason: String,
     },
+    /// An error code which is not known by this version of the client.
+    #[serde(skip)]
+    Unrecognized {
+        raw: serde_json::Value,
+    },
 }
 
+impl_decthings_error_code!(SetUsedPersistentLaunchersForEvaluateError {
+    PersistentLauncherNotFound => "persistent_launcher_not_found", "persistent launcher not found";
+    ModelNotFound => "model_not_found", "model not found";
+    ModelVersionNotFound => "model_version_not_found", "model version not found";
+    AccessDenied => "access_denied", "access denied";
+    BadCredentials => "bad_credentials", "invalid API key";
+    TooManyRequests => "too_many_requests", "too many requests";
+    PaymentRequired => "payment_required", "payment required";
+    Unknown => "unknown", "unknown error";
+    InvalidParameter { parameter_name, reason } => "invalid_parameter", "parameter '{parameter_name}' invalid: {reason}";
+});
+
 #[derive(Debug, Clone, Deserialize, Serialize)]
-#[serde(rename_all = "camelCase")]
+#[serde(rename_all = "camelCase", remote = "Self")]
 pub enum UsedPersistentLauncherLevel {
     Launcher,
     CodeStart,
     InstantiatedModel,
+    /// A level which is not known by this version of the client.
+    #[serde(skip)]
+    Unrecognized {
+        raw: serde_json::Value,
+    },
+}
+
+impl_deserialize_unrecognized!(
+    UsedPersistentLauncherLevel,
+    ["launcher", "codeStart", "instantiatedModel"]
+);
+
+impl Serialize for UsedPersistentLauncherLevel {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        match self {
+            Self::Unrecognized { raw } => raw.serialize(serializer),
+            _ => Self::serialize(self, serializer),
+        }
+    }
 }
 
 #[derive(Debug, Clone, Deserialize)]
@@ -930,7 +1419,7 @@ pub struct GetUsedPersistentLaunchersForEvaluateResult {
 }
 
 #[derive(Debug, Clone, Deserialize)]
-#[serde(rename_all = "snake_case", tag = "code")]
+#[serde(rename_all = "snake_case", tag = "code", remote = "Self")]
 pub enum GetUsedPersistentLaunchersForEvaluateError {
     ModelNotFound,
     ModelVersionNotFound,
@@ -943,4 +1432,19 @@ pub enum GetUsedPersistentLaunchersForEvaluateError {
         parameter_name: String,
         reason: String,
     },
+    /// An error code which is not known by this version of the client.
+    #[serde(skip)]
+    Unrecognized {
+        raw: serde_json::Value,
+    },
 }
+
+impl_decthings_error_code!(GetUsedPersistentLaunchersForEvaluateError {
+    ModelNotFound => "model_not_found", "model not found";
+    ModelVersionNotFound => "model_version_not_found", "model version not found";
+    BadCredentials => "bad_credentials", "invalid API key";
+    TooManyRequests => "too_many_requests", "too many requests";
+    PaymentRequired => "payment_required", "payment required";
+    Unknown => "unknown", "unknown error";
+    InvalidParameter { parameter_name, reason } => "invalid_parameter", "parameter '{parameter_name}' invalid: {reason}";
+});
@@ -15,6 +15,31 @@ impl PersistentLauncherRpc {
         Self { rpc }
     }
 
+    /// Returns a copy of this API which uses the given timeout for each request, instead of
+    /// `DecthingsClientOptions::timeout`. Pass None to wait without a timeout.
+    pub fn with_timeout(&self, timeout: Option<std::time::Duration>) -> Self {
+        Self {
+            rpc: self.rpc.with_timeout(timeout),
+        }
+    }
+
+    /// Returns a copy of this API which retries requests according to the given policy, instead
+    /// of `DecthingsClientOptions::retry_policy`. Pass None to never retry.
+    pub fn with_retry_policy(&self, retry_policy: Option<crate::client::RetryPolicy>) -> Self {
+        Self {
+            rpc: self.rpc.with_retry_policy(retry_policy),
+        }
+    }
+
+    /// Returns a copy of this API which sends requests using the given protocol, instead of
+    /// `DecthingsClientOptions::default_protocol`. Methods which subscribe to events always use
+    /// WebSocket.
+    pub fn with_protocol(&self, protocol: crate::client::RpcProtocol) -> Self {
+        Self {
+            rpc: self.rpc.with_protocol(protocol),
+        }
+    }
+
     pub async fn create_persistent_launcher(
         &self,
         params: CreatePersistentLauncherParams<'_>,
@@ -24,12 +49,12 @@ impl PersistentLauncherRpc {
     > {
         let (tx, rx) = tokio::sync::oneshot::channel();
         self.rpc
-            .raw_method_call::<_, _, &[u8]>(
+            .raw_method_call(
                 "PersistentLauncher",
                 "createPersistentLauncher",
                 params,
-                &[],
-                crate::client::RpcProtocol::Http,
+                vec![],
+                self.rpc.protocol(),
                 |x| {
                     tx.send(x).ok();
                     StateModification::empty()
@@ -60,12 +85,12 @@ impl PersistentLauncherRpc {
     > {
         let (tx, rx) = tokio::sync::oneshot::channel();
         self.rpc
-            .raw_method_call::<_, _, &[u8]>(
+            .raw_method_call(
                 "PersistentLaunchers",
                 "getPersistentLaunchers",
                 params,
-                &[],
-                crate::client::RpcProtocol::Http,
+                vec![],
+                self.rpc.protocol(),
                 |x| {
                     tx.send(x).ok();
                     StateModification::empty()
@@ -93,12 +118,12 @@ impl PersistentLauncherRpc {
     ) -> Result<GetSysinfoResult, crate::client::DecthingsRpcError<GetSysinfoError>> {
         let (tx, rx) = tokio::sync::oneshot::channel();
         self.rpc
-            .raw_method_call::<_, _, &[u8]>(
+            .raw_method_call(
                 "PersistentLaunchers",
                 "getSysinfo",
                 params,
-                &[],
-                crate::client::RpcProtocol::Http,
+                vec![],
+                self.rpc.protocol(),
                 |x| {
                     tx.send(x).ok();
                     StateModification::empty()
@@ -127,12 +152,12 @@ impl PersistentLauncherRpc {
     > {
         let (tx, rx) = tokio::sync::oneshot::channel();
         self.rpc
-            .raw_method_call::<_, _, &[u8]>(
+            .raw_method_call(
                 "PersistentLaunchers",
                 "deletePersistentLauncher",
                 params,
-                &[],
-                crate::client::RpcProtocol::Http,
+                vec![],
+                self.rpc.protocol(),
                 |x| {
                     tx.send(x).ok();
                     StateModification::empty()
@@ -9,7 +9,7 @@ pub struct CreatePersistentLauncherResult {
 }
 
 #[derive(Debug, Clone, Deserialize)]
-#[serde(rename_all = "snake_case", tag = "code")]
+#[serde(rename_all = "snake_case", tag = "code", remote = "Self")]
 pub enum CreatePersistentLauncherError {
     QuotaExceeded,
     BadCredentials,
@@ -21,17 +21,38 @@ pub enum CreatePersistentLauncherError {
         parameter_name: String,
         reason: String,
     },
+    /// An error code which is not known by this version of the client.
+    #[serde(skip)]
+    Unrecognized {
+        raw: serde_json::Value,
+    },
 }
 
+impl_decthings_error_code!(CreatePersistentLauncherError {
+    QuotaExceeded => "quota_exceeded", "quota exceeded";
+    BadCredentials => "bad_credentials", "invalid API key";
+    TooManyRequests => "too_many_requests", "too many requests";
+    PaymentRequired => "payment_required", "payment required";
+    Unknown => "unknown", "unknown error";
+    InvalidParameter { parameter_name, reason } => "invalid_parameter", "parameter '{parameter_name}' invalid: {reason}";
+});
+
 #[derive(Debug, Clone, Deserialize)]
-#[serde(rename_all = "snake_case")]
+#[serde(rename_all = "snake_case", remote = "Self")]
 pub enum PersistentLauncherPreviousState {
     Exit,
     Unknown,
+    /// A state which is not known by this version of the client.
+    #[serde(skip)]
+    Unrecognized {
+        raw: serde_json::Value,
+    },
 }
 
+impl_deserialize_unrecognized!(PersistentLauncherPreviousState, ["exit", "unknown"]);
+
 #[derive(Debug, Clone, Deserialize)]
-#[serde(rename_all = "camelCase", tag = "type")]
+#[serde(rename_all = "camelCase", tag = "type", remote = "Self")]
 pub enum PersistentLauncherState {
     Creating,
     Active,
@@ -40,18 +61,39 @@ pub enum PersistentLauncherState {
     Recreating {
         previous: PersistentLauncherPreviousState,
     },
+    /// A type which is not known by this version of the client.
+    #[serde(skip)]
+    Unrecognized {
+        raw: serde_json::Value,
+    },
 }
 
+impl_deserialize_unrecognized!(
+    PersistentLauncherState,
+    "type",
+    ["creating", "active", "deleting", "recreating"]
+);
+
 #[derive(Debug, Clone, Deserialize)]
-#[serde(rename_all = "camelCase")]
+#[serde(rename_all = "camelCase", remote = "Self")]
 pub enum PersistentLauncherRunningType {
     Terminal,
     Spawned,
     Debug,
     Train,
     Evaluate,
+    /// A type which is not known by this version of the client.
+    #[serde(skip)]
+    Unrecognized {
+        raw: serde_json::Value,
+    },
 }
 
+impl_deserialize_unrecognized!(
+    PersistentLauncherRunningType,
+    ["terminal", "spawned", "debug", "train", "evaluate"]
+);
+
 #[derive(Debug, Clone, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct PersistentLauncherRunning {
@@ -81,7 +123,7 @@ pub struct GetPersistentLaunchersResult {
 }
 
 #[derive(Debug, Clone, Deserialize)]
-#[serde(rename_all = "snake_case", tag = "code")]
+#[serde(rename_all = "snake_case", tag = "code", remote = "Self")]
 pub enum GetPersistentLaunchersError {
     BadCredentials,
     TooManyRequests,
@@ -92,8 +134,21 @@ pub enum GetPersistentLaunchersError {
         parameter_name: String,
         reason: String,
     },
+    /// An error code which is not known by this version of the client.
+    #[serde(skip)]
+    Unrecognized {
+        raw: serde_json::Value,
+    },
 }
 
+impl_decthings_error_code!(GetPersistentLaunchersError {
+    BadCredentials => "bad_credentials", "invalid API key";
+    TooManyRequests => "too_many_requests", "too many requests";
+    PaymentRequired => "payment_required", "payment required";
+    Unknown => "unknown", "unknown error";
+    InvalidParameter { parameter_name, reason } => "invalid_parameter", "parameter '{parameter_name}' invalid: {reason}";
+});
+
 #[derive(Debug, Clone, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct SysinfoDataPoint {
@@ -110,7 +165,7 @@ pub struct GetSysinfoResult {
 }
 
 #[derive(Debug, Clone, Deserialize)]
-#[serde(rename_all = "snake_case", tag = "code")]
+#[serde(rename_all = "snake_case", tag = "code", remote = "Self")]
 pub enum GetSysinfoError {
     PersistentLauncherNotFound,
     BadCredentials,
@@ -122,14 +177,28 @@ pub enum GetSysinfoError {
         parameter_name: String,
         reason: String,
     },
+    /// An error code which is not known by this version of the client.
+    #[serde(skip)]
+    Unrecognized {
+        raw: serde_json::Value,
+    },
 }
 
+impl_decthings_error_code!(GetSysinfoError {
+    PersistentLauncherNotFound => "persistent_launcher_not_found", "persistent launcher not found";
+    BadCredentials => "bad_credentials", "invalid API key";
+    TooManyRequests => "too_many_requests", "too many requests";
+    PaymentRequired => "payment_required", "payment required";
+    Unknown => "unknown", "unknown error";
+    InvalidParameter { parameter_name, reason } => "invalid_parameter", "parameter '{parameter_name}' invalid: {reason}";
+});
+
 #[derive(Debug, Clone, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct DeletePersistentLauncherResult {}
 
 #[derive(Debug, Clone, Deserialize)]
-#[serde(rename_all = "snake_case", tag = "code")]
+#[serde(rename_all = "snake_case", tag = "code", remote = "Self")]
 pub enum DeletePersistentLauncherError {
     PersistentLauncherNotFound,
     PersistentLauncherBeingDeleted,
@@ -142,4 +211,19 @@ pub enum DeletePersistentLauncherError {
         parameter_name: String,
         reason: String,
     },
+    /// An error code which is not known by this version of the client.
+    #[serde(skip)]
+    Unrecognized {
+        raw: serde_json::Value,
+    },
 }
+
+impl_decthings_error_code!(DeletePersistentLauncherError {
+    PersistentLauncherNotFound => "persistent_launcher_not_found", "persistent launcher not found";
+    PersistentLauncherBeingDeleted => "persistent_launcher_being_deleted", "persistent launcher is being deleted";
+    BadCredentials => "bad_credentials", "invalid API key";
+    TooManyRequests => "too_many_requests", "too many requests";
+    PaymentRequired => "payment_required", "payment required";
+    Unknown => "unknown", "unknown error";
+    InvalidParameter { parameter_name, reason } => "invalid_parameter", "parameter '{parameter_name}' invalid: {reason}";
+});
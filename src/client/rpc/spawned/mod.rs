@@ -15,6 +15,31 @@ impl SpawnedRpc {
         Self { rpc }
     }
 
+    /// Returns a copy of this API which uses the given timeout for each request, instead of
+    /// `DecthingsClientOptions::timeout`. Pass None to wait without a timeout.
+    pub fn with_timeout(&self, timeout: Option<std::time::Duration>) -> Self {
+        Self {
+            rpc: self.rpc.with_timeout(timeout),
+        }
+    }
+
+    /// Returns a copy of this API which retries requests according to the given policy, instead
+    /// of `DecthingsClientOptions::retry_policy`. Pass None to never retry.
+    pub fn with_retry_policy(&self, retry_policy: Option<crate::client::RetryPolicy>) -> Self {
+        Self {
+            rpc: self.rpc.with_retry_policy(retry_policy),
+        }
+    }
+
+    /// Returns a copy of this API which sends requests using the given protocol, instead of
+    /// `DecthingsClientOptions::default_protocol`. Methods which subscribe to events always use
+    /// WebSocket.
+    pub fn with_protocol(&self, protocol: crate::client::RpcProtocol) -> Self {
+        Self {
+            rpc: self.rpc.with_protocol(protocol),
+        }
+    }
+
     pub async fn spawn_command(
         &self,
         params: SpawnCommandParams<'_, impl AsRef<str>>,
@@ -26,19 +51,19 @@ impl SpawnedRpc {
         let protocol = if subscribe_to_events {
             crate::client::RpcProtocol::Ws
         } else {
-            crate::client::RpcProtocol::Http
+            self.rpc.protocol()
         };
 
         #[cfg(not(feature = "events"))]
-        let protocol = crate::client::RpcProtocol::Http;
+        let protocol = self.rpc.protocol();
 
         let (tx, rx) = tokio::sync::oneshot::channel();
         self.rpc
-            .raw_method_call::<_, _, &[u8]>(
+            .raw_method_call(
                 "Spawned",
                 "spawnCommand",
                 params,
-                &[],
+                vec![],
                 protocol,
                 move |x| {
                     match x {
@@ -57,7 +82,7 @@ impl SpawnedRpc {
                                     #[cfg(feature = "events")]
                                     if subscribe_to_events {
                                         return StateModification {
-                                            add_events: vec![spawned_command_id],
+                                            add_events: vec![("Spawned", spawned_command_id)],
                                             remove_events: vec![],
                                         };
                                     }
@@ -96,19 +121,19 @@ impl SpawnedRpc {
         let protocol = if subscribe_to_events {
             crate::client::RpcProtocol::Ws
         } else {
-            crate::client::RpcProtocol::Http
+            self.rpc.protocol()
         };
 
         #[cfg(not(feature = "events"))]
-        let protocol = crate::client::RpcProtocol::Http;
+        let protocol = self.rpc.protocol();
 
         let (tx, rx) = tokio::sync::oneshot::channel();
         self.rpc
-            .raw_method_call::<_, _, &[u8]>(
+            .raw_method_call(
                 "Spawned",
                 "spawnCommandForModel",
                 params,
-                &[],
+                vec![],
                 protocol,
                 move |x| {
                     match x {
@@ -130,7 +155,7 @@ impl SpawnedRpc {
                                     #[cfg(feature = "events")]
                                     if subscribe_to_events {
                                         return StateModification {
-                                            add_events: vec![spawned_command_id],
+                                            add_events: vec![("Spawned", spawned_command_id)],
                                             remove_events: vec![],
                                         };
                                     }
@@ -167,12 +192,12 @@ impl SpawnedRpc {
 
         let (tx, rx) = tokio::sync::oneshot::channel();
         self.rpc
-            .raw_method_call::<_, _, &[u8]>(
+            .raw_method_call(
                 "Spawned",
                 "terminateSpawnedCommand",
                 params,
-                &[],
-                crate::client::RpcProtocol::Http,
+                vec![],
+                self.rpc.protocol(),
                 move |x| {
                     match x {
                         Ok(val) => {
@@ -220,12 +245,12 @@ impl SpawnedRpc {
     {
         let (tx, rx) = tokio::sync::oneshot::channel();
         self.rpc
-            .raw_method_call::<_, _, &[u8]>(
+            .raw_method_call(
                 "Spawned",
                 "getSpawnedCommands",
                 params,
-                &[],
-                crate::client::RpcProtocol::Http,
+                vec![],
+                self.rpc.protocol(),
                 |x| {
                     tx.send(x).ok();
                     StateModification::empty()
@@ -247,19 +272,27 @@ impl SpawnedRpc {
 
     pub async fn write_to_spawned_command(
         &self,
-        params: WriteToSpawnedCommandParams<'_, impl AsRef<[u8]>>,
+        params: WriteToSpawnedCommandParams<'_, impl Into<bytes::Bytes>>,
     ) -> Result<
         WriteToSpawnedCommandResult,
         crate::client::DecthingsRpcError<WriteToSpawnedCommandError>,
     > {
+        let WriteToSpawnedCommandParams {
+            spawned_command_id,
+            data,
+        } = params;
+        let params = WriteToSpawnedCommandParams {
+            spawned_command_id,
+            data: bytes::Bytes::new(),
+        };
         let (tx, rx) = tokio::sync::oneshot::channel();
         self.rpc
             .raw_method_call(
                 "Spawned",
                 "writeToSpawnedCommand",
                 &params,
-                &[&params.data],
-                crate::client::RpcProtocol::Http,
+                vec![data.into()],
+                self.rpc.protocol(),
                 |x| {
                     tx.send(x).ok();
                     StateModification::empty()
@@ -279,6 +312,20 @@ impl SpawnedRpc {
             })
     }
 
+    /// Returns a stream of the events of the spawned command `spawned_command_id`, which ends after the Exit
+    /// event. See [`EventStream`](crate::client::event::EventStream).
+    ///
+    /// Only events received after this call are included. To not miss any, pass
+    /// `subscribe_to_events: Some(false)` to `spawn_command`, create the stream and then call
+    /// `subscribe_to_events`.
+    #[cfg(feature = "events")]
+    pub fn events(
+        &self,
+        spawned_command_id: impl Into<String>,
+    ) -> crate::client::event::EventStream<SpawnedEvent> {
+        self.rpc.event_stream(spawned_command_id.into())
+    }
+
     #[cfg(feature = "events")]
     pub async fn subscribe_to_events(
         &self,
@@ -290,11 +337,11 @@ impl SpawnedRpc {
         let (tx, rx) = tokio::sync::oneshot::channel();
         let spawned_command_id_owned = params.spawned_command_id.to_owned();
         self.rpc
-            .raw_method_call::<_, _, &[u8]>(
+            .raw_method_call(
                 "Spawned",
                 "subscribeToEvents",
                 params,
-                &[],
+                vec![],
                 crate::client::RpcProtocol::Ws,
                 move |x| {
                     match x {
@@ -310,7 +357,7 @@ impl SpawnedRpc {
                                 Ok(super::Response::Result(val)) => {
                                     tx.send(Ok(val)).ok();
                                     return StateModification {
-                                        add_events: vec![spawned_command_id_owned],
+                                        add_events: vec![("Spawned", spawned_command_id_owned)],
                                         remove_events: vec![],
                                     };
                                 }
@@ -346,11 +393,11 @@ impl SpawnedRpc {
         let spawned_command_id_owned = params.spawned_command_id.to_owned();
         let did_call = self
             .rpc
-            .raw_method_call::<_, _, &[u8]>(
+            .raw_method_call(
                 "Spawned",
                 "unsubscribeFromEvents",
                 params,
-                &[],
+                vec![],
                 crate::client::RpcProtocol::WsIfAvailableOtherwiseNone,
                 move |x| {
                     match x {
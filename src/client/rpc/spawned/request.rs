@@ -78,9 +78,10 @@ pub struct GetSpawnedCommandsParams<'a, S: AsRef<str>> {
 
 #[derive(Debug, Clone, Serialize)]
 #[serde(rename_all = "camelCase")]
-pub struct WriteToSpawnedCommandParams<'a, T: AsRef<[u8]>> {
+pub struct WriteToSpawnedCommandParams<'a, T: Into<bytes::Bytes>> {
     /// The spawned command's id.
     pub spawned_command_id: &'a str,
+    /// Data to write. Pass `bytes::Bytes` to avoid copying it.
     #[serde(skip_serializing)]
     pub data: T,
 }
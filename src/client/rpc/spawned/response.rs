@@ -3,7 +3,7 @@ use serde::Deserialize;
 
 #[cfg(feature = "events")]
 #[derive(Debug, Clone, Deserialize)]
-#[serde(rename_all = "snake_case", tag = "code")]
+#[serde(rename_all = "snake_case", tag = "code", remote = "Self")]
 pub enum SpawnedCommandTerminatedReason {
     TerminatedOnRequest,
     LauncherTerminated,
@@ -15,11 +15,34 @@ pub enum SpawnedCommandTerminatedReason {
         signal: Option<String>,
         oom: bool,
     },
+    /// A reason which is not known by this version of the client.
+    #[serde(skip)]
+    Unrecognized {
+        raw: serde_json::Value,
+    },
 }
 
+#[cfg(feature = "events")]
+impl_deserialize_unrecognized!(
+    SpawnedCommandTerminatedReason,
+    "code",
+    [
+        "terminated_on_request",
+        "launcher_terminated",
+        "inactive_timeout",
+        "unknown",
+        "process_exit"
+    ]
+);
+
 #[cfg(feature = "events")]
 #[derive(Debug, Clone, Deserialize)]
-#[serde(rename_all = "camelCase", tag = "event", content = "params")]
+#[serde(
+    rename_all = "camelCase",
+    tag = "event",
+    content = "params",
+    remote = "Self"
+)]
 pub enum SpawnedEvent {
     #[serde(rename_all = "camelCase")]
     Exit {
@@ -38,8 +61,14 @@ pub enum SpawnedEvent {
         #[serde(skip_deserializing)]
         data: bytes::Bytes,
     },
+    /// An event which is not known by this version of the client.
+    #[serde(skip)]
+    Unrecognized { raw: serde_json::Value },
 }
 
+#[cfg(feature = "events")]
+impl_deserialize_unrecognized!(SpawnedEvent, "event", ["exit", "stdout", "stderr"]);
+
 #[derive(Debug, Clone, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct SpawnCommandResult {
@@ -48,7 +77,7 @@ pub struct SpawnCommandResult {
 }
 
 #[derive(Debug, Clone, Deserialize)]
-#[serde(rename_all = "snake_case", tag = "code")]
+#[serde(rename_all = "snake_case", tag = "code", remote = "Self")]
 pub enum SpawnCommandError {
     PersistentLauncherNotFound,
     QuotaExceeded,
@@ -62,8 +91,24 @@ pub enum SpawnCommandError {
         parameter_name: String,
         reason: String,
     },
+    /// An error code which is not known by this version of the client.
+    #[serde(skip)]
+    Unrecognized {
+        raw: serde_json::Value,
+    },
 }
 
+impl_decthings_error_code!(SpawnCommandError {
+    PersistentLauncherNotFound => "persistent_launcher_not_found", "persistent launcher not found";
+    QuotaExceeded => "quota_exceeded", "quota exceeded";
+    ServerOverloaded => "server_overloaded", "server overloaded";
+    BadCredentials => "bad_credentials", "invalid API key";
+    TooManyRequests => "too_many_requests", "too many requests";
+    PaymentRequired => "payment_required", "payment required";
+    Unknown => "unknown", "unknown error";
+    InvalidParameter { parameter_name, reason } => "invalid_parameter", "parameter '{parameter_name}' invalid: {reason}";
+});
+
 #[derive(Debug, Clone, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct SpawnCommandForModelResult {
@@ -72,7 +117,7 @@ pub struct SpawnCommandForModelResult {
 }
 
 #[derive(Debug, Clone, Deserialize)]
-#[serde(rename_all = "snake_case", tag = "code")]
+#[serde(rename_all = "snake_case", tag = "code", remote = "Self")]
 pub enum SpawnCommandForModelError {
     ModelNotFound,
     PersistentLauncherNotFound,
@@ -87,14 +132,31 @@ pub enum SpawnCommandForModelError {
         parameter_name: String,
         reason: String,
     },
+    /// An error code which is not known by this version of the client.
+    #[serde(skip)]
+    Unrecognized {
+        raw: serde_json::Value,
+    },
 }
 
+impl_decthings_error_code!(SpawnCommandForModelError {
+    ModelNotFound => "model_not_found", "model not found";
+    PersistentLauncherNotFound => "persistent_launcher_not_found", "persistent launcher not found";
+    QuotaExceeded => "quota_exceeded", "quota exceeded";
+    ServerOverloaded => "server_overloaded", "server overloaded";
+    BadCredentials => "bad_credentials", "invalid API key";
+    TooManyRequests => "too_many_requests", "too many requests";
+    PaymentRequired => "payment_required", "payment required";
+    Unknown => "unknown", "unknown error";
+    InvalidParameter { parameter_name, reason } => "invalid_parameter", "parameter '{parameter_name}' invalid: {reason}";
+});
+
 #[derive(Debug, Clone, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct TerminateSpawnedCommandResult {}
 
 #[derive(Debug, Clone, Deserialize)]
-#[serde(rename_all = "snake_case", tag = "code")]
+#[serde(rename_all = "snake_case", tag = "code", remote = "Self")]
 pub enum TerminateSpawnedCommandError {
     SpawnedCommandNotFound,
     BadCredentials,
@@ -106,8 +168,22 @@ pub enum TerminateSpawnedCommandError {
         parameter_name: String,
         reason: String,
     },
+    /// An error code which is not known by this version of the client.
+    #[serde(skip)]
+    Unrecognized {
+        raw: serde_json::Value,
+    },
 }
 
+impl_decthings_error_code!(TerminateSpawnedCommandError {
+    SpawnedCommandNotFound => "spawned_command_not_found", "spawned command not found";
+    BadCredentials => "bad_credentials", "invalid API key";
+    TooManyRequests => "too_many_requests", "too many requests";
+    PaymentRequired => "payment_required", "payment required";
+    Unknown => "unknown", "unknown error";
+    InvalidParameter { parameter_name, reason } => "invalid_parameter", "parameter '{parameter_name}' invalid: {reason}";
+});
+
 #[derive(Debug, Clone, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct SpawnedCommand {
@@ -124,7 +200,7 @@ pub struct GetSpawnedCommandsResult {
 }
 
 #[derive(Debug, Clone, Deserialize)]
-#[serde(rename_all = "snake_case", tag = "code")]
+#[serde(rename_all = "snake_case", tag = "code", remote = "Self")]
 pub enum GetSpawnedCommandsError {
     BadCredentials,
     TooManyRequests,
@@ -135,14 +211,27 @@ pub enum GetSpawnedCommandsError {
         parameter_name: String,
         reason: String,
     },
+    /// An error code which is not known by this version of the client.
+    #[serde(skip)]
+    Unrecognized {
+        raw: serde_json::Value,
+    },
 }
 
+impl_decthings_error_code!(GetSpawnedCommandsError {
+    BadCredentials => "bad_credentials", "invalid API key";
+    TooManyRequests => "too_many_requests", "too many requests";
+    PaymentRequired => "payment_required", "payment required";
+    Unknown => "unknown", "unknown error";
+    InvalidParameter { parameter_name, reason } => "invalid_parameter", "parameter '{parameter_name}' invalid: {reason}";
+});
+
 #[derive(Debug, Clone, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct WriteToSpawnedCommandResult {}
 
 #[derive(Debug, Clone, Deserialize)]
-#[serde(rename_all = "snake_case", tag = "code")]
+#[serde(rename_all = "snake_case", tag = "code", remote = "Self")]
 pub enum WriteToSpawnedCommandError {
     SpawnedCommandNotFound,
     BadCredentials,
@@ -154,8 +243,22 @@ pub enum WriteToSpawnedCommandError {
         parameter_name: String,
         reason: String,
     },
+    /// An error code which is not known by this version of the client.
+    #[serde(skip)]
+    Unrecognized {
+        raw: serde_json::Value,
+    },
 }
 
+impl_decthings_error_code!(WriteToSpawnedCommandError {
+    SpawnedCommandNotFound => "spawned_command_not_found", "spawned command not found";
+    BadCredentials => "bad_credentials", "invalid API key";
+    TooManyRequests => "too_many_requests", "too many requests";
+    PaymentRequired => "payment_required", "payment required";
+    Unknown => "unknown", "unknown error";
+    InvalidParameter { parameter_name, reason } => "invalid_parameter", "parameter '{parameter_name}' invalid: {reason}";
+});
+
 #[cfg(feature = "events")]
 #[derive(Debug, Clone, Deserialize)]
 #[serde(rename_all = "camelCase")]
@@ -163,7 +266,7 @@ pub struct SpawnedSubscribeToEventsResult {}
 
 #[cfg(feature = "events")]
 #[derive(Debug, Clone, Deserialize)]
-#[serde(rename_all = "snake_case", tag = "code")]
+#[serde(rename_all = "snake_case", tag = "code", remote = "Self")]
 pub enum SpawnedSubscribeToEventsError {
     SpawnedCommandNotFound,
     BadCredentials,
@@ -175,8 +278,23 @@ pub enum SpawnedSubscribeToEventsError {
         parameter_name: String,
         reason: String,
     },
+    /// An error code which is not known by this version of the client.
+    #[serde(skip)]
+    Unrecognized {
+        raw: serde_json::Value,
+    },
 }
 
+#[cfg(feature = "events")]
+impl_decthings_error_code!(SpawnedSubscribeToEventsError {
+    SpawnedCommandNotFound => "spawned_command_not_found", "spawned command not found";
+    BadCredentials => "bad_credentials", "invalid API key";
+    TooManyRequests => "too_many_requests", "too many requests";
+    PaymentRequired => "payment_required", "payment required";
+    Unknown => "unknown", "unknown error";
+    InvalidParameter { parameter_name, reason } => "invalid_parameter", "parameter '{parameter_name}' invalid: {reason}";
+});
+
 #[cfg(feature = "events")]
 #[derive(Debug, Clone, Deserialize)]
 #[serde(rename_all = "camelCase")]
@@ -184,7 +302,7 @@ pub struct SpawnedUnsubscribeFromEventsResult {}
 
 #[cfg(feature = "events")]
 #[derive(Debug, Clone, Deserialize)]
-#[serde(rename_all = "snake_case", tag = "code")]
+#[serde(rename_all = "snake_case", tag = "code", remote = "Self")]
 pub enum SpawnedUnsubscribeFromEventsError {
     NotSubscribed,
     TooManyRequests,
@@ -194,4 +312,17 @@ pub enum SpawnedUnsubscribeFromEventsError {
         parameter_name: String,
         reason: String,
     },
+    /// An error code which is not known by this version of the client.
+    #[serde(skip)]
+    Unrecognized {
+        raw: serde_json::Value,
+    },
 }
+
+#[cfg(feature = "events")]
+impl_decthings_error_code!(SpawnedUnsubscribeFromEventsError {
+    NotSubscribed => "not_subscribed", "not subscribed";
+    TooManyRequests => "too_many_requests", "too many requests";
+    Unknown => "unknown", "unknown error";
+    InvalidParameter { parameter_name, reason } => "invalid_parameter", "parameter '{parameter_name}' invalid: {reason}";
+});
@@ -15,6 +15,31 @@ impl TerminalRpc {
         Self { rpc }
     }
 
+    /// Returns a copy of this API which uses the given timeout for each request, instead of
+    /// `DecthingsClientOptions::timeout`. Pass None to wait without a timeout.
+    pub fn with_timeout(&self, timeout: Option<std::time::Duration>) -> Self {
+        Self {
+            rpc: self.rpc.with_timeout(timeout),
+        }
+    }
+
+    /// Returns a copy of this API which retries requests according to the given policy, instead
+    /// of `DecthingsClientOptions::retry_policy`. Pass None to never retry.
+    pub fn with_retry_policy(&self, retry_policy: Option<crate::client::RetryPolicy>) -> Self {
+        Self {
+            rpc: self.rpc.with_retry_policy(retry_policy),
+        }
+    }
+
+    /// Returns a copy of this API which sends requests using the given protocol, instead of
+    /// `DecthingsClientOptions::default_protocol`. Methods which subscribe to events always use
+    /// WebSocket.
+    pub fn with_protocol(&self, protocol: crate::client::RpcProtocol) -> Self {
+        Self {
+            rpc: self.rpc.with_protocol(protocol),
+        }
+    }
+
     pub async fn launch_terminal_session(
         &self,
         params: LaunchTerminalSessionParams<'_>,
@@ -29,19 +54,19 @@ impl TerminalRpc {
         let protocol = if subscribe_to_events {
             crate::client::RpcProtocol::Ws
         } else {
-            crate::client::RpcProtocol::Http
+            self.rpc.protocol()
         };
 
         #[cfg(not(feature = "events"))]
-        let protocol = crate::client::RpcProtocol::Http;
+        let protocol = self.rpc.protocol();
 
         let (tx, rx) = tokio::sync::oneshot::channel();
         self.rpc
-            .raw_method_call::<_, _, &[u8]>(
+            .raw_method_call(
                 "Terminal",
                 "launchTerminalSession",
                 params,
-                &[],
+                vec![],
                 protocol,
                 move |x| {
                     match x {
@@ -63,7 +88,7 @@ impl TerminalRpc {
                                     #[cfg(feature = "events")]
                                     if subscribe_to_events {
                                         return StateModification {
-                                            add_events: vec![terminal_session_id],
+                                            add_events: vec![("Terminal", terminal_session_id)],
                                             remove_events: vec![],
                                         };
                                     }
@@ -100,12 +125,12 @@ impl TerminalRpc {
 
         let (tx, rx) = tokio::sync::oneshot::channel();
         self.rpc
-            .raw_method_call::<_, _, &[u8]>(
+            .raw_method_call(
                 "Terminal",
                 "terminateTerminalSession",
                 params,
-                &[],
-                crate::client::RpcProtocol::Http,
+                vec![],
+                self.rpc.protocol(),
                 move |x| {
                     match x {
                         Ok(val) => {
@@ -153,12 +178,12 @@ impl TerminalRpc {
     {
         let (tx, rx) = tokio::sync::oneshot::channel();
         self.rpc
-            .raw_method_call::<_, _, &[u8]>(
+            .raw_method_call(
                 "Terminal",
                 "getTerminalSessions",
                 params,
-                &[],
-                crate::client::RpcProtocol::Http,
+                vec![],
+                self.rpc.protocol(),
                 |x| {
                     tx.send(x).ok();
                     StateModification::empty()
@@ -180,19 +205,27 @@ impl TerminalRpc {
 
     pub async fn write_to_terminal_session(
         &self,
-        params: WriteToTerminalSessionParams<'_, impl AsRef<[u8]>>,
+        params: WriteToTerminalSessionParams<'_, impl Into<bytes::Bytes>>,
     ) -> Result<
         WriteToTerminalSessionResult,
         crate::client::DecthingsRpcError<WriteToTerminalSessionError>,
     > {
+        let WriteToTerminalSessionParams {
+            terminal_session_id,
+            data,
+        } = params;
+        let params = WriteToTerminalSessionParams {
+            terminal_session_id,
+            data: bytes::Bytes::new(),
+        };
         let (tx, rx) = tokio::sync::oneshot::channel();
         self.rpc
             .raw_method_call(
                 "Terminal",
                 "writeToTerminalSession",
                 &params,
-                &[&params.data],
-                crate::client::RpcProtocol::Http,
+                vec![data.into()],
+                self.rpc.protocol(),
                 |x| {
                     tx.send(x).ok();
                     StateModification::empty()
@@ -223,12 +256,12 @@ impl TerminalRpc {
     > {
         let (tx, rx) = tokio::sync::oneshot::channel();
         self.rpc
-            .raw_method_call::<_, _, &[u8]>(
+            .raw_method_call(
                 "Terminal",
                 "resizeTerminalSession",
                 params,
-                &[],
-                crate::client::RpcProtocol::Http,
+                vec![],
+                self.rpc.protocol(),
                 |x| {
                     tx.send(x).ok();
                     StateModification::empty()
@@ -248,6 +281,20 @@ impl TerminalRpc {
             })
     }
 
+    /// Returns a stream of the events of the terminal session `terminal_session_id`, which ends after the Exit
+    /// event. See [`EventStream`](crate::client::event::EventStream).
+    ///
+    /// Only events received after this call are included. To not miss any, pass
+    /// `subscribe_to_events: Some(false)` to `launch_terminal_session`, create the stream and then call
+    /// `subscribe_to_events`.
+    #[cfg(feature = "events")]
+    pub fn events(
+        &self,
+        terminal_session_id: impl Into<String>,
+    ) -> crate::client::event::EventStream<TerminalEvent> {
+        self.rpc.event_stream(terminal_session_id.into())
+    }
+
     #[cfg(feature = "events")]
     pub async fn subscribe_to_events(
         &self,
@@ -259,11 +306,11 @@ impl TerminalRpc {
         let (tx, rx) = tokio::sync::oneshot::channel();
         let spawned_command_id_owned = params.terminal_session_id.to_owned();
         self.rpc
-            .raw_method_call::<_, _, &[u8]>(
+            .raw_method_call(
                 "Terminal",
                 "subscribeToEvents",
                 params,
-                &[],
+                vec![],
                 crate::client::RpcProtocol::Ws,
                 move |x| {
                     match x {
@@ -279,7 +326,7 @@ impl TerminalRpc {
                                 Ok(super::Response::Result(val)) => {
                                     tx.send(Ok(val)).ok();
                                     return StateModification {
-                                        add_events: vec![spawned_command_id_owned],
+                                        add_events: vec![("Terminal", spawned_command_id_owned)],
                                         remove_events: vec![],
                                     };
                                 }
@@ -315,11 +362,11 @@ impl TerminalRpc {
         let spawned_command_id_owned = params.terminal_session_id.to_owned();
         let did_call = self
             .rpc
-            .raw_method_call::<_, _, &[u8]>(
+            .raw_method_call(
                 "Terminal",
                 "unsubscribeFromEvents",
                 params,
-                &[],
+                vec![],
                 crate::client::RpcProtocol::WsIfAvailableOtherwiseNone,
                 move |x| {
                     match x {
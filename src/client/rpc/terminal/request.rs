@@ -57,9 +57,10 @@ pub struct GetTerminalSessionsParams<'a, S: AsRef<str>> {
 
 #[derive(Debug, Clone, Serialize)]
 #[serde(rename_all = "camelCase")]
-pub struct WriteToTerminalSessionParams<'a, T: AsRef<[u8]>> {
+pub struct WriteToTerminalSessionParams<'a, T: Into<bytes::Bytes>> {
     /// The terminal session's id.
     pub terminal_session_id: &'a str,
+    /// Data to write. Pass `bytes::Bytes` to avoid copying it.
     #[serde(skip_serializing)]
     pub data: T,
 }
@@ -3,7 +3,7 @@ use serde::Deserialize;
 
 #[cfg(feature = "events")]
 #[derive(Debug, Clone, Deserialize)]
-#[serde(rename_all = "snake_case", tag = "code")]
+#[serde(rename_all = "snake_case", tag = "code", remote = "Self")]
 pub enum TerminalSessionTerminatedReason {
     TerminatedOnRequest,
     LauncherTerminated,
@@ -15,11 +15,34 @@ pub enum TerminalSessionTerminatedReason {
         signal: Option<String>,
         oom: bool,
     },
+    /// A reason which is not known by this version of the client.
+    #[serde(skip)]
+    Unrecognized {
+        raw: serde_json::Value,
+    },
 }
 
+#[cfg(feature = "events")]
+impl_deserialize_unrecognized!(
+    TerminalSessionTerminatedReason,
+    "code",
+    [
+        "terminated_on_request",
+        "launcher_terminated",
+        "inactive_timeout",
+        "unknown",
+        "process_exit"
+    ]
+);
+
 #[cfg(feature = "events")]
 #[derive(Debug, Clone, Deserialize)]
-#[serde(rename_all = "camelCase", tag = "event", content = "params")]
+#[serde(
+    rename_all = "camelCase",
+    tag = "event",
+    content = "params",
+    remote = "Self"
+)]
 pub enum TerminalEvent {
     #[serde(rename_all = "camelCase")]
     Exit {
@@ -32,8 +55,14 @@ pub enum TerminalEvent {
         #[serde(skip_deserializing)]
         data: bytes::Bytes,
     },
+    /// An event which is not known by this version of the client.
+    #[serde(skip)]
+    Unrecognized { raw: serde_json::Value },
 }
 
+#[cfg(feature = "events")]
+impl_deserialize_unrecognized!(TerminalEvent, "event", ["exit", "data"]);
+
 #[derive(Debug, Clone, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct LaunchTerminalSessionResult {
@@ -42,7 +71,7 @@ pub struct LaunchTerminalSessionResult {
 }
 
 #[derive(Debug, Clone, Deserialize)]
-#[serde(rename_all = "snake_case", tag = "code")]
+#[serde(rename_all = "snake_case", tag = "code", remote = "Self")]
 pub enum LaunchTerminalSessionError {
     ModelNotFound,
     PersistentLauncherNotFound,
@@ -57,14 +86,31 @@ pub enum LaunchTerminalSessionError {
         parameter_name: String,
         reason: String,
     },
+    /// An error code which is not known by this version of the client.
+    #[serde(skip)]
+    Unrecognized {
+        raw: serde_json::Value,
+    },
 }
 
+impl_decthings_error_code!(LaunchTerminalSessionError {
+    ModelNotFound => "model_not_found", "model not found";
+    PersistentLauncherNotFound => "persistent_launcher_not_found", "persistent launcher not found";
+    QuotaExceeded => "quota_exceeded", "quota exceeded";
+    ServerOverloaded => "server_overloaded", "server overloaded";
+    BadCredentials => "bad_credentials", "invalid API key";
+    TooManyRequests => "too_many_requests", "too many requests";
+    PaymentRequired => "payment_required", "payment required";
+    Unknown => "unknown", "unknown error";
+    InvalidParameter { parameter_name, reason } => "invalid_parameter", "parameter '{parameter_name}' invalid: {reason}";
+});
+
 #[derive(Debug, Clone, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct TerminateTerminalSessionResult {}
 
 #[derive(Debug, Clone, Deserialize)]
-#[serde(rename_all = "snake_case", tag = "code")]
+#[serde(rename_all = "snake_case", tag = "code", remote = "Self")]
 pub enum TerminateTerminalSessionError {
     TerminalSessionNotFound,
     BadCredentials,
@@ -76,8 +122,22 @@ pub enum TerminateTerminalSessionError {
         parameter_name: String,
         reason: String,
     },
+    /// An error code which is not known by this version of the client.
+    #[serde(skip)]
+    Unrecognized {
+        raw: serde_json::Value,
+    },
 }
 
+impl_decthings_error_code!(TerminateTerminalSessionError {
+    TerminalSessionNotFound => "terminal_session_not_found", "terminal session not found";
+    BadCredentials => "bad_credentials", "invalid API key";
+    TooManyRequests => "too_many_requests", "too many requests";
+    PaymentRequired => "payment_required", "payment required";
+    Unknown => "unknown", "unknown error";
+    InvalidParameter { parameter_name, reason } => "invalid_parameter", "parameter '{parameter_name}' invalid: {reason}";
+});
+
 #[derive(Debug, Clone, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct TerminalSession {
@@ -94,7 +154,7 @@ pub struct GetTerminalSessionsResult {
 }
 
 #[derive(Debug, Clone, Deserialize)]
-#[serde(rename_all = "snake_case", tag = "code")]
+#[serde(rename_all = "snake_case", tag = "code", remote = "Self")]
 pub enum GetTerminalSessionsError {
     BadCredentials,
     TooManyRequests,
@@ -105,14 +165,27 @@ pub enum GetTerminalSessionsError {
         parameter_name: String,
         reason: String,
     },
+    /// An error code which is not known by this version of the client.
+    #[serde(skip)]
+    Unrecognized {
+        raw: serde_json::Value,
+    },
 }
 
+impl_decthings_error_code!(GetTerminalSessionsError {
+    BadCredentials => "bad_credentials", "invalid API key";
+    TooManyRequests => "too_many_requests", "too many requests";
+    PaymentRequired => "payment_required", "payment required";
+    Unknown => "unknown", "unknown error";
+    InvalidParameter { parameter_name, reason } => "invalid_parameter", "parameter '{parameter_name}' invalid: {reason}";
+});
+
 #[derive(Debug, Clone, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct WriteToTerminalSessionResult {}
 
 #[derive(Debug, Clone, Deserialize)]
-#[serde(rename_all = "snake_case", tag = "code")]
+#[serde(rename_all = "snake_case", tag = "code", remote = "Self")]
 pub enum WriteToTerminalSessionError {
     TerminalSessionNotFound,
     BadCredentials,
@@ -124,14 +197,28 @@ pub enum WriteToTerminalSessionError {
         parameter_name: String,
         reason: String,
     },
+    /// An error code which is not known by this version of the client.
+    #[serde(skip)]
+    Unrecognized {
+        raw: serde_json::Value,
+    },
 }
 
+impl_decthings_error_code!(WriteToTerminalSessionError {
+    TerminalSessionNotFound => "terminal_session_not_found", "terminal session not found";
+    BadCredentials => "bad_credentials", "invalid API key";
+    TooManyRequests => "too_many_requests", "too many requests";
+    PaymentRequired => "payment_required", "payment required";
+    Unknown => "unknown", "unknown error";
+    InvalidParameter { parameter_name, reason } => "invalid_parameter", "parameter '{parameter_name}' invalid: {reason}";
+});
+
 #[derive(Debug, Clone, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct ResizeTerminalSessionResult {}
 
 #[derive(Debug, Clone, Deserialize)]
-#[serde(rename_all = "snake_case", tag = "code")]
+#[serde(rename_all = "snake_case", tag = "code", remote = "Self")]
 pub enum ResizeTerminalSessionError {
     TerminalSessionNotFound,
     BadCredentials,
@@ -143,14 +230,28 @@ pub enum ResizeTerminalSessionError {
         parameter_name: String,
         reason: String,
     },
+    /// An error code which is not known by this version of the client.
+    #[serde(skip)]
+    Unrecognized {
+        raw: serde_json::Value,
+    },
 }
 
+impl_decthings_error_code!(ResizeTerminalSessionError {
+    TerminalSessionNotFound => "terminal_session_not_found", "terminal session not found";
+    BadCredentials => "bad_credentials", "invalid API key";
+    TooManyRequests => "too_many_requests", "too many requests";
+    PaymentRequired => "payment_required", "payment required";
+    Unknown => "unknown", "unknown error";
+    InvalidParameter { parameter_name, reason } => "invalid_parameter", "parameter '{parameter_name}' invalid: {reason}";
+});
+
 #[derive(Debug, Clone, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct AddFilesystemAccessForTerminalSessionResult {}
 
 #[derive(Debug, Clone, Deserialize)]
-#[serde(rename_all = "snake_case", tag = "code")]
+#[serde(rename_all = "snake_case", tag = "code", remote = "Self")]
 pub enum AddFilesystemAccessForTerminalSessionError {
     TerminalSessionNotFound,
     ModelNotFound,
@@ -163,8 +264,23 @@ pub enum AddFilesystemAccessForTerminalSessionError {
         parameter_name: String,
         reason: String,
     },
+    /// An error code which is not known by this version of the client.
+    #[serde(skip)]
+    Unrecognized {
+        raw: serde_json::Value,
+    },
 }
 
+impl_decthings_error_code!(AddFilesystemAccessForTerminalSessionError {
+    TerminalSessionNotFound => "terminal_session_not_found", "terminal session not found";
+    ModelNotFound => "model_not_found", "model not found";
+    BadCredentials => "bad_credentials", "invalid API key";
+    TooManyRequests => "too_many_requests", "too many requests";
+    PaymentRequired => "payment_required", "payment required";
+    Unknown => "unknown", "unknown error";
+    InvalidParameter { parameter_name, reason } => "invalid_parameter", "parameter '{parameter_name}' invalid: {reason}";
+});
+
 #[cfg(feature = "events")]
 #[derive(Debug, Clone, Deserialize)]
 #[serde(rename_all = "camelCase")]
@@ -172,7 +288,7 @@ pub struct TerminalSubscribeToEventsResult {}
 
 #[cfg(feature = "events")]
 #[derive(Debug, Clone, Deserialize)]
-#[serde(rename_all = "snake_case", tag = "code")]
+#[serde(rename_all = "snake_case", tag = "code", remote = "Self")]
 pub enum TerminalSubscribeToEventsError {
     TerminalSessionNotFound,
     BadCredentials,
@@ -184,8 +300,23 @@ pub enum TerminalSubscribeToEventsError {
         parameter_name: String,
         reason: String,
     },
+    /// An error code which is not known by this version of the client.
+    #[serde(skip)]
+    Unrecognized {
+        raw: serde_json::Value,
+    },
 }
 
+#[cfg(feature = "events")]
+impl_decthings_error_code!(TerminalSubscribeToEventsError {
+    TerminalSessionNotFound => "terminal_session_not_found", "terminal session not found";
+    BadCredentials => "bad_credentials", "invalid API key";
+    TooManyRequests => "too_many_requests", "too many requests";
+    PaymentRequired => "payment_required", "payment required";
+    Unknown => "unknown", "unknown error";
+    InvalidParameter { parameter_name, reason } => "invalid_parameter", "parameter '{parameter_name}' invalid: {reason}";
+});
+
 #[cfg(feature = "events")]
 #[derive(Debug, Clone, Deserialize)]
 #[serde(rename_all = "camelCase")]
@@ -193,7 +324,7 @@ pub struct TerminalUnsubscribeFromEventsResult {}
 
 #[cfg(feature = "events")]
 #[derive(Debug, Clone, Deserialize)]
-#[serde(rename_all = "snake_case", tag = "code")]
+#[serde(rename_all = "snake_case", tag = "code", remote = "Self")]
 pub enum TerminalUnsubscribeFromEventsError {
     NotSubscribed,
     TooManyRequests,
@@ -203,4 +334,17 @@ pub enum TerminalUnsubscribeFromEventsError {
         parameter_name: String,
         reason: String,
     },
+    /// An error code which is not known by this version of the client.
+    #[serde(skip)]
+    Unrecognized {
+        raw: serde_json::Value,
+    },
 }
+
+#[cfg(feature = "events")]
+impl_decthings_error_code!(TerminalUnsubscribeFromEventsError {
+    NotSubscribed => "not_subscribed", "not subscribed";
+    TooManyRequests => "too_many_requests", "too many requests";
+    Unknown => "unknown", "unknown error";
+    InvalidParameter { parameter_name, reason } => "invalid_parameter", "parameter '{parameter_name}' invalid: {reason}";
+});
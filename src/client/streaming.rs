@@ -0,0 +1,313 @@
+// ResponseBlobs is only created by the streamed methods of some of the APIs.
+#![cfg_attr(
+    not(any(feature = "dataset", feature = "debug", feature = "model")),
+    allow(dead_code)
+)]
+
+use futures::StreamExt;
+
+use super::{DecodeLimits, DecthingsClientError, DeserializeError, DeserializeErrorKind};
+
+/// A stream of byte chunks, as returned by [`Transport::get_streamed`](super::Transport).
+pub type ByteStream = std::pin::Pin<
+    Box<dyn futures::Stream<Item = Result<bytes::Bytes, DecthingsClientError>> + Send>,
+>;
+
+/// The blobs of a response which is read while it is downloaded, so that large responses do not
+/// need to be kept in memory.
+///
+/// Call `next_blob` to move to the next blob, and then `chunk` repeatedly to read it:
+///
+/// ```ignore
+/// while let Some(len) = blobs.next_blob().await? {
+///     while let Some(chunk) = blobs.chunk().await? {
+///         file.write_all(&chunk).await?;
+///     }
+/// }
+/// ```
+pub struct ResponseBlobs {
+    stream: ByteStream,
+    /// Data received but not yet consumed.
+    buffer: bytes::Bytes,
+    /// Number of bytes left in the current blob.
+    remaining: u64,
+    /// Number of bytes consumed from the stream, used as the offset in errors.
+    offset: u64,
+    /// Number of blobs read so far.
+    count: usize,
+    limits: DecodeLimits,
+}
+
+impl std::fmt::Debug for ResponseBlobs {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("ResponseBlobs")
+            .field("remaining", &self.remaining)
+            .finish_non_exhaustive()
+    }
+}
+
+impl ResponseBlobs {
+    /// Read the JSON part of an HTTP response from the stream. The rest of the stream is returned
+    /// as blobs. See protocol::deserialize_for_http for the format.
+    pub(crate) async fn from_http_stream(
+        stream: ByteStream,
+        limits: DecodeLimits,
+    ) -> Result<(bytes::Bytes, Self), DecthingsClientError> {
+        let mut blobs = Self {
+            stream,
+            buffer: bytes::Bytes::new(),
+            remaining: 0,
+            offset: 0,
+            count: 0,
+            limits,
+        };
+        let len = blobs
+            .read_varint("JSON data")
+            .await?
+            .ok_or_else(|| blobs.error(DeserializeErrorKind::UnexpectedEnd("JSON data")))?;
+        blobs
+            .limits
+            .check_size(len, 0)
+            .map_err(DecthingsClientError::InvalidFrame)?;
+        let len = usize::try_from(len)
+            .map_err(|_| blobs.error(DeserializeErrorKind::LengthOverflow(len)))?;
+        let json = blobs.read_exact(len, "JSON data").await?;
+        Ok((json, blobs))
+    }
+
+    /// Wrap blobs which have already been received in full, for example over WebSocket.
+    pub(crate) fn from_blobs(blobs: Vec<bytes::Bytes>) -> Self {
+        let mut buffer = vec![];
+        for blob in &blobs {
+            crate::varint::append_varint_u64(blob.len() as u64, &mut buffer);
+        }
+        let headers = bytes::Bytes::from(buffer);
+        // Interleave each length with its blob, so that the data can be read like an HTTP stream.
+        let mut chunks = Vec::with_capacity(blobs.len() * 2);
+        let mut pos = 0;
+        for blob in blobs {
+            let varint_len = crate::varint::get_varint_u64_len(blob.len() as u64) as usize;
+            chunks.push(Ok(headers.slice(pos..pos + varint_len)));
+            chunks.push(Ok(blob));
+            pos += varint_len;
+        }
+        Self {
+            stream: Box::pin(futures::stream::iter(chunks)),
+            buffer: bytes::Bytes::new(),
+            remaining: 0,
+            offset: 0,
+            count: 0,
+            limits: DecodeLimits::default(),
+        }
+    }
+
+    fn error(&self, kind: DeserializeErrorKind) -> DecthingsClientError {
+        DecthingsClientError::InvalidFrame(DeserializeError {
+            offset: self.offset,
+            kind,
+        })
+    }
+
+    /// Move to the next blob and return its length in bytes, or None if there are no more blobs.
+    /// Any part of the current blob which has not been read is skipped.
+    pub async fn next_blob(&mut self) -> Result<Option<u64>, DecthingsClientError> {
+        while self.chunk().await?.is_some() {}
+        if self.buffer.is_empty() && !self.fill().await? {
+            return Ok(None);
+        }
+        self.limits
+            .check_count(self.count, self.offset)
+            .map_err(DecthingsClientError::InvalidFrame)?;
+        let offset = self.offset;
+        let len = self.read_varint("blob").await?;
+        if let Some(len) = len {
+            self.limits
+                .check_size(len, offset)
+                .map_err(DecthingsClientError::InvalidFrame)?;
+            self.count += 1;
+        }
+        self.remaining = len.unwrap_or(0);
+        Ok(len)
+    }
+
+    /// Read the next chunk of the current blob, or None if the whole blob has been read.
+    pub async fn chunk(&mut self) -> Result<Option<bytes::Bytes>, DecthingsClientError> {
+        if self.remaining == 0 {
+            return Ok(None);
+        }
+        if self.buffer.is_empty() && !self.fill().await? {
+            return Err(self.error(DeserializeErrorKind::UnexpectedEnd("blob")));
+        }
+        let len = self
+            .buffer
+            .len()
+            .min(self.remaining.try_into().unwrap_or(usize::MAX));
+        self.remaining -= len as u64;
+        self.offset += len as u64;
+        Ok(Some(self.buffer.split_to(len)))
+    }
+
+    /// Read the rest of the current blob into memory.
+    pub async fn read_blob(&mut self) -> Result<bytes::Bytes, DecthingsClientError> {
+        let mut res = bytes::BytesMut::new();
+        while let Some(chunk) = self.chunk().await? {
+            if res.is_empty() && self.remaining == 0 {
+                return Ok(chunk);
+            }
+            res.extend_from_slice(&chunk);
+        }
+        Ok(res.freeze())
+    }
+
+    /// Read `len` bytes of `field`. The data is only copied if it spans several chunks, and then
+    /// each chunk is copied once.
+    async fn read_exact(
+        &mut self,
+        len: usize,
+        field: &'static str,
+    ) -> Result<bytes::Bytes, DecthingsClientError> {
+        if self.buffer.len() >= len {
+            self.offset += len as u64;
+            return Ok(self.buffer.split_to(len));
+        }
+        let mut res = bytes::BytesMut::new();
+        while res.len() < len {
+            if self.buffer.is_empty() && !self.fill().await? {
+                return Err(self.error(DeserializeErrorKind::UnexpectedEnd(field)));
+            }
+            let take = self.buffer.len().min(len - res.len());
+            res.extend_from_slice(&self.buffer.split_to(take));
+        }
+        self.offset += len as u64;
+        Ok(res.freeze())
+    }
+
+    /// Receive more data from the stream. Returns false if the stream has ended. The buffer is
+    /// only joined with the new chunk when it holds the start of a varint, which is at most 9
+    /// bytes.
+    async fn fill(&mut self) -> Result<bool, DecthingsClientError> {
+        loop {
+            let Some(chunk) = self.stream.next().await.transpose()? else {
+                return Ok(false);
+            };
+            if chunk.is_empty() {
+                continue;
+            }
+            if self.buffer.is_empty() {
+                self.buffer = chunk;
+            } else {
+                let mut joined = bytes::BytesMut::with_capacity(self.buffer.len() + chunk.len());
+                joined.extend_from_slice(&self.buffer);
+                joined.extend_from_slice(&chunk);
+                self.buffer = joined.freeze();
+            }
+            return Ok(true);
+        }
+    }
+
+    /// Read the varint length of `field`, or return None if the stream ended before it.
+    async fn read_varint(
+        &mut self,
+        field: &'static str,
+    ) -> Result<Option<u64>, DecthingsClientError> {
+        if self.buffer.is_empty() && !self.fill().await? {
+            return Ok(None);
+        }
+        loop {
+            if let Some((value, varint_len)) = crate::varint::deserialize_varint_u64(&self.buffer) {
+                let _ = self.buffer.split_to(varint_len.into());
+                self.offset += u64::from(varint_len);
+                return Ok(Some(value));
+            }
+            if !self.fill().await? {
+                return Err(self.error(DeserializeErrorKind::UnexpectedEnd(field)));
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+#[tokio::test]
+async fn test_response_blobs() {
+    let mut body = vec![];
+    crate::varint::append_varint_u64(2, &mut body);
+    body.extend_from_slice(b"{}");
+    for blob in [&[1u8; 300][..], &[], &[2, 3]] {
+        crate::varint::append_varint_u64(blob.len() as u64, &mut body);
+        body.extend_from_slice(blob);
+    }
+
+    // Split the body into chunks of one byte, so that every varint and blob crosses a boundary.
+    let chunks = body
+        .into_iter()
+        .map(|byte| Ok(bytes::Bytes::from(vec![byte])))
+        .collect::<Vec<_>>();
+    let (json, mut blobs) = ResponseBlobs::from_http_stream(
+        Box::pin(futures::stream::iter(chunks)),
+        DecodeLimits::default(),
+    )
+    .await
+    .unwrap();
+    assert_eq!(&json[..], b"{}");
+
+    assert_eq!(blobs.next_blob().await.unwrap(), Some(300));
+    assert_eq!(blobs.read_blob().await.unwrap(), vec![1u8; 300]);
+    assert_eq!(blobs.next_blob().await.unwrap(), Some(0));
+    assert_eq!(blobs.chunk().await.unwrap(), None);
+    assert_eq!(blobs.next_blob().await.unwrap(), Some(2));
+    assert_eq!(blobs.next_blob().await.unwrap(), None);
+}
+
+#[cfg(test)]
+#[tokio::test]
+async fn test_response_blobs_from_blobs() {
+    let mut blobs = ResponseBlobs::from_blobs(vec![vec![4u8; 1000].into(), vec![5].into()]);
+    assert_eq!(blobs.next_blob().await.unwrap(), Some(1000));
+    assert_eq!(blobs.next_blob().await.unwrap(), Some(1));
+    assert_eq!(&blobs.read_blob().await.unwrap()[..], &[5]);
+    assert_eq!(blobs.next_blob().await.unwrap(), None);
+}
+
+#[cfg(all(feature = "mock", feature = "model"))]
+#[tokio::test]
+async fn test_get_weights_streamed() {
+    use crate::{
+        client::{DecthingsClient, DecthingsClientOptions, RpcProtocol, rpc::model},
+        mock::{MockResponse, MockServer},
+    };
+
+    let server = MockServer::start().await.unwrap();
+    server
+        .handle("Model", "getWeights", |_| {
+            MockResponse::result(serde_json::json!({ "weightKeyNames": ["a", "b"] }))
+                .with_data([vec![1u8; 100_000], vec![2u8; 3]])
+        })
+        .await;
+
+    for protocol in [RpcProtocol::Http, RpcProtocol::Ws] {
+        let client = DecthingsClient::new(DecthingsClientOptions {
+            default_protocol: protocol,
+            ..server.client_options()
+        });
+        let mut res = client
+            .model
+            .get_weights_streamed(model::GetWeightsParams::<&str> {
+                model_id: "model",
+                version_id: "version",
+                keys: None,
+            })
+            .await
+            .unwrap();
+        assert_eq!(res.keys, ["a", "b"]);
+        assert_eq!(res.data.next_blob().await.unwrap(), Some(100_000));
+        let mut total = 0;
+        while let Some(chunk) = res.data.chunk().await.unwrap() {
+            assert!(chunk.iter().all(|x| *x == 1));
+            total += chunk.len();
+        }
+        assert_eq!(total, 100_000);
+        assert_eq!(res.data.next_blob().await.unwrap(), Some(3));
+        assert_eq!(&res.data.read_blob().await.unwrap()[..], &[2, 2, 2]);
+        assert_eq!(res.data.next_blob().await.unwrap(), None);
+    }
+}
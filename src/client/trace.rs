@@ -0,0 +1,60 @@
+//! Helpers for the `tracing` feature.
+
+use super::{DecthingsClientError, RpcProtocol, RpcResult};
+
+/// Create the span which covers one RPC, including retries. `protocol` and `request_id` are
+/// recorded once the request is sent, and `response_bytes` and `outcome` when it finishes.
+pub(crate) fn rpc_span(
+    api: &str,
+    method: &str,
+    params: &[u8],
+    data: &[bytes::Bytes],
+) -> tracing::Span {
+    let request_bytes = params.len() + data.iter().map(|x| x.len()).sum::<usize>();
+    tracing::debug_span!(
+        "rpc",
+        api,
+        method,
+        protocol = tracing::field::Empty,
+        request_id = tracing::field::Empty,
+        request_bytes,
+        request_compressed_bytes = tracing::field::Empty,
+        response_bytes = tracing::field::Empty,
+        outcome = tracing::field::Empty,
+    )
+}
+
+pub(crate) fn record_protocol(protocol: &RpcProtocol) {
+    let protocol = match protocol {
+        RpcProtocol::Http => "http",
+        #[cfg(feature = "events")]
+        RpcProtocol::Ws | RpcProtocol::WsIfAvailableOtherwiseNone => "ws",
+    };
+    tracing::Span::current().record("protocol", protocol);
+}
+
+/// Record the outcome of an RPC: "ok", the error code returned by Decthings, or "client_error"
+/// if the request failed.
+pub(crate) fn record_outcome(span: &tracing::Span, result: &RpcResult) {
+    match result {
+        Ok((response, blobs)) => record_response(
+            span,
+            response,
+            response.len() + blobs.iter().map(|x| x.len()).sum::<usize>(),
+        ),
+        Err(e) => record_error(span, e),
+    }
+}
+
+pub(crate) fn record_response(span: &tracing::Span, response: &[u8], response_bytes: usize) {
+    span.record("response_bytes", response_bytes);
+    match super::protocol::peek_error_code(response) {
+        Some(code) => span.record("outcome", &*code),
+        None => span.record("outcome", "ok"),
+    };
+}
+
+pub(crate) fn record_error(span: &tracing::Span, error: &DecthingsClientError) {
+    span.record("outcome", "client_error");
+    span.in_scope(|| tracing::debug!(%error, "RPC failed"));
+}
@@ -0,0 +1,53 @@
+use std::{future::Future, pin::Pin, sync::Arc};
+
+use super::{ByteStream, DecthingsClientError};
+
+/// Future returned by [`Transport::get`].
+pub type TransportFuture<'a> =
+    Pin<Box<dyn Future<Output = Result<bytes::Bytes, DecthingsClientError>> + Send + 'a>>;
+
+/// Future returned by [`Transport::get_streamed`].
+pub type TransportStreamFuture<'a> =
+    Pin<Box<dyn Future<Output = Result<ByteStream, DecthingsClientError>> + Send + 'a>>;
+
+/// The transport used to send HTTP RPC requests to Decthings.
+///
+/// By default, the client uses reqwest (or the esp-idf HTTP client on ESP32). Implement this trait
+/// and set `DecthingsClientOptions::transport` to send requests some other way, for example
+/// through your own HTTP stack or an in-memory fake in tests.
+pub trait Transport: Send + Sync {
+    /// Send a serialized request body for the method `api`/`method` and return the raw response
+    /// body.
+    ///
+    /// The body is already encoded using the Decthings HTTP message protocol and is split into
+    /// segments, which should be sent in order without separators. Large blobs are passed as
+    /// separate segments so that they do not need to be copied. The body should be sent with
+    /// content type "application/octet-stream". If `api_key` is set, it should be sent as a
+    /// bearer token. Each of `extra_headers` should be added to the request.
+    fn get<'a>(
+        &'a self,
+        api: &'a str,
+        method: &'a str,
+        body: Vec<bytes::Bytes>,
+        api_key: Option<Arc<str>>,
+        extra_headers: Arc<http::HeaderMap<http::HeaderValue>>,
+    ) -> TransportFuture<'a>;
+
+    /// Like `get`, but return the response body as a stream of chunks as it is received. This is
+    /// used for methods which may return very large responses, such as "Model"/"getWeights".
+    ///
+    /// The default implementation calls `get` and returns the whole body as one chunk.
+    fn get_streamed<'a>(
+        &'a self,
+        api: &'a str,
+        method: &'a str,
+        body: Vec<bytes::Bytes>,
+        api_key: Option<Arc<str>>,
+        extra_headers: Arc<http::HeaderMap<http::HeaderValue>>,
+    ) -> TransportStreamFuture<'a> {
+        Box::pin(async move {
+            let response = self.get(api, method, body, api_key, extra_headers).await?;
+            Ok(Box::pin(futures::stream::once(async { Ok(response) })) as ByteStream)
+        })
+    }
+}
@@ -1,6 +1,7 @@
 use std::{
     collections::{HashMap, HashSet},
     sync::{Arc, Weak},
+    time::{Duration, Instant},
 };
 
 use futures::{SinkExt, StreamExt};
@@ -8,33 +9,139 @@ use tokio::sync::Mutex;
 
 use super::StateModification;
 
-#[derive(Clone)]
+#[derive(Debug, Clone)]
 pub(crate) enum WebSocketClientError {
     Connect(Arc<tokio_tungstenite::tungstenite::Error>),
     Write(Arc<tokio_tungstenite::tungstenite::Error>),
     Read(Arc<tokio_tungstenite::tungstenite::Error>),
-    InvalidMessage,
+    InvalidFrame(super::DeserializeError),
+    /// The connection was lost while waiting for a response, or a heartbeat ping was not
+    /// answered in time.
+    ConnectionLost,
+}
+
+/// Controls how the client detects that a WebSocket connection is dead. See
+/// `DecthingsClientOptions::ws_keepalive`.
+#[derive(Debug, Clone)]
+pub struct Keepalive {
+    /// Time between two pings. Default: 30 seconds.
+    pub ping_interval: Duration,
+    /// If nothing is received from Decthings within this time after a ping was sent, the
+    /// connection is closed. Default: 10 seconds.
+    pub pong_timeout: Duration,
+}
+
+impl Default for Keepalive {
+    fn default() -> Self {
+        Self {
+            ping_interval: Duration::from_secs(30),
+            pong_timeout: Duration::from_secs(10),
+        }
+    }
+}
+
+/// How to connect to the WebSocket server.
+pub(crate) struct ConnectOptions {
+    pub address: String,
+    #[cfg(any(feature = "native-tls", feature = "rustls-tls"))]
+    pub tls: super::tls::TlsConfig,
+    pub proxy: Option<super::proxy::Proxy>,
+    pub keepalive: Option<Keepalive>,
+    pub decode_limits: super::DecodeLimits,
+}
+
+/// A message to send on the socket.
+enum Outgoing {
+    Call(u32, Vec<u8>, ResultCallback),
+    Ping,
+}
+
+type ResultCallback = Box<
+    dyn FnOnce(Result<(bytes::Bytes, Vec<bytes::Bytes>), WebSocketClientError>) -> StateModification
+        + Send
+        + 'static,
+>;
+
+#[derive(Default)]
+struct PendingRequests {
+    /// Requests which have been sent and are waiting for a response.
+    waiting_for_response: HashMap<u32, ResultCallback>,
+    /// Requests which were cancelled before they were sent.
+    cancelled: HashSet<u32>,
 }
 
 struct DecthingsClientWebsocketState {
     request_id_counter: u32,
-    events: HashSet<String>,
+    /// Active event subscriptions, mapping id to api.
+    events: HashMap<String, &'static str>,
     requests: HashSet<u32>,
 }
 
 pub(crate) struct DecthingsClientWebsocket {
     state: Arc<Mutex<DecthingsClientWebsocketState>>,
-    call_method_tx: tokio::sync::mpsc::Sender<(
-        u32,
-        Vec<u8>,
-        Box<
-            dyn FnOnce(
-                    Result<(bytes::Bytes, Vec<bytes::Bytes>), WebSocketClientError>,
-                ) -> StateModification
-                + Send
-                + 'static,
-        >,
-    )>,
+    call_method_tx: tokio::sync::mpsc::Sender<Outgoing>,
+    cancel_tx: tokio::sync::mpsc::UnboundedSender<u32>,
+}
+
+/// Open a TCP connection to the server, through the proxy if it applies, and perform the TLS and
+/// WebSocket handshakes.
+async fn connect_socket(
+    req: tokio_tungstenite::tungstenite::handshake::client::Request,
+    options: &ConnectOptions,
+) -> Result<
+    tokio_tungstenite::WebSocketStream<tokio_tungstenite::MaybeTlsStream<tokio::net::TcpStream>>,
+    tokio_tungstenite::tungstenite::Error,
+> {
+    use tokio_tungstenite::tungstenite::{Error, error::UrlError, stream::Mode};
+
+    let host = req.uri().host().ok_or(Error::Url(UrlError::NoHostName))?;
+    let port = req.uri().port_u16().unwrap_or(
+        match tokio_tungstenite::tungstenite::client::uri_mode(req.uri())? {
+            Mode::Plain => 80,
+            Mode::Tls => 443,
+        },
+    );
+    let host = host
+        .strip_prefix('[')
+        .and_then(|host| host.strip_suffix(']'))
+        .unwrap_or(host);
+    // Messages which are larger than the decode limits allow are rejected before they are
+    // received in full.
+    let config = options
+        .decode_limits
+        .max_message_size()
+        .and_then(|size| usize::try_from(size).ok())
+        .map(
+            |size| tokio_tungstenite::tungstenite::protocol::WebSocketConfig {
+                max_message_size: Some(size),
+                max_frame_size: Some(size),
+                ..Default::default()
+            },
+        );
+    let stream = match options
+        .proxy
+        .as_ref()
+        .filter(|proxy| proxy.applies_to(host))
+    {
+        Some(proxy) => proxy.connect(host, port).await?,
+        None => tokio::net::TcpStream::connect((host, port)).await?,
+    };
+    #[cfg(any(feature = "native-tls", feature = "rustls-tls"))]
+    let res = tokio_tungstenite::client_async_tls_with_config(
+        req,
+        stream,
+        config,
+        Some(options.tls.ws_connector().map_err(|e| *e)?),
+    )
+    .await;
+    #[cfg(not(any(feature = "native-tls", feature = "rustls-tls")))]
+    let res = tokio_tungstenite::client_async_with_config(
+        req,
+        tokio_tungstenite::MaybeTlsStream::Plain(stream),
+        config,
+    )
+    .await;
+    res.map(|(ws_stream, _)| ws_stream)
 }
 
 impl DecthingsClientWebsocket {
@@ -43,30 +150,24 @@ impl DecthingsClientWebsocket {
         Fut2: futures::Future<Output = ()> + Send + 'static,
     >(
         extra_headers: &http::HeaderMap<http::HeaderValue>,
-        on_disconnected: impl FnOnce() -> Fut + Send + 'static,
+        on_disconnected: impl FnOnce(Vec<(&'static str, String)>) -> Fut + Send + 'static,
         remove_if_unused: impl Fn() -> Fut2 + Send + Sync + 'static,
-        ws_server_address: &str,
+        options: Arc<ConnectOptions>,
         weak_event_listeners: Weak<super::event::EventListeners>,
     ) -> Self {
         let state = Arc::new(Mutex::new(DecthingsClientWebsocketState {
             request_id_counter: 0,
-            events: HashSet::new(),
+            events: HashMap::new(),
             requests: HashSet::new(),
         }));
         let state2 = Arc::clone(&state);
-        let (call_method_tx, mut call_method_rx) = tokio::sync::mpsc::channel::<(
-            u32,
-            Vec<u8>,
-            Box<
-                dyn FnOnce(
-                        Result<(bytes::Bytes, Vec<bytes::Bytes>), WebSocketClientError>,
-                    ) -> StateModification
-                    + Send
-                    + 'static,
-            >,
-        )>(10);
+        let (call_method_tx, mut call_method_rx) = tokio::sync::mpsc::channel::<Outgoing>(10);
+        // Weak, so that the socket is still closed when the last DecthingsClientWebsocket is
+        // dropped.
+        let ping_tx = call_method_tx.downgrade();
+        let (cancel_tx, mut cancel_rx) = tokio::sync::mpsc::unbounded_channel::<u32>();
         let req = tokio_tungstenite::tungstenite::client::IntoClientRequest::into_client_request(
-            ws_server_address,
+            options.address.as_str(),
         )
         .map(|mut req| {
             let headers = req.headers_mut();
@@ -75,20 +176,30 @@ impl DecthingsClientWebsocket {
             }
             req
         });
+        #[cfg(feature = "tracing")]
+        tracing::debug!(address = options.address, "Connecting WebSocket");
         tokio::spawn(async move {
             let connect_res = match req {
-                Ok(req) => tokio_tungstenite::connect_async(req).await,
+                Ok(req) => connect_socket(req, &options).await,
                 Err(e) => Err(e),
             };
             let sock = match connect_res {
-                Ok((ws_stream, _)) => ws_stream,
+                Ok(ws_stream) => {
+                    #[cfg(feature = "tracing")]
+                    tracing::debug!("WebSocket connected");
+                    ws_stream
+                }
                 Err(err) => {
-                    on_disconnected().await;
+                    #[cfg(feature = "tracing")]
+                    tracing::warn!(error = %err, "WebSocket connection failed");
+                    on_disconnected(vec![]).await;
 
                     let e = WebSocketClientError::Connect(Arc::new(err));
 
-                    while let Some(next_method_call) = call_method_rx.recv().await {
-                        (next_method_call.2)(Err(e.clone()));
+                    while let Some(outgoing) = call_method_rx.recv().await {
+                        if let Outgoing::Call(_, _, on_result) = outgoing {
+                            on_result(Err(e.clone()));
+                        }
                     }
                     return;
                 }
@@ -96,15 +207,25 @@ impl DecthingsClientWebsocket {
 
             let (mut write_half, mut read_half) = sock.split();
 
-            let waiting_for_response = Mutex::new(HashMap::new());
+            let pending = Mutex::new(PendingRequests::default());
+            let last_received = std::sync::Mutex::new(Instant::now());
 
             let write_fut = async {
-                while let Some(next) = call_method_rx.recv().await {
-                    let mut locked_waiting_for_response = waiting_for_response.lock().await;
-                    locked_waiting_for_response.insert(next.0, next.2);
-                    drop(locked_waiting_for_response);
+                while let Some(outgoing) = call_method_rx.recv().await {
+                    let message = match outgoing {
+                        Outgoing::Call(id, message, on_result) => {
+                            let mut locked_pending = pending.lock().await;
+                            if locked_pending.cancelled.remove(&id) {
+                                continue;
+                            }
+                            locked_pending.waiting_for_response.insert(id, on_result);
+                            drop(locked_pending);
+                            tokio_tungstenite::tungstenite::Message::Binary(message)
+                        }
+                        Outgoing::Ping => tokio_tungstenite::tungstenite::Message::Ping(vec![]),
+                    };
                     write_half
-                        .send(tokio_tungstenite::tungstenite::Message::Binary(next.1))
+                        .send(message)
                         .await
                         .map_err(|x| WebSocketClientError::Write(Arc::new(x)))?;
                 }
@@ -131,35 +252,55 @@ impl DecthingsClientWebsocket {
                         Err(e) => return Err::<(), _>(e),
                         Ok(val) => val,
                     };
+                    *last_received.lock().unwrap() = Instant::now();
                     let binary = match next {
                         tokio_tungstenite::tungstenite::Message::Binary(bin) => bin,
                         tokio_tungstenite::tungstenite::Message::Text(text) => text.into_bytes(),
                         _ => continue,
                     };
-                    let (rpc_response_or_event, first_blob, blobs) =
-                        super::protocol::deserialize_for_websocket(bytes::Bytes::from(binary))
-                            .map_err(|_| WebSocketClientError::InvalidMessage)?;
+                    #[cfg(feature = "tracing")]
+                    let message_len = binary.len();
+                    let invalid_frame = |e| {
+                        #[cfg(feature = "tracing")]
+                        tracing::warn!(
+                            len = message_len,
+                            error = %e,
+                            "Received an invalid WebSocket message"
+                        );
+                        WebSocketClientError::InvalidFrame(e)
+                    };
+                    // Only a message whose header cannot be read closes the connection. Otherwise
+                    // the error is passed to the request the message belongs to.
+                    let (rpc_response_or_event, body) = super::protocol::deserialize_for_websocket(
+                        bytes::Bytes::from(binary),
+                        &options.decode_limits,
+                    )
+                    .map_err(invalid_frame)?;
+                    let body = body.map_err(invalid_frame);
                     match rpc_response_or_event {
                         super::protocol::RpcResponseOrEvent::RpcResponse(id) => {
                             // RPC response message
-                            let mut locked_waiting_for_response = waiting_for_response.lock().await;
-                            let maybe_waiting = locked_waiting_for_response.remove(&id);
-                            drop(locked_waiting_for_response);
+                            let mut locked_pending = pending.lock().await;
+                            let maybe_waiting = locked_pending.waiting_for_response.remove(&id);
+                            drop(locked_pending);
 
                             if let Some(waiting) = maybe_waiting {
-                                let state_modification = (waiting)(Ok((first_blob, blobs)));
+                                let state_modification = (waiting)(body);
+                                #[cfg(feature = "tracing")]
+                                trace_state_modification(&state_modification);
 
                                 let mut state_locked = state2.lock().await;
                                 for remove_event in state_modification.remove_events {
                                     state_locked.events.remove(&remove_event);
                                 }
-                                for add_event in state_modification.add_events {
-                                    state_locked.events.insert(add_event);
+                                for (event_api, event_id) in state_modification.add_events {
+                                    state_locked.events.insert(event_id, event_api);
                                 }
                                 state_locked.requests.remove(&id);
                                 if state_locked.requests.is_empty()
                                     && state_locked.events.is_empty()
                                 {
+                                    drop(state_locked);
                                     remove_if_unused().await;
                                 }
                             }
@@ -167,14 +308,33 @@ impl DecthingsClientWebsocket {
                         super::protocol::RpcResponseOrEvent::Event(api) => {
                             // Event message
                             if let Some(event_listeners) = weak_event_listeners.upgrade() {
-                                let (parsed, state_modification) =
-                                    super::event::DecthingsEvent::deserialize(
-                                        &api,
-                                        &first_blob,
-                                        blobs,
-                                    )
-                                    .map_err(|_| WebSocketClientError::InvalidMessage)?;
+                                // An event which cannot be parsed is skipped, since the
+                                // connection may still be used for other requests and events.
+                                let Ok((parsed, state_modification)) =
+                                    body.map_err(|_| ()).and_then(|(first_blob, blobs)| {
+                                        super::event::DecthingsEvent::deserialize(
+                                            &api,
+                                            &first_blob,
+                                            blobs,
+                                        )
+                                    })
+                                else {
+                                    #[cfg(feature = "tracing")]
+                                    tracing::warn!(
+                                        api = %String::from_utf8_lossy(&api),
+                                        "Received an invalid event"
+                                    );
+                                    continue;
+                                };
 
+                                #[cfg(feature = "tracing")]
+                                {
+                                    tracing::trace!(
+                                        api = %String::from_utf8_lossy(&api),
+                                        "Dispatching event"
+                                    );
+                                    trace_state_modification(&state_modification);
+                                }
                                 event_listeners.call(&parsed).await;
 
                                 if !state_modification.add_events.is_empty()
@@ -184,8 +344,8 @@ impl DecthingsClientWebsocket {
                                     for remove_event in state_modification.remove_events {
                                         state_locked.events.remove(&remove_event);
                                     }
-                                    for add_event in state_modification.add_events {
-                                        state_locked.events.insert(add_event);
+                                    for (api, id) in state_modification.add_events {
+                                        state_locked.events.insert(id, api);
                                     }
                                     if state_locked.requests.is_empty()
                                         && state_locked.events.is_empty()
@@ -200,17 +360,99 @@ impl DecthingsClientWebsocket {
                 }
             };
 
-            let e = futures::try_join!(write_fut, read_fut).unwrap_err();
+            let cancel_fut = async {
+                while let Some(id) = cancel_rx.recv().await {
+                    let mut state_locked = state2.lock().await;
+                    if !state_locked.requests.remove(&id) {
+                        // Already finished
+                        continue;
+                    }
+                    let is_unused =
+                        state_locked.requests.is_empty() && state_locked.events.is_empty();
+                    drop(state_locked);
+
+                    let mut locked_pending = pending.lock().await;
+                    if locked_pending.waiting_for_response.remove(&id).is_none() {
+                        locked_pending.cancelled.insert(id);
+                    }
+                    drop(locked_pending);
+                    #[cfg(feature = "tracing")]
+                    tracing::debug!(request_id = id, "Cancelled WebSocket request");
+
+                    if is_unused {
+                        remove_if_unused().await;
+                    }
+                }
+                Ok::<_, WebSocketClientError>(())
+            };
+
+            // Any message counts as an answer to a ping, since a busy connection may receive
+            // the pong after other messages.
+            let heartbeat_fut = async {
+                let Some(keepalive) = &options.keepalive else {
+                    return futures::future::pending().await;
+                };
+                loop {
+                    tokio::time::sleep(keepalive.ping_interval).await;
+                    let Some(ping_tx) = ping_tx.upgrade() else {
+                        return futures::future::pending().await;
+                    };
+                    let ping_sent = Instant::now();
+                    // The queue of outgoing messages is full if the connection is stuck, so the
+                    // time spent waiting for it counts towards the pong timeout.
+                    let deadline =
+                        tokio::time::Instant::from_std(ping_sent) + keepalive.pong_timeout;
+                    tokio::time::timeout_at(deadline, ping_tx.send(Outgoing::Ping))
+                        .await
+                        .ok();
+                    drop(ping_tx);
+                    tokio::time::sleep_until(deadline).await;
+                    if *last_received.lock().unwrap() < ping_sent {
+                        #[cfg(feature = "tracing")]
+                        tracing::warn!("WebSocket heartbeat timed out");
+                        return Err::<(), _>(WebSocketClientError::ConnectionLost);
+                    }
+                }
+            };
+
+            let e = futures::try_join!(write_fut, read_fut, cancel_fut, heartbeat_fut).unwrap_err();
+
+            let subscriptions: Vec<_> = {
+                let mut state_locked = state2.lock().await;
+                state_locked
+                    .events
+                    .drain()
+                    .map(|(id, api)| (api, id))
+                    .collect()
+            };
+            #[cfg(feature = "tracing")]
+            if subscriptions.is_empty() {
+                tracing::debug!(reason = ?e, "WebSocket disconnected");
+            } else {
+                tracing::warn!(
+                    reason = ?e,
+                    subscriptions = subscriptions.len(),
+                    "WebSocket disconnected, event subscriptions were removed"
+                );
+            }
+            on_disconnected(subscriptions).await;
 
-            on_disconnected().await;
+            // Requests which were sent will never receive a response.
+            let waiting = std::mem::take(&mut pending.lock().await.waiting_for_response);
+            for (_, on_result) in waiting {
+                on_result(Err(WebSocketClientError::ConnectionLost));
+            }
 
-            while let Some(next_method_call) = call_method_rx.recv().await {
-                (next_method_call.2)(Err(e.clone()));
+            while let Some(outgoing) = call_method_rx.recv().await {
+                if let Outgoing::Call(_, _, on_result) = outgoing {
+                    on_result(Err(e.clone()));
+                }
             }
         });
         Self {
             state,
             call_method_tx,
+            cancel_tx,
         }
     }
 
@@ -221,14 +463,8 @@ impl DecthingsClientWebsocket {
         params: P,
         api_key: Option<&str>,
         data: impl AsRef<[D]>,
-        on_result: Box<
-            dyn FnOnce(
-                    Result<(bytes::Bytes, Vec<bytes::Bytes>), WebSocketClientError>,
-                ) -> StateModification
-                + Send
-                + 'static,
-        >,
-    ) {
+        on_result: ResultCallback,
+    ) -> u32 {
         let mut state_locked = self.state.lock().await;
 
         let id = state_locked.request_id_counter;
@@ -244,9 +480,16 @@ impl DecthingsClientWebsocket {
         drop(data);
 
         self.call_method_tx
-            .send((id, serialized, on_result))
+            .send(Outgoing::Call(id, serialized, on_result))
             .await
             .ok();
+        id
+    }
+
+    /// Stop waiting for the response to the request with the given id. The result callback will
+    /// not be called.
+    pub(crate) fn cancel(&self, id: u32) {
+        self.cancel_tx.send(id).ok();
     }
 
     pub(crate) async fn is_unused(&self) -> bool {
@@ -254,3 +497,176 @@ impl DecthingsClientWebsocket {
         state_locked.events.is_empty() && state_locked.requests.is_empty()
     }
 }
+
+/// Cancels a WebSocket request when dropped, unless disarmed.
+pub(crate) struct CancelOnDrop<'a> {
+    ws: &'a DecthingsClientWebsocket,
+    id: u32,
+    armed: bool,
+}
+
+impl<'a> CancelOnDrop<'a> {
+    pub(crate) fn new(ws: &'a DecthingsClientWebsocket, id: u32) -> Self {
+        Self {
+            ws,
+            id,
+            armed: true,
+        }
+    }
+
+    pub(crate) fn disarm(&mut self) {
+        self.armed = false;
+    }
+}
+
+impl Drop for CancelOnDrop<'_> {
+    fn drop(&mut self) {
+        if self.armed {
+            self.ws.cancel(self.id);
+        }
+    }
+}
+
+#[cfg(feature = "tracing")]
+fn trace_state_modification(state_modification: &StateModification) {
+    for (api, id) in &state_modification.add_events {
+        tracing::debug!(api, id, "Subscribed to events");
+    }
+    for id in &state_modification.remove_events {
+        tracing::debug!(id, "Unsubscribed from events");
+    }
+}
+
+#[cfg(all(feature = "mock", feature = "spawned"))]
+#[tokio::test]
+async fn test_keepalive() {
+    use crate::client::{
+        DecthingsClient, DecthingsClientError, DecthingsClientOptions, DecthingsRpcError,
+        RpcProtocol, event::DecthingsEvent, rpc::spawned,
+    };
+
+    // A server which accepts the connection and then stops answering, like a half-open
+    // connection.
+    let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let address = listener.local_addr().unwrap();
+    tokio::spawn(async move {
+        let (stream, _) = listener.accept().await.unwrap();
+        let mut ws = tokio_tungstenite::accept_async(stream).await.unwrap();
+        ws.next().await;
+        std::future::pending::<()>().await;
+        drop(ws);
+    });
+
+    let client = DecthingsClient::new(DecthingsClientOptions {
+        ws_server_address: format!("ws://{address}"),
+        ws_keepalive: Some(Keepalive {
+            ping_interval: Duration::from_millis(50),
+            pong_timeout: Duration::from_millis(50),
+        }),
+        default_protocol: RpcProtocol::Ws,
+        ..Default::default()
+    });
+    let (removed_tx, mut removed_rx) = tokio::sync::mpsc::unbounded_channel();
+    let _disposer = client
+        .on_event(move |event| {
+            if let DecthingsEvent::SubscriptionsRemoved = event {
+                removed_tx.send(()).ok();
+            }
+        })
+        .await;
+
+    let res = tokio::time::timeout(
+        Duration::from_secs(5),
+        client
+            .spawned
+            .get_spawned_commands(spawned::GetSpawnedCommandsParams::<&str> {
+                spawned_command_ids: None,
+            }),
+    )
+    .await
+    .expect("the request should fail when the heartbeat times out");
+    assert!(matches!(
+        res,
+        Err(DecthingsRpcError::Request(
+            DecthingsClientError::ConnectionLost
+        ))
+    ));
+    removed_rx.recv().await.unwrap();
+}
+
+#[cfg(all(feature = "mock", feature = "spawned"))]
+#[tokio::test]
+async fn test_decode_limits() {
+    use crate::{
+        client::{
+            DecodeLimits, DecthingsClient, DecthingsClientError, DecthingsClientOptions,
+            DecthingsRpcError, RpcProtocol, event::DecthingsEvent, rpc::spawned,
+        },
+        mock::{MockResponse, MockServer},
+    };
+
+    let server = MockServer::start().await.unwrap();
+    server
+        .handle("Spawned", "subscribeToEvents", |_| {
+            MockResponse::result(serde_json::json!({}))
+        })
+        .await;
+    server
+        .handle("Spawned", "getSpawnedCommands", |_| {
+            MockResponse::result(serde_json::json!({ "spawnedCommands": [], "x": "x".repeat(100) }))
+        })
+        .await;
+
+    let client = DecthingsClient::new(DecthingsClientOptions {
+        decode_limits: DecodeLimits {
+            max_blobs: Some(1),
+            // The event below is 54 bytes, and the response is over 100 bytes.
+            max_blob_size: Some(80),
+        },
+        default_protocol: RpcProtocol::Ws,
+        ..server.client_options()
+    });
+    let (tx, mut rx) = tokio::sync::mpsc::unbounded_channel();
+    let _disposer = client
+        .on_event(move |event| {
+            tx.send(event.clone()).ok();
+        })
+        .await;
+    client
+        .spawned
+        .subscribe_to_events(spawned::SpawnedSubscribeToEventsParams {
+            spawned_command_id: "cmd",
+        })
+        .await
+        .unwrap();
+
+    let res = client
+        .spawned
+        .get_spawned_commands(spawned::GetSpawnedCommandsParams::<&str> {
+            spawned_command_ids: None,
+        })
+        .await;
+    assert!(matches!(
+        res,
+        Err(DecthingsRpcError::Request(
+            DecthingsClientError::InvalidFrame(_)
+        ))
+    ));
+
+    // Only the request failed, and the connection is still used for events.
+    server
+        .push_event(
+            "Spawned",
+            serde_json::json!({ "event": "stdout", "params": { "spawnedCommandId": "cmd" } }),
+            [b"out".to_vec()],
+        )
+        .await;
+    let event = tokio::time::timeout(std::time::Duration::from_secs(5), rx.recv())
+        .await
+        .unwrap()
+        .unwrap();
+    assert!(matches!(
+        event,
+        DecthingsEvent::Spawned(spawned::SpawnedEvent::Stdout { .. })
+    ));
+}
@@ -3,5 +3,8 @@ pub use ndarray;
 #[cfg(feature = "client")]
 pub mod client;
 
+#[cfg(feature = "mock")]
+pub mod mock;
+
 pub mod tensor;
 mod varint;
@@ -0,0 +1,604 @@
+//! An in-process mock of the Decthings API, for use in tests.
+//!
+//! [`MockServer`] listens on a local port and speaks the same HTTP and WebSocket protocol as
+//! Decthings, so a regular [`DecthingsClient`](crate::client::DecthingsClient) can be pointed at
+//! it. Register a handler for each method you want to answer using [`MockServer::handle`], and
+//! push events to connected WebSockets using [`MockServer::push_event`].
+
+use std::{
+    collections::HashMap,
+    convert::Infallible,
+    net::SocketAddr,
+    sync::{
+        Arc,
+        atomic::{AtomicU64, Ordering},
+    },
+};
+
+use futures::{SinkExt, StreamExt};
+use hyper::{Body, Request, Response, StatusCode, header};
+use tokio::sync::{Mutex, RwLock};
+use tokio_tungstenite::tungstenite::{Message, handshake::derive_accept_key, protocol::Role};
+
+use crate::client::{
+    DecthingsClient, DecthingsClientOptions,
+    compression::{self, Compression, CompressionAlgorithm},
+    protocol,
+};
+
+/// The protocol a request was received over.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MockProtocol {
+    Http,
+    Ws,
+}
+
+/// A request received by the mock server.
+#[derive(Debug, Clone)]
+pub struct MockRequest {
+    pub api: String,
+    pub method: String,
+    pub params: serde_json::Value,
+    /// Binary blobs sent along with the params.
+    pub data: Vec<bytes::Bytes>,
+    pub api_key: Option<String>,
+    pub protocol: MockProtocol,
+}
+
+/// A response to send back from a mock handler.
+#[derive(Debug, Clone)]
+pub struct MockResponse {
+    body: serde_json::Value,
+    data: Vec<bytes::Bytes>,
+    delay: Option<std::time::Duration>,
+}
+
+impl MockResponse {
+    /// A successful response with the given result.
+    pub fn result(result: impl serde::Serialize) -> Self {
+        Self {
+            body: serde_json::json!({ "result": result }),
+            data: vec![],
+            delay: None,
+        }
+    }
+
+    /// An error response. The error should contain a `code`, for example
+    /// `{ "code": "model_not_found" }`.
+    pub fn error(error: impl serde::Serialize) -> Self {
+        Self {
+            body: serde_json::json!({ "error": error }),
+            data: vec![],
+            delay: None,
+        }
+    }
+
+    /// An error response with only a code, such as "model_not_found".
+    pub fn error_code(code: &str) -> Self {
+        Self::error(serde_json::json!({ "code": code }))
+    }
+
+    /// Binary blobs to send along with the response.
+    pub fn with_data(mut self, data: impl IntoIterator<Item = impl Into<bytes::Bytes>>) -> Self {
+        self.data = data.into_iter().map(Into::into).collect();
+        self
+    }
+
+    /// Wait for the given duration before sending the response.
+    pub fn with_delay(mut self, delay: std::time::Duration) -> Self {
+        self.delay = Some(delay);
+        self
+    }
+}
+
+type MockHandler = Arc<dyn Fn(MockRequest) -> MockResponse + Send + Sync>;
+
+struct MockServerState {
+    handlers: RwLock<HashMap<(String, String), MockHandler>>,
+    sockets: Mutex<HashMap<u64, tokio::sync::mpsc::UnboundedSender<Vec<u8>>>>,
+    socket_id_counter: AtomicU64,
+    shutdown_rx: tokio::sync::watch::Receiver<bool>,
+}
+
+impl MockServerState {
+    async fn call(&self, request: MockRequest) -> MockResponse {
+        let handler = {
+            let locked = self.handlers.read().await;
+            locked
+                .get(&(request.api.clone(), request.method.clone()))
+                .cloned()
+        };
+        match handler {
+            Some(handler) => handler(request),
+            None => MockResponse::error_code("unknown"),
+        }
+    }
+}
+
+/// A local server which imitates the Decthings API. The server is shut down when dropped.
+pub struct MockServer {
+    address: SocketAddr,
+    state: Arc<MockServerState>,
+    shutdown_tx: tokio::sync::watch::Sender<bool>,
+}
+
+impl MockServer {
+    /// Start the server on a random local port. Must be called from within a Tokio runtime.
+    pub async fn start() -> Result<Self, hyper::Error> {
+        let (shutdown_tx, shutdown_rx) = tokio::sync::watch::channel(false);
+        let state = Arc::new(MockServerState {
+            handlers: RwLock::new(HashMap::new()),
+            sockets: Mutex::new(HashMap::new()),
+            socket_id_counter: AtomicU64::new(0),
+            shutdown_rx: shutdown_rx.clone(),
+        });
+
+        let state_clone = Arc::clone(&state);
+        let make_service = hyper::service::make_service_fn(move |_| {
+            let state = Arc::clone(&state_clone);
+            async move {
+                Ok::<_, Infallible>(hyper::service::service_fn(move |req| {
+                    handle_request(Arc::clone(&state), req)
+                }))
+            }
+        });
+        let server =
+            hyper::Server::try_bind(&SocketAddr::from(([127, 0, 0, 1], 0)))?.serve(make_service);
+        let address = server.local_addr();
+
+        let mut server_shutdown_rx = shutdown_rx;
+        tokio::spawn(server.with_graceful_shutdown(async move {
+            server_shutdown_rx.changed().await.ok();
+        }));
+
+        Ok(Self {
+            address,
+            state,
+            shutdown_tx,
+        })
+    }
+
+    /// Address to use as `DecthingsClientOptions::http_server_address`.
+    pub fn http_server_address(&self) -> String {
+        format!("http://{}/v0", self.address)
+    }
+
+    /// Address to use as `DecthingsClientOptions::ws_server_address`.
+    pub fn ws_server_address(&self) -> String {
+        format!("ws://{}/v0/ws", self.address)
+    }
+
+    /// Client options which point to this server.
+    pub fn client_options(&self) -> DecthingsClientOptions {
+        DecthingsClientOptions {
+            http_server_address: self.http_server_address(),
+            ws_server_address: self.ws_server_address(),
+            ..Default::default()
+        }
+    }
+
+    /// Create a client which is connected to this server.
+    pub fn client(&self) -> DecthingsClient {
+        DecthingsClient::new(self.client_options())
+    }
+
+    /// Set the handler for the method `api`/`method`, for example "Model"/"evaluate". Replaces
+    /// any previous handler for the same method. Methods without a handler respond with the error
+    /// code "unknown".
+    pub async fn handle(
+        &self,
+        api: &str,
+        method: &str,
+        handler: impl Fn(MockRequest) -> MockResponse + Send + Sync + 'static,
+    ) {
+        let mut locked = self.state.handlers.write().await;
+        locked.insert((api.to_owned(), method.to_owned()), Arc::new(handler));
+    }
+
+    /// Send an event to all connected WebSockets. The event should contain `event` and `params`,
+    /// for example `{ "event": "stdout", "params": { "spawnedCommandId": "..." } }`.
+    ///
+    /// Returns the number of WebSockets the event was sent to.
+    pub async fn push_event(
+        &self,
+        api: &str,
+        event: impl serde::Serialize,
+        data: impl IntoIterator<Item = impl Into<bytes::Bytes>>,
+    ) -> usize {
+        let data: Vec<bytes::Bytes> = data.into_iter().map(Into::into).collect();
+        let frame = protocol::serialize_event_for_websocket(
+            api,
+            &serde_json::to_vec(&event).unwrap(),
+            &data,
+        );
+        let locked = self.state.sockets.lock().await;
+        locked
+            .values()
+            .filter(|socket| socket.send(frame.clone()).is_ok())
+            .count()
+    }
+
+    /// The number of WebSockets currently connected to the server.
+    pub async fn connected_websockets(&self) -> usize {
+        self.state.sockets.lock().await.len()
+    }
+
+    /// Close all connected WebSockets, as if the connection was lost.
+    pub async fn disconnect_websockets(&self) {
+        self.state.sockets.lock().await.clear();
+    }
+}
+
+impl Drop for MockServer {
+    fn drop(&mut self) {
+        self.shutdown_tx.send(true).ok();
+    }
+}
+
+fn status_response(status: StatusCode) -> Response<Body> {
+    Response::builder()
+        .status(status)
+        .body(Body::empty())
+        .unwrap()
+}
+
+async fn handle_request(
+    state: Arc<MockServerState>,
+    req: Request<Body>,
+) -> Result<Response<Body>, Infallible> {
+    let is_upgrade = req
+        .headers()
+        .get(header::UPGRADE)
+        .is_some_and(|value| value.as_bytes().eq_ignore_ascii_case(b"websocket"));
+    if is_upgrade {
+        return Ok(upgrade_websocket(state, req));
+    }
+
+    let path = req.uri().path().to_owned();
+    let mut segments = path.rsplit('/');
+    let (Some(method), Some(api)) = (segments.next(), segments.next()) else {
+        return Ok(status_response(StatusCode::NOT_FOUND));
+    };
+    let api_key = req
+        .headers()
+        .get(header::AUTHORIZATION)
+        .and_then(|value| value.to_str().ok())
+        .and_then(|value| value.strip_prefix("Bearer "))
+        .map(str::to_owned);
+    let header_str = |name| {
+        req.headers()
+            .get(name)
+            .and_then(|value| value.to_str().ok())
+    };
+    let Ok(decoder) = compression::Decoder::new(header_str(header::CONTENT_ENCODING), None) else {
+        return Ok(status_response(StatusCode::UNSUPPORTED_MEDIA_TYPE));
+    };
+    let response_compression =
+        header_str(header::ACCEPT_ENCODING).and_then(CompressionAlgorithm::from_accept_encoding);
+
+    let Ok(body) = hyper::body::to_bytes(req.into_body()).await else {
+        return Ok(status_response(StatusCode::BAD_REQUEST));
+    };
+    let body = match decoder {
+        Some(mut decoder) => {
+            let Ok(body) = decoder
+                .write(&body)
+                .and_then(|head| Ok([head, decoder.finish()?].concat()))
+            else {
+                return Ok(status_response(StatusCode::BAD_REQUEST));
+            };
+            body.into()
+        }
+        None => body,
+    };
+    let Ok((params, data)) = protocol::deserialize_request_for_http(body) else {
+        return Ok(status_response(StatusCode::BAD_REQUEST));
+    };
+    let Ok(params) = serde_json::from_slice(&params) else {
+        return Ok(status_response(StatusCode::BAD_REQUEST));
+    };
+
+    let response = state
+        .call(MockRequest {
+            api: api.to_owned(),
+            method: method.to_owned(),
+            params,
+            data,
+            api_key,
+            protocol: MockProtocol::Http,
+        })
+        .await;
+    if let Some(delay) = response.delay {
+        tokio::time::sleep(delay).await;
+    }
+
+    let body = bytes::Bytes::from(protocol::serialize_response_for_http(
+        &serde_json::to_vec(&response.body).unwrap(),
+        &response.data,
+    ));
+    // Responses are compressed like Decthings does, if the client accepts it.
+    let compressed = response_compression.and_then(|algorithm| {
+        let (compressed, stats) =
+            compression::compress(&Compression::new(algorithm), vec![body.clone()]);
+        Some((compressed.concat(), stats?.algorithm))
+    });
+    let builder = Response::builder().header(header::CONTENT_TYPE, "application/octet-stream");
+    Ok(match compressed {
+        Some((compressed, algorithm)) => builder
+            .header(header::CONTENT_ENCODING, algorithm.as_str())
+            .body(compressed.into()),
+        None => builder.body(body.into()),
+    }
+    .unwrap())
+}
+
+fn upgrade_websocket(state: Arc<MockServerState>, req: Request<Body>) -> Response<Body> {
+    let Some(key) = req.headers().get(header::SEC_WEBSOCKET_KEY) else {
+        return status_response(StatusCode::BAD_REQUEST);
+    };
+    let accept = derive_accept_key(key.as_bytes());
+
+    tokio::spawn(async move {
+        if let Ok(upgraded) = hyper::upgrade::on(req).await {
+            let sock =
+                tokio_tungstenite::WebSocketStream::from_raw_socket(upgraded, Role::Server, None)
+                    .await;
+            serve_websocket(state, sock).await;
+        }
+    });
+
+    Response::builder()
+        .status(StatusCode::SWITCHING_PROTOCOLS)
+        .header(header::CONNECTION, "Upgrade")
+        .header(header::UPGRADE, "websocket")
+        .header(header::SEC_WEBSOCKET_ACCEPT, accept)
+        .body(Body::empty())
+        .unwrap()
+}
+
+#[derive(serde::Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct WebSocketRequestMessage {
+    api: String,
+    method: String,
+    #[serde(default)]
+    params: serde_json::Value,
+    api_key: Option<String>,
+}
+
+async fn serve_websocket(
+    state: Arc<MockServerState>,
+    sock: tokio_tungstenite::WebSocketStream<hyper::upgrade::Upgraded>,
+) {
+    let (tx, mut rx) = tokio::sync::mpsc::unbounded_channel();
+    let (delayed_tx, mut delayed_rx) = tokio::sync::mpsc::unbounded_channel();
+    let id = state.socket_id_counter.fetch_add(1, Ordering::Relaxed);
+    state.sockets.lock().await.insert(id, tx);
+
+    let mut shutdown_rx = state.shutdown_rx.clone();
+    let (mut write_half, mut read_half) = sock.split();
+
+    loop {
+        tokio::select! {
+            next = read_half.next() => {
+                let binary = match next {
+                    Some(Ok(Message::Binary(binary))) => binary,
+                    Some(Ok(Message::Close(_))) | Some(Err(_)) | None => break,
+                    Some(Ok(_)) => continue,
+                };
+                let Ok((request_id, message, data)) =
+                    protocol::deserialize_request_for_websocket(binary.into())
+                else {
+                    break;
+                };
+                let Ok(message) = serde_json::from_slice::<WebSocketRequestMessage>(&message)
+                else {
+                    break;
+                };
+                let response = state
+                    .call(MockRequest {
+                        api: message.api,
+                        method: message.method,
+                        params: message.params,
+                        data,
+                        api_key: message.api_key,
+                        protocol: MockProtocol::Ws,
+                    })
+                    .await;
+                let frame = protocol::serialize_response_for_websocket(
+                    request_id,
+                    &serde_json::to_vec(&response.body).unwrap(),
+                    &response.data,
+                );
+                if let Some(delay) = response.delay {
+                    let delayed_tx = delayed_tx.clone();
+                    tokio::spawn(async move {
+                        tokio::time::sleep(delay).await;
+                        delayed_tx.send(frame).ok();
+                    });
+                } else if write_half.send(Message::Binary(frame)).await.is_err() {
+                    break;
+                }
+            }
+            Some(frame) = delayed_rx.recv() => {
+                if write_half.send(Message::Binary(frame)).await.is_err() {
+                    break;
+                }
+            }
+            frame = rx.recv() => {
+                let Some(frame) = frame else {
+                    break;
+                };
+                if write_half.send(Message::Binary(frame)).await.is_err() {
+                    break;
+                }
+            }
+            _ = shutdown_rx.changed() => break,
+        }
+    }
+
+    state.sockets.lock().await.remove(&id);
+    write_half.close().await.ok();
+}
+
+// Tests of client features which work the same way for every API, such as credentials, limits and
+// proxies, call the method "Test"/"ping", so that they do not depend on any API feature.
+#[cfg(test)]
+impl MockServer {
+    /// Answer "Test"/"ping" with an empty result, after `delay` if it is set.
+    pub(crate) async fn handle_ping(&self, delay: Option<std::time::Duration>) {
+        self.handle("Test", "ping", move |_| {
+            let response = MockResponse::result(serde_json::json!({}));
+            match delay {
+                Some(delay) => response.with_delay(delay),
+                None => response,
+            }
+        })
+        .await;
+    }
+}
+
+/// Call "Test"/"ping" using the default protocol of `client`. Panics if the server responds with
+/// an error.
+#[cfg(test)]
+pub(crate) async fn ping(
+    client: &DecthingsClient,
+) -> Result<(), crate::client::DecthingsClientError> {
+    let (response, _) = client
+        .raw_method_call(
+            "Test",
+            "ping",
+            serde_json::json!({}),
+            Vec::<bytes::Bytes>::new(),
+        )
+        .await?;
+    let response: serde_json::Value = serde_json::from_slice(&response).unwrap();
+    assert!(response.get("error").is_none(), "ping failed: {response}");
+    Ok(())
+}
+
+#[cfg(feature = "fs")]
+#[tokio::test]
+async fn test_mock_http() {
+    use crate::client::{DecthingsRpcError, rpc::fs};
+
+    let server = MockServer::start().await.unwrap();
+    server
+        .handle("FS", "read", |req| {
+            assert_eq!(req.protocol, MockProtocol::Http);
+            assert_eq!(req.api_key.as_deref(), Some("key"));
+            if req.params["inode"] == 1 {
+                MockResponse::result(serde_json::json!({})).with_data([&b"hello"[..]])
+            } else {
+                MockResponse::error_code("ESTALE")
+            }
+        })
+        .await;
+    server
+        .handle("FS", "write", |req| {
+            assert_eq!(req.data, vec![bytes::Bytes::from_static(b"data")]);
+            MockResponse::result(serde_json::json!({ "bytesWritten": 4 }))
+        })
+        .await;
+
+    let client = server.client();
+    client.set_api_key("key".to_string()).await;
+
+    let read = |inode| {
+        client.fs.read(fs::ReadParams {
+            model_id: "model",
+            version_id: None,
+            inode,
+            offset: 0,
+            count: 5,
+        })
+    };
+    assert_eq!(read(1).await.unwrap().data, &b"hello"[..]);
+    assert!(matches!(
+        read(2).await,
+        Err(DecthingsRpcError::Rpc(fs::ReadError::ESTALE))
+    ));
+
+    let written = client
+        .fs
+        .write(fs::WriteParams {
+            model_id: "model",
+            inode: 1,
+            data: bytes::Bytes::from_static(b"data"),
+            offset: 0,
+            truncate: None,
+        })
+        .await
+        .unwrap();
+    assert_eq!(written.bytes_written, 4);
+
+    assert!(matches!(
+        client
+            .fs
+            .getattr(fs::GetattrParams {
+                model_id: "model",
+                version_id: None,
+                inode: 1,
+            })
+            .await,
+        Err(DecthingsRpcError::Rpc(fs::GetattrError::Unknown))
+    ));
+}
+
+#[cfg(feature = "spawned")]
+#[tokio::test]
+async fn test_mock_websocket_events() {
+    use crate::client::rpc::{ExecutionLocationProvider, spawned};
+
+    let server = MockServer::start().await.unwrap();
+    server
+        .handle("Spawned", "spawnCommand", |req| {
+            assert_eq!(req.protocol, MockProtocol::Ws);
+            assert_eq!(req.params["command"], "echo");
+            MockResponse::result(serde_json::json!({ "spawnedCommandId": "cmd" }))
+        })
+        .await;
+
+    let client = server.client();
+    let (tx, mut rx) = tokio::sync::mpsc::unbounded_channel();
+    let _disposer = client
+        .on_event(move |event| {
+            tx.send(event.clone()).ok();
+        })
+        .await;
+
+    let res = client
+        .spawned
+        .spawn_command(spawned::SpawnCommandParams {
+            execution_location: ExecutionLocationProvider::PersistentLauncher {
+                persistent_launcher_id: "launcher",
+            },
+            command: "echo",
+            args: &["hello"],
+            options: None,
+            subscribe_to_events: None,
+        })
+        .await
+        .unwrap();
+    assert_eq!(res.spawned_command_id, "cmd");
+
+    let sent = server
+        .push_event(
+            "Spawned",
+            serde_json::json!({ "event": "stdout", "params": { "spawnedCommandId": "cmd" } }),
+            [&b"hello\n"[..]],
+        )
+        .await;
+    assert_eq!(sent, 1);
+
+    match rx.recv().await.unwrap() {
+        crate::client::event::DecthingsEvent::Spawned(spawned::SpawnedEvent::Stdout {
+            spawned_command_id,
+            data,
+        }) => {
+            assert_eq!(spawned_command_id, "cmd");
+            assert_eq!(data, &b"hello\n"[..]);
+        }
+        other => panic!("unexpected event {other:?}"),
+    }
+}
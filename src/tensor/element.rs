@@ -82,7 +82,7 @@ impl<'a> DecthingsElementImage<'a> {
         if format.len() > u8::MAX.into() {
             return Err(SetFormatError::FormatStringTooLong);
         }
-        self.format = format.into();
+        self.format = format;
         Ok(())
     }
 }
@@ -1,10 +1,14 @@
+//! Encoding and decoding of Decthings tensors. This module does not depend on the client, so it
+//! can be used with `default-features = false`, which leaves only serde, ndarray and bytes as
+//! dependencies.
+
 mod element;
 mod tensor_impl;
 
 pub use element::*;
 pub use tensor_impl::*;
 
-use serde::{ser::SerializeSeq, Deserialize, Serialize, Serializer};
+use serde::{Deserialize, Serialize, Serializer, ser::SerializeSeq};
 
 fn serialize_shape<S: Serializer>(
     values: &[Option<u32>],
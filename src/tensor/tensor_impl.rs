@@ -276,7 +276,7 @@ impl<'a> DecthingsTensor<'a> {
     }
 
     /// If this is an image type with length 1, returns the image.
-    pub fn as_image_item(&self) -> Option<&DecthingsElementImage> {
+    pub fn as_image_item(&self) -> Option<&DecthingsElementImage<'_>> {
         if self.len() != 1 {
             return None;
         }
@@ -287,7 +287,7 @@ impl<'a> DecthingsTensor<'a> {
     }
 
     /// If this is a audio type with length 1, returns the audio.
-    pub fn as_audio_item(&self) -> Option<&DecthingsElementAudio> {
+    pub fn as_audio_item(&self) -> Option<&DecthingsElementAudio<'_>> {
         if self.len() != 1 {
             return None;
         }
@@ -298,7 +298,7 @@ impl<'a> DecthingsTensor<'a> {
     }
 
     /// If this is a video type with length 1, returns the video.
-    pub fn as_video_item(&self) -> Option<&DecthingsElementVideo> {
+    pub fn as_video_item(&self) -> Option<&DecthingsElementVideo<'_>> {
         if self.len() != 1 {
             return None;
         }
@@ -642,15 +642,10 @@ impl OwnedDecthingsTensor {
         let mut pos = 2;
 
         for _ in 0..num_dims {
-            if data.len() < pos + 1 {
+            let Some((dim, varint_len)) = crate::varint::deserialize_varint_u64(&data[pos..])
+            else {
                 return Err(DeserializeDecthingsTensorError::UnexpectedEndOfBytes);
             };
-            if data.len()
-                < pos + crate::varint::get_serialized_varint_u64_len(&data[pos..]) as usize
-            {
-                return Err(DeserializeDecthingsTensorError::UnexpectedEndOfBytes);
-            }
-            let (dim, varint_len) = crate::varint::deserialize_varint_u64(&data[pos..]);
             pos += varint_len as usize;
             shape.push(dim.try_into().unwrap());
         }
@@ -690,35 +685,31 @@ impl OwnedDecthingsTensor {
                 pos += crate::varint::get_serialized_varint_u64_len(&data[pos..]) as usize;
 
                 for _ in 0..numel {
-                    if data.len() < pos + 1 {
+                    let Some((len, varint_len)) =
+                        crate::varint::deserialize_varint_u64(&data[pos..])
+                    else {
                         return Err(DeserializeDecthingsTensorError::UnexpectedEndOfBytes);
                     };
-                    if data.len()
-                        < pos + crate::varint::get_serialized_varint_u64_len(&data[pos..]) as usize
-                    {
-                        return Err(DeserializeDecthingsTensorError::UnexpectedEndOfBytes);
-                    }
-                    let (len, varint_len) = crate::varint::deserialize_varint_u64(&data[pos..]);
                     let len: usize = len.try_into().unwrap();
                     pos += varint_len as usize;
-                    if matches!(first_byte, TYPE_SPEC_STRING) {
-                        if let Err(e) = std::str::from_utf8(&data[pos..pos + len]) {
-                            return Err(DeserializeDecthingsTensorError::InvalidBytes(format!(
-                                "The string was not UTF-8: {e:?}"
-                            )));
-                        }
+                    if matches!(first_byte, TYPE_SPEC_STRING)
+                        && let Err(e) = std::str::from_utf8(&data[pos..pos + len])
+                    {
+                        return Err(DeserializeDecthingsTensorError::InvalidBytes(format!(
+                            "The string was not UTF-8: {e:?}"
+                        )));
                     }
                     if matches!(first_byte, TYPE_SPEC_IMAGE) {
                         if len < 1 {
-                            return Err(DeserializeDecthingsTensorError::InvalidBytes(format!(
-                                "Unexpected end of bytes while parsing image format"
-                            )));
+                            return Err(DeserializeDecthingsTensorError::InvalidBytes(
+                                "Unexpected end of bytes while parsing image format".to_string(),
+                            ));
                         }
                         let format_length = data[pos] as usize;
                         if len < 1 + format_length {
-                            return Err(DeserializeDecthingsTensorError::InvalidBytes(format!(
-                                "Unexpected end of bytes while parsing image format"
-                            )));
+                            return Err(DeserializeDecthingsTensorError::InvalidBytes(
+                                "Unexpected end of bytes while parsing image format".to_string(),
+                            ));
                         }
                         if let Err(e) = std::str::from_utf8(&data[pos + 1..pos + 1 + format_length])
                         {
@@ -729,15 +720,15 @@ impl OwnedDecthingsTensor {
                     }
                     if matches!(first_byte, TYPE_SPEC_AUDIO) {
                         if len < 1 {
-                            return Err(DeserializeDecthingsTensorError::InvalidBytes(format!(
-                                "Unexpected end of bytes while parsing audio format"
-                            )));
+                            return Err(DeserializeDecthingsTensorError::InvalidBytes(
+                                "Unexpected end of bytes while parsing audio format".to_string(),
+                            ));
                         }
                         let format_length = data[pos] as usize;
                         if len < 1 + format_length {
-                            return Err(DeserializeDecthingsTensorError::InvalidBytes(format!(
-                                "Unexpected end of bytes while parsing audio format"
-                            )));
+                            return Err(DeserializeDecthingsTensorError::InvalidBytes(
+                                "Unexpected end of bytes while parsing audio format".to_string(),
+                            ));
                         }
                         if let Err(e) = std::str::from_utf8(&data[pos + 1..pos + 1 + format_length])
                         {
@@ -748,15 +739,15 @@ impl OwnedDecthingsTensor {
                     }
                     if matches!(first_byte, TYPE_SPEC_VIDEO) {
                         if len < 1 {
-                            return Err(DeserializeDecthingsTensorError::InvalidBytes(format!(
-                                "Unexpected end of bytes while parsing video format"
-                            )));
+                            return Err(DeserializeDecthingsTensorError::InvalidBytes(
+                                "Unexpected end of bytes while parsing video format".to_string(),
+                            ));
                         }
                         let format_length = data[pos] as usize;
                         if len < 1 + format_length {
-                            return Err(DeserializeDecthingsTensorError::InvalidBytes(format!(
-                                "Unexpected end of bytes while parsing video format"
-                            )));
+                            return Err(DeserializeDecthingsTensorError::InvalidBytes(
+                                "Unexpected end of bytes while parsing video format".to_string(),
+                            ));
                         }
                         if let Err(e) = std::str::from_utf8(&data[pos + 1..pos + 1 + format_length])
                         {
@@ -787,7 +778,8 @@ impl OwnedDecthingsTensor {
         let mut pos = 2;
 
         for _ in 0..num_dims {
-            let (dim, varint_len) = crate::varint::deserialize_varint_u64(&self.data[pos..]);
+            let (dim, varint_len) =
+                crate::varint::deserialize_varint_u64(&self.data[pos..]).unwrap();
             pos += varint_len as usize;
             shape.push(dim.try_into().unwrap());
         }
@@ -833,34 +825,30 @@ impl OwnedDecthingsTensor {
 
         match first_byte {
             TYPE_SPEC_F32 => {
-                return sized_into_tensor(&shape, &self.data, pos, numel, DecthingsTensor::F32);
+                sized_into_tensor(&shape, &self.data, pos, numel, DecthingsTensor::F32)
             }
             TYPE_SPEC_F64 => {
-                return sized_into_tensor(&shape, &self.data, pos, numel, DecthingsTensor::F64);
-            }
-            TYPE_SPEC_I8 => {
-                return sized_into_tensor(&shape, &self.data, pos, numel, DecthingsTensor::I8);
+                sized_into_tensor(&shape, &self.data, pos, numel, DecthingsTensor::F64)
             }
+            TYPE_SPEC_I8 => sized_into_tensor(&shape, &self.data, pos, numel, DecthingsTensor::I8),
             TYPE_SPEC_I16 => {
-                return sized_into_tensor(&shape, &self.data, pos, numel, DecthingsTensor::I16);
+                sized_into_tensor(&shape, &self.data, pos, numel, DecthingsTensor::I16)
             }
             TYPE_SPEC_I32 => {
-                return sized_into_tensor(&shape, &self.data, pos, numel, DecthingsTensor::I32);
+                sized_into_tensor(&shape, &self.data, pos, numel, DecthingsTensor::I32)
             }
             TYPE_SPEC_I64 => {
-                return sized_into_tensor(&shape, &self.data, pos, numel, DecthingsTensor::I64);
-            }
-            TYPE_SPEC_U8 => {
-                return sized_into_tensor(&shape, &self.data, pos, numel, DecthingsTensor::U8);
+                sized_into_tensor(&shape, &self.data, pos, numel, DecthingsTensor::I64)
             }
+            TYPE_SPEC_U8 => sized_into_tensor(&shape, &self.data, pos, numel, DecthingsTensor::U8),
             TYPE_SPEC_U16 => {
-                return sized_into_tensor(&shape, &self.data, pos, numel, DecthingsTensor::U16);
+                sized_into_tensor(&shape, &self.data, pos, numel, DecthingsTensor::U16)
             }
             TYPE_SPEC_U32 => {
-                return sized_into_tensor(&shape, &self.data, pos, numel, DecthingsTensor::U32);
+                sized_into_tensor(&shape, &self.data, pos, numel, DecthingsTensor::U32)
             }
             TYPE_SPEC_U64 => {
-                return sized_into_tensor(&shape, &self.data, pos, numel, DecthingsTensor::U64);
+                sized_into_tensor(&shape, &self.data, pos, numel, DecthingsTensor::U64)
             }
             TYPE_SPEC_STRING => {
                 pos += crate::varint::get_serialized_varint_u64_len(&self.data[pos..]) as usize;
@@ -868,18 +856,18 @@ impl OwnedDecthingsTensor {
                 let mut strings = Vec::with_capacity(numel);
                 for _ in 0..numel {
                     let (len, varint_len) =
-                        crate::varint::deserialize_varint_u64(&self.data[pos..]);
+                        crate::varint::deserialize_varint_u64(&self.data[pos..]).unwrap();
                     let len: usize = len.try_into().unwrap();
                     pos += varint_len as usize;
                     strings.push(std::str::from_utf8(&self.data[pos..pos + len]).unwrap());
                     pos += len;
                 }
-                return DecthingsTensor::String(
+                DecthingsTensor::String(
                     Array::from_vec(strings)
                         .into_shape(IxDyn(&shape))
                         .unwrap()
                         .into(),
-                );
+                )
             }
             TYPE_SPEC_BINARY => {
                 pos += crate::varint::get_serialized_varint_u64_len(&self.data[pos..]) as usize;
@@ -887,30 +875,30 @@ impl OwnedDecthingsTensor {
                 let mut binaries = Vec::with_capacity(numel);
                 for _ in 0..numel {
                     let (len, varint_len) =
-                        crate::varint::deserialize_varint_u64(&self.data[pos..]);
+                        crate::varint::deserialize_varint_u64(&self.data[pos..]).unwrap();
                     let len: usize = len.try_into().unwrap();
                     pos += varint_len as usize;
                     binaries.push(&self.data[pos..pos + len]);
                     pos += len;
                 }
-                return DecthingsTensor::Binary(
+                DecthingsTensor::Binary(
                     Array::from_vec(binaries)
                         .into_shape(IxDyn(&shape))
                         .unwrap()
                         .into(),
-                );
+                )
             }
             TYPE_SPEC_BOOLEAN => {
                 let converted = self.data[pos..pos + numel * std::mem::size_of::<u8>()]
                     .as_slice_of::<u8>()
                     .unwrap();
-                return DecthingsTensor::Boolean(
+                DecthingsTensor::Boolean(
                     ArrayView::from(converted)
                         .into_shape(IxDyn(&shape))
                         .unwrap()
                         .map(|&x| x != 0)
                         .into(),
-                );
+                )
             }
             TYPE_SPEC_IMAGE => {
                 pos += crate::varint::get_serialized_varint_u64_len(&self.data[pos..]) as usize;
@@ -918,7 +906,7 @@ impl OwnedDecthingsTensor {
                 let mut images = Vec::with_capacity(numel);
                 for _ in 0..numel {
                     let (len, varint_len) =
-                        crate::varint::deserialize_varint_u64(&self.data[pos..]);
+                        crate::varint::deserialize_varint_u64(&self.data[pos..]).unwrap();
                     let len: usize = len.try_into().unwrap();
                     pos += varint_len as usize;
                     let format_length = self.data[pos] as usize;
@@ -933,12 +921,12 @@ impl OwnedDecthingsTensor {
                     );
                     pos += len;
                 }
-                return DecthingsTensor::Image(
+                DecthingsTensor::Image(
                     Array::from_vec(images)
                         .into_shape(IxDyn(&shape))
                         .unwrap()
                         .into(),
-                );
+                )
             }
             TYPE_SPEC_AUDIO => {
                 pos += crate::varint::get_serialized_varint_u64_len(&self.data[pos..]) as usize;
@@ -946,7 +934,7 @@ impl OwnedDecthingsTensor {
                 let mut audios = Vec::with_capacity(numel);
                 for _ in 0..numel {
                     let (len, varint_len) =
-                        crate::varint::deserialize_varint_u64(&self.data[pos..]);
+                        crate::varint::deserialize_varint_u64(&self.data[pos..]).unwrap();
                     let len: usize = len.try_into().unwrap();
                     pos += varint_len as usize;
                     let format_length = self.data[pos] as usize;
@@ -961,12 +949,12 @@ impl OwnedDecthingsTensor {
                     );
                     pos += len;
                 }
-                return DecthingsTensor::Audio(
+                DecthingsTensor::Audio(
                     Array::from_vec(audios)
                         .into_shape(IxDyn(&shape))
                         .unwrap()
                         .into(),
-                );
+                )
             }
             TYPE_SPEC_VIDEO => {
                 pos += crate::varint::get_serialized_varint_u64_len(&self.data[pos..]) as usize;
@@ -974,7 +962,7 @@ impl OwnedDecthingsTensor {
                 let mut videos = Vec::with_capacity(numel);
                 for _ in 0..numel {
                     let (len, varint_len) =
-                        crate::varint::deserialize_varint_u64(&self.data[pos..]);
+                        crate::varint::deserialize_varint_u64(&self.data[pos..]).unwrap();
                     let len: usize = len.try_into().unwrap();
                     pos += varint_len as usize;
                     let format_length = self.data[pos] as usize;
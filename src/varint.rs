@@ -1,3 +1,4 @@
+/// Returns the length of the varint at the start of `data`, which must not be empty.
 pub(crate) fn get_serialized_varint_u64_len(data: &[u8]) -> u8 {
     let first = data[0];
     match first {
@@ -38,14 +39,19 @@ pub(crate) fn append_varint_u64(value: u64, data: &mut Vec<u8>) {
     }
 }
 
-pub(crate) fn deserialize_varint_u64(data: &[u8]) -> (u64, u8) {
-    let first = data[0];
-    match first {
-        253 => (u16::from_be_bytes(data[1..3].try_into().unwrap()) as u64, 3),
-        254 => (u32::from_be_bytes(data[1..5].try_into().unwrap()) as u64, 5),
-        255 => (u64::from_be_bytes(data[1..9].try_into().unwrap()), 9),
-        _ => (first as u64, 1),
-    }
+/// Returns the value of the varint at the start of `data` and its length, or None if `data` ends
+/// before the varint.
+pub(crate) fn deserialize_varint_u64(data: &[u8]) -> Option<(u64, u8)> {
+    let first = *data.first()?;
+    let len = get_serialized_varint_u64_len(data);
+    let rest = data.get(1..len as usize)?;
+    let value = match first {
+        253 => u16::from_be_bytes(rest.try_into().unwrap()) as u64,
+        254 => u32::from_be_bytes(rest.try_into().unwrap()) as u64,
+        255 => u64::from_be_bytes(rest.try_into().unwrap()),
+        _ => first as u64,
+    };
+    Some((value, len))
 }
 
 #[test]
@@ -56,7 +62,7 @@ fn test_varint_u64() {
         println!("serialized {} into {:?}", val, serialized);
         assert_eq!(serialized.len(), get_varint_u64_len(val) as usize);
         assert_eq!(serialized, expected_val);
-        let deserialized = deserialize_varint_u64(&serialized);
+        let deserialized = deserialize_varint_u64(&serialized).unwrap();
         assert_eq!(deserialized.1 as usize, serialized.len());
         assert_eq!(deserialized.0, val);
         assert_eq!(
@@ -64,6 +70,9 @@ fn test_varint_u64() {
             expected_val.len()
         );
         assert_eq!(get_varint_u64_len(val) as usize, expected_val.len());
+        for len in 0..serialized.len() {
+            assert_eq!(deserialize_varint_u64(&serialized[..len]), None);
+        }
     }
 
     inner_test(0, vec![0]);